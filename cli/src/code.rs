@@ -0,0 +1,185 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! The code sets covered by the library, and the identification of which a code belongs to
+//!
+//! Codes are accepted in any case, so `us`, `US` and `Us` are each taken to be the alpha-2 code of
+//! the United States, and numeric codes may be given with or without leading zeroes.
+
+use iso::{
+    country::{Iso3166_1_alpha_2, Iso3166_1_alpha_3},
+    language::{Iso639_1, Iso639_2b, Iso639_2t, Iso639_3},
+};
+use std::convert::TryFrom;
+
+/// A code set covered by the library
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum CodeSet {
+    /// ISO 3166-1 alpha-2
+    Alpha2,
+
+    /// ISO 3166-1 alpha-3
+    Alpha3,
+
+    /// ISO 3166-1 numeric
+    Numeric,
+
+    /// ISO 639-1
+    Iso639_1,
+
+    /// ISO 639-3
+    Iso639_3,
+
+    /// ISO 639-2/T
+    Iso639_2t,
+
+    /// ISO 639-2/B
+    Iso639_2b,
+}
+
+impl CodeSet {
+    /// Every code set, in the order a code is tried against them when identifying it
+    pub const ALL: [Self; 7] = [
+        Self::Alpha2,
+        Self::Alpha3,
+        Self::Numeric,
+        Self::Iso639_1,
+        Self::Iso639_3,
+        Self::Iso639_2t,
+        Self::Iso639_2b,
+    ];
+
+    /// Returns the name the code set is given on the command line, such as `alpha-2` or `639-1`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Alpha2 => "alpha-2",
+            Self::Alpha3 => "alpha-3",
+            Self::Numeric => "numeric",
+            Self::Iso639_1 => "639-1",
+            Self::Iso639_3 => "639-3",
+            Self::Iso639_2t => "639-2t",
+            Self::Iso639_2b => "639-2b",
+        }
+    }
+
+    /// Parses the name of a code set, which may also be prefixed with `iso` (such as `iso639-1`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        let name = name.trim_start_matches("iso").trim_start_matches('-');
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|set| set.name() == name || set.name().trim_start_matches("639-") == name)
+    }
+
+    /// Returns whether or not the code set is made up of country codes
+    pub fn is_country(self) -> bool {
+        matches!(self, Self::Alpha2 | Self::Alpha3 | Self::Numeric)
+    }
+
+    /// Returns what a code within the code set identifies, if it is within it
+    pub fn parse(self, code: &str) -> Option<Entity> {
+        let upper = code.to_ascii_uppercase();
+        let lower = code.to_ascii_lowercase();
+        let language = |language: Option<Iso639_3>| language.map(Entity::Language);
+        match self {
+            Self::Alpha2 => Iso3166_1_alpha_2::from_bytes(upper.as_bytes()).map(Entity::Country),
+            Self::Alpha3 => Iso3166_1_alpha_3::from_bytes(upper.as_bytes())
+                .map(|country| Entity::Country(country.into())),
+            Self::Numeric => Some(code)
+                .filter(|code| {
+                    (1..=3).contains(&code.len()) && code.bytes().all(|c| c.is_ascii_digit())
+                })
+                .and_then(|code| code.parse::<u16>().ok())
+                .and_then(|code| Iso3166_1_alpha_2::try_from(code).ok())
+                .map(Entity::Country),
+            Self::Iso639_1 => language(
+                Iso639_1::from_bytes(lower.as_bytes())
+                    .and_then(|language| Iso639_3::try_from(language).ok()),
+            ),
+            Self::Iso639_3 => language(Iso639_3::from_bytes(lower.as_bytes())),
+            Self::Iso639_2t => language(
+                Iso639_2t::from_bytes(lower.as_bytes())
+                    .and_then(|language| Iso639_3::try_from(language).ok()),
+            ),
+            Self::Iso639_2b => language(
+                Iso639_2b::from_bytes(lower.as_bytes())
+                    .and_then(|language| Iso639_3::try_from(language).ok()),
+            ),
+        }
+    }
+}
+
+/// A country or language, as identified by a code
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Entity {
+    /// A country, by its alpha-2 code
+    Country(Iso3166_1_alpha_2),
+
+    /// A language, by its ISO 639-3 code
+    Language(Iso639_3),
+}
+
+impl Entity {
+    /// Returns whether or not the entity is a country
+    pub fn is_country(self) -> bool {
+        matches!(self, Self::Country(_))
+    }
+
+    /// Returns the name of the country or language (or its code, if names are left out by the
+    /// `without-names` feature)
+    #[cfg(not(feature = "without-names"))]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Country(country) => country.name(),
+            Self::Language(language) => language.name(),
+        }
+    }
+
+    /// Returns the name of the country or language (or its code, if names are left out by the
+    /// `without-names` feature)
+    #[cfg(feature = "without-names")]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Country(country) => country.code(),
+            Self::Language(language) => language.code(),
+        }
+    }
+
+    /// Returns the code of the entity within a code set, if it has one there
+    pub fn code(self, set: CodeSet) -> Option<String> {
+        match (self, set) {
+            (Self::Country(country), CodeSet::Alpha2) => Some(country.code().to_string()),
+            (Self::Country(country), CodeSet::Alpha3) => {
+                Some(Iso3166_1_alpha_3::from(country).code().to_string())
+            }
+            (Self::Country(country), CodeSet::Numeric) => Some(format!("{:03}", country.numeric())),
+            (Self::Language(language), CodeSet::Iso639_1) => Iso639_1::try_from(language)
+                .ok()
+                .map(|language| language.code().to_string()),
+            (Self::Language(language), CodeSet::Iso639_3) => Some(language.code().to_string()),
+            (Self::Language(language), CodeSet::Iso639_2t) => Iso639_2t::try_from(language)
+                .ok()
+                .map(|language| language.code().to_string()),
+            (Self::Language(language), CodeSet::Iso639_2b) => Iso639_2b::try_from(language)
+                .ok()
+                .map(|language| language.code().to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Returns each code set a code is within, alongside what it identifies there
+pub fn identify(code: &str) -> Vec<(CodeSet, Entity)> {
+    CodeSet::ALL
+        .iter()
+        .filter_map(|set| Some((*set, set.parse(code)?)))
+        .collect()
+}
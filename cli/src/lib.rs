@@ -0,0 +1,52 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! The library behind the `iso` command-line tool, which identifies, searches for and normalizes
+//! ISO country and language codes
+//!
+//! The tool itself is a thin layer over this library. Identifying what a code stands for (within
+//! any of the code sets covered by `iso`) is done with [`identify`], searching by name with
+//! [`search`], and validating and normalizing columns of codes within CSV or JSON Lines with the
+//! [`normalize`] module.
+
+#![allow(clippy::cognitive_complexity)]
+#![warn(clippy::cargo_common_metadata)]
+#![warn(clippy::dbg_macro)]
+#![warn(clippy::explicit_deref_methods)]
+#![warn(clippy::filetype_is_file)]
+#![warn(clippy::imprecise_flops)]
+#![warn(clippy::large_stack_arrays)]
+#![warn(clippy::todo)]
+#![warn(clippy::unimplemented)]
+#![deny(clippy::await_holding_lock)]
+#![deny(clippy::cast_lossless)]
+#![deny(clippy::clone_on_ref_ptr)]
+#![deny(clippy::doc_markdown)]
+#![deny(clippy::empty_enums)]
+#![deny(clippy::enum_glob_use)]
+#![deny(clippy::exit)]
+#![deny(clippy::explicit_into_iter_loop)]
+#![deny(clippy::explicit_iter_loop)]
+#![deny(clippy::fallible_impl_from)]
+#![deny(clippy::inefficient_to_string)]
+#![deny(clippy::large_digit_groups)]
+#![deny(clippy::wildcard_dependencies)]
+#![deny(clippy::wildcard_imports)]
+#![deny(clippy::unused_self)]
+#![deny(clippy::single_match_else)]
+#![deny(clippy::option_option)]
+#![deny(clippy::mut_mut)]
+
+pub use code::{identify, CodeSet, Entity};
+pub use search::search;
+
+mod code;
+pub mod normalize;
+mod search;
@@ -0,0 +1,578 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! A command-line tool for looking up, converting and searching ISO country and language codes
+//!
+//! ```text
+//! iso [--format table|json|csv] <command> ...
+//!
+//! iso lookup [--country | --language] <code>...
+//! iso convert [--from <set>] --to <set> <code>...
+//! iso search [--country | --language] [--limit <n>] <name>...
+//! iso list countries [--independent | --dependent] [--sovereign <code>] [--neighbor-of <code>]
+//!                    [--calling-code <code>]
+//! iso list languages [--with <set>]... [--without <set>]...
+//! iso normalize --column <name> --to <set> [--strictness strict|lenient|permissive]
+//!               [--input-format csv|jsonl] [--output <path>] [--report <path>] <input>
+//! ```
+//!
+//! Code sets are named `alpha-2`, `alpha-3` and `numeric` for ISO 3166-1, and `639-1`, `639-2b`,
+//! `639-2t` and `639-3` for ISO 639. Codes are accepted in any case, and a code given without its
+//! set is tried against each set in that order (with `639-3` and `639-2t` tried before `639-2b`),
+//! so `lookup` describes everything a code may stand for. Output is written as an aligned table by
+//! default, or as JSON or CSV with `--format`.
+//!
+//! `normalize` rewrites a column of CSV (or a field of JSON Lines) into codes within a single code
+//! set, reading from standard input when its input is `-` and writing to standard output unless
+//! given `--output`. Values it cannot normalize are left as they are, and reported (in the format
+//! given by `--format`) alongside suggestions of what they may have been meant to be, to the file
+//! given by `--report` or otherwise to standard error.
+//!
+//! The tool exits with a non-zero status when any code given to it was not found (or could not be
+//! converted or normalized), after writing out everything else.
+
+#![allow(clippy::cognitive_complexity)]
+#![warn(clippy::cargo_common_metadata)]
+#![warn(clippy::dbg_macro)]
+#![warn(clippy::explicit_deref_methods)]
+#![warn(clippy::filetype_is_file)]
+#![warn(clippy::imprecise_flops)]
+#![warn(clippy::large_stack_arrays)]
+#![warn(clippy::todo)]
+#![warn(clippy::unimplemented)]
+#![deny(clippy::await_holding_lock)]
+#![deny(clippy::cast_lossless)]
+#![deny(clippy::clone_on_ref_ptr)]
+#![deny(clippy::doc_markdown)]
+#![deny(clippy::empty_enums)]
+#![deny(clippy::enum_glob_use)]
+#![deny(clippy::exit)]
+#![deny(clippy::explicit_into_iter_loop)]
+#![deny(clippy::explicit_iter_loop)]
+#![deny(clippy::fallible_impl_from)]
+#![deny(clippy::inefficient_to_string)]
+#![deny(clippy::large_digit_groups)]
+#![deny(clippy::wildcard_dependencies)]
+#![deny(clippy::wildcard_imports)]
+#![deny(clippy::unused_self)]
+#![deny(clippy::single_match_else)]
+#![deny(clippy::option_option)]
+#![deny(clippy::mut_mut)]
+
+use iso::{
+    country::{Country, Iso3166_1_alpha_2},
+    language::Iso639_3,
+};
+use iso_cli::{
+    identify,
+    normalize::{self, Normalizer, Report, Strictness},
+    CodeSet, Entity,
+};
+use std::{
+    env::args,
+    fs::File,
+    io::{self, stdin, stdout, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    process::ExitCode,
+    result::Result as StdResult,
+};
+
+use record::{Format, Record};
+
+mod record;
+
+/// The usage of the tool
+const USAGE: &str = "\
+usage: iso [--format table|json|csv] <command> ...
+
+commands:
+  lookup [--country | --language] <code>...
+  convert [--from <set>] --to <set> <code>...
+  search [--country | --language] [--limit <n>] <name>...
+  list countries [--independent | --dependent] [--sovereign <code>] [--neighbor-of <code>]
+                 [--calling-code <code>]
+  list languages [--with <set>]... [--without <set>]...
+  normalize --column <name> --to <set> [--strictness strict|lenient|permissive]
+            [--input-format csv|jsonl] [--output <path>] [--report <path>] <input>
+
+code sets: alpha-2, alpha-3, numeric, 639-1, 639-2b, 639-2t, 639-3";
+
+/// The arguments given to the tool, from which options are taken as they are parsed
+struct Arguments(Vec<String>);
+
+impl Arguments {
+    /// Takes every occurrence of a flag, returning whether or not there were any
+    fn flag(&mut self, name: &str) -> bool {
+        let count = self.0.len();
+        self.0.retain(|argument| argument != name);
+        self.0.len() != count
+    }
+
+    /// Takes every occurrence of an option given either as `--name value` or `--name=value`,
+    /// returning their values
+    fn options(&mut self, name: &str) -> StdResult<Vec<String>, String> {
+        let prefix = format!("{}=", name);
+        let mut values = Vec::new();
+        let mut index = 0;
+        while index < self.0.len() {
+            if self.0[index] == name {
+                if index + 1 == self.0.len() {
+                    return Err(format!("{} must be followed by a value", name));
+                }
+                values.push(self.0.remove(index + 1));
+                self.0.remove(index);
+            } else if let Some(value) = self.0[index].strip_prefix(&prefix) {
+                values.push(value.to_string());
+                self.0.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Takes an option which may be given at most once
+    fn option(&mut self, name: &str) -> StdResult<Option<String>, String> {
+        let mut values = self.options(name)?;
+        if values.len() > 1 {
+            return Err(format!("{} may only be given once", name));
+        }
+        Ok(values.pop())
+    }
+
+    /// Returns the arguments left once every option has been taken
+    fn finish(self) -> StdResult<Vec<String>, String> {
+        match self.0.iter().find(|argument| argument.starts_with("--")) {
+            Some(argument) => Err(format!("`{}` is not an option of this command", argument)),
+            None => Ok(self.0),
+        }
+    }
+}
+
+/// Parses the name of a code set
+fn code_set(name: &str) -> StdResult<CodeSet, String> {
+    CodeSet::from_name(name).ok_or_else(|| format!("`{}` is not a code set", name))
+}
+
+/// Parses a country code given within any of the country code sets
+fn country(code: &str) -> StdResult<Iso3166_1_alpha_2, String> {
+    identify(code)
+        .into_iter()
+        .find_map(|(_, entity)| match entity {
+            Entity::Country(country) => Some(country),
+            Entity::Language(_) => None,
+        })
+        .ok_or_else(|| format!("`{}` is not a country code", code))
+}
+
+/// Returns the record describing an entity
+fn record(entity: Entity) -> Record {
+    match entity {
+        Entity::Country(country) => Record::country(country),
+        Entity::Language(language) => Record::language(language),
+    }
+}
+
+/// Takes the flags restricting a command to countries or languages, returning whether each is
+/// included
+fn kinds(arguments: &mut Arguments) -> StdResult<(bool, bool), String> {
+    match (arguments.flag("--country"), arguments.flag("--language")) {
+        (true, true) => Err("--country and --language may not be given together".to_string()),
+        (false, false) => Ok((true, true)),
+        kinds => Ok(kinds),
+    }
+}
+
+/// The outcome of a command, holding the records to write and the problems to report
+type Outcome = StdResult<(Vec<Record>, Vec<String>), String>;
+
+/// Describes everything each code may stand for
+fn lookup(mut arguments: Arguments) -> Outcome {
+    let (countries, languages) = kinds(&mut arguments)?;
+    let codes = arguments.finish()?;
+    if codes.is_empty() {
+        return Err("lookup must be given at least one code".to_string());
+    }
+
+    let mut entities = Vec::new();
+    let mut problems = Vec::new();
+    for code in codes {
+        let found = identify(&code)
+            .into_iter()
+            .map(|(_, entity)| entity)
+            .filter(|entity| match entity {
+                Entity::Country(_) => countries,
+                Entity::Language(_) => languages,
+            })
+            .collect::<Vec<Entity>>();
+        if found.is_empty() {
+            problems.push(format!("`{}` is not a known code", code));
+        }
+        for entity in found {
+            if !entities.contains(&entity) {
+                entities.push(entity);
+            }
+        }
+    }
+    Ok((entities.into_iter().map(record).collect(), problems))
+}
+
+/// Converts each code into a code set
+fn convert(mut arguments: Arguments) -> Outcome {
+    let from = arguments
+        .option("--from")?
+        .map(|set| code_set(&set))
+        .transpose()?;
+    let to = arguments
+        .option("--to")?
+        .ok_or("convert must be given a code set to convert into with --to")
+        .map(|set| code_set(&set))??;
+    let codes = arguments.finish()?;
+    if codes.is_empty() {
+        return Err("convert must be given at least one code".to_string());
+    }
+    if let Some(from) = from {
+        if from.is_country() != to.is_country() {
+            return Err(format!(
+                "codes within {} cannot be converted into {}",
+                from.name(),
+                to.name()
+            ));
+        }
+    }
+
+    let mut records = Vec::new();
+    let mut problems = Vec::new();
+    for code in codes {
+        let found = match from {
+            Some(from) => from.parse(&code).map(|entity| (from, entity)),
+            None => identify(&code)
+                .into_iter()
+                .find(|(set, _)| set.is_country() == to.is_country()),
+        };
+        let (from, entity) = if let Some(found) = found {
+            found
+        } else {
+            let set = match from {
+                Some(from) => from.name(),
+                None if to.is_country() => "country",
+                None => "language",
+            };
+            problems.push(format!("`{}` is not a {} code", code, set));
+            continue;
+        };
+
+        let converted = entity.code(to);
+        if converted.is_none() {
+            problems.push(format!(
+                "`{}` has no corresponding {} code",
+                code,
+                to.name()
+            ));
+        }
+        records.push(Record {
+            kind: "conversion",
+            fields: vec![
+                ("input", code.into()),
+                ("from", from.name().into()),
+                ("to", to.name().into()),
+                ("output", converted.into()),
+            ],
+        });
+    }
+    Ok((records, problems))
+}
+
+/// Searches the countries and languages by name
+fn search(mut arguments: Arguments) -> Outcome {
+    let (countries, languages) = kinds(&mut arguments)?;
+    let limit = arguments
+        .option("--limit")?
+        .map(|limit| {
+            limit
+                .parse::<usize>()
+                .map_err(|_| format!("`{}` is not a number of results", limit))
+        })
+        .transpose()?;
+    let query = arguments.finish()?.join(" ");
+    if query.trim().is_empty() {
+        return Err("search must be given a name to search for".to_string());
+    }
+
+    let found = iso_cli::search(&query, countries, languages);
+    let problems = if found.is_empty() {
+        vec![format!("nothing is named `{}`", query)]
+    } else {
+        Vec::new()
+    };
+    let records = found
+        .into_iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .map(record)
+        .collect();
+    Ok((records, problems))
+}
+
+/// Lists the countries or languages, filtered by the options given
+fn list(mut arguments: Arguments) -> Outcome {
+    let what = if arguments.0.is_empty() {
+        None
+    } else {
+        Some(arguments.0.remove(0))
+    };
+    match what.as_deref() {
+        Some("countries") => {
+            let independent = arguments.flag("--independent");
+            let dependent = arguments.flag("--dependent");
+            let sovereign = arguments
+                .option("--sovereign")?
+                .map(|code| country(&code))
+                .transpose()?;
+            let neighbor = arguments
+                .option("--neighbor-of")?
+                .map(|code| country(&code))
+                .transpose()?;
+            let calling_code = arguments
+                .option("--calling-code")?
+                .map(|code| code.trim_start_matches('+').to_string());
+            if !arguments.finish()?.is_empty() {
+                return Err("list countries takes no arguments other than its options".to_string());
+            }
+
+            let records =
+                Iso3166_1_alpha_2::iter()
+                    .filter(|country| !independent || country.independent())
+                    .filter(|country| !dependent || !country.independent())
+                    .filter(|country| sovereign.is_none_or(|s| country.sovereign() == Some(s)))
+                    .filter(|country| {
+                        neighbor.is_none_or(|neighbor| neighbor.neighbors().contains(country))
+                    })
+                    .filter(|country| {
+                        calling_code.as_ref().is_none_or(|prefix| {
+                            country.calling_codes().codes().iter().any(|code| {
+                                code == prefix || code.starts_with(&format!("{} ", prefix))
+                            })
+                        })
+                    })
+                    .map(Record::country)
+                    .collect();
+            Ok((records, Vec::new()))
+        }
+        Some("languages") => {
+            let with = arguments
+                .options("--with")?
+                .iter()
+                .map(|set| code_set(set))
+                .collect::<StdResult<Vec<CodeSet>, String>>()?;
+            let without = arguments
+                .options("--without")?
+                .iter()
+                .map(|set| code_set(set))
+                .collect::<StdResult<Vec<CodeSet>, String>>()?;
+            if let Some(set) = with.iter().chain(&without).find(|set| set.is_country()) {
+                return Err(format!("{} is not a language code set", set.name()));
+            }
+            if !arguments.finish()?.is_empty() {
+                return Err("list languages takes no arguments other than its options".to_string());
+            }
+
+            let records = Iso639_3::iter()
+                .map(Entity::Language)
+                .filter(|language| with.iter().all(|set| language.code(*set).is_some()))
+                .filter(|language| without.iter().all(|set| language.code(*set).is_none()))
+                .map(record)
+                .collect();
+            Ok((records, Vec::new()))
+        }
+        _ => Err("list must be given either `countries` or `languages`".to_string()),
+    }
+}
+
+/// Normalizes a column of a file into a code set, writing the values which could not be normalized
+/// to the report
+fn normalize(mut arguments: Arguments, format: Format) -> Outcome {
+    let column = arguments
+        .option("--column")?
+        .ok_or("normalize must be given the column to normalize with --column")?;
+    let to = arguments
+        .option("--to")?
+        .ok_or("normalize must be given a code set to normalize into with --to")
+        .map(|set| code_set(&set))??;
+    let strictness = match arguments.option("--strictness")? {
+        Some(name) => Strictness::from_name(&name).ok_or_else(|| {
+            format!(
+                "`{}` is not a strictness policy, which must be strict, lenient or permissive",
+                name
+            )
+        })?,
+        None => Strictness::default(),
+    };
+    let input_format = arguments.option("--input-format")?;
+    let output = arguments.option("--output")?;
+    let report = arguments.option("--report")?;
+    let input = match arguments.finish()?.as_slice() {
+        [input] => input.clone(),
+        _ => return Err("normalize must be given a single input file (or `-`)".to_string()),
+    };
+    let jsonl = match input_format.as_deref() {
+        Some("csv") => false,
+        Some("jsonl") => true,
+        Some(format) => {
+            return Err(format!(
+                "`{}` is not an input format, which must be csv or jsonl",
+                format
+            ))
+        }
+        None => Path::new(&input)
+            .extension()
+            .is_some_and(|extension| extension == "jsonl" || extension == "ndjson"),
+    };
+
+    let reader: Box<dyn BufRead> = if input == "-" {
+        Box::new(BufReader::new(stdin()))
+    } else {
+        Box::new(BufReader::new(
+            File::open(&input).map_err(|e| format!("unable to open `{}`: {}", input, e))?,
+        ))
+    };
+    let writer: Box<dyn Write> = match &output {
+        Some(output) => Box::new(
+            File::create(output).map_err(|e| format!("unable to create `{}`: {}", output, e))?,
+        ),
+        None => Box::new(stdout()),
+    };
+    let writer = BufWriter::new(writer);
+    let mut normalizer = Normalizer::new(to, strictness);
+    let outcome = if jsonl {
+        normalize::normalize_jsonl(reader, writer, &column, &mut normalizer)
+    } else {
+        normalize::normalize_csv(reader, writer, &column, &mut normalizer)
+    }
+    .map_err(|e| format!("unable to normalize `{}`: {}", input, e))?;
+
+    write_report(&outcome, to, report.as_deref(), format)
+        .map_err(|e| format!("unable to write the report: {}", e))?;
+    eprintln!(
+        "iso: normalized {} rows ({} changed, {} empty, {} unresolved)",
+        outcome.rows,
+        outcome.changed,
+        outcome.empty,
+        outcome.unresolved_values()
+    );
+    let problems = if outcome.unresolved.is_empty() {
+        Vec::new()
+    } else {
+        vec![format!(
+            "{} distinct values could not be normalized into {}",
+            outcome.unresolved.len(),
+            to.name()
+        )]
+    };
+    Ok((Vec::new(), problems))
+}
+
+/// Writes the values which could not be normalized (with suggestions given as codes within the
+/// target code set) to a file, or to standard error if there is
+/// none and there are any such values
+fn write_report(
+    report: &Report,
+    to: CodeSet,
+    path: Option<&str>,
+    format: Format,
+) -> io::Result<()> {
+    let records = report
+        .unresolved
+        .iter()
+        .map(|unresolved| Record {
+            kind: "unresolved",
+            fields: vec![
+                ("value", unresolved.value.as_str().into()),
+                ("occurrences", unresolved.occurrences.into()),
+                ("first-row", unresolved.first_row.into()),
+                ("reason", unresolved.unresolved.reason.to_string().into()),
+                (
+                    "suggestions",
+                    unresolved
+                        .unresolved
+                        .suggestions
+                        .iter()
+                        .map(|entity| {
+                            let code = entity.code(to).unwrap_or_default();
+                            format!("{} ({})", code, entity.name())
+                        })
+                        .collect::<Vec<String>>()
+                        .into(),
+                ),
+            ],
+        })
+        .collect::<Vec<Record>>();
+    match path {
+        Some(path) => {
+            let mut output = BufWriter::new(File::create(path)?);
+            record::write(&mut output, format, &records)?;
+            output.flush()
+        }
+        None if records.is_empty() => Ok(()),
+        None => record::write(&mut io::stderr(), format, &records),
+    }
+}
+
+/// Runs the command given to the tool, returning whether or not every code given was found
+fn run() -> StdResult<bool, String> {
+    let mut arguments = Arguments(args().skip(1).collect());
+    let format = match arguments.option("--format")? {
+        Some(format) => Format::from_name(&format).ok_or_else(|| {
+            format!(
+                "`{}` is not a format, which must be table, json or csv",
+                format
+            )
+        })?,
+        None => Format::Table,
+    };
+    if arguments.flag("--help") || arguments.flag("-h") || arguments.0.is_empty() {
+        println!("{}", USAGE);
+        return Ok(true);
+    }
+
+    let command = arguments.0.remove(0);
+    let (records, problems) = match command.as_str() {
+        "lookup" => lookup(arguments),
+        "convert" => convert(arguments),
+        "search" => search(arguments),
+        "list" => list(arguments),
+        "normalize" => normalize(arguments, format),
+        "help" => {
+            println!("{}", USAGE);
+            return Ok(true);
+        }
+        command => Err(format!("`{}` is not a command\n\n{}", command, USAGE)),
+    }?;
+
+    let stdout = stdout();
+    let mut output = BufWriter::new(stdout.lock());
+    record::write(&mut output, format, &records)
+        .and_then(|()| output.flush())
+        .map_err(|e| format!("unable to write the output: {}", e))?;
+    for problem in &problems {
+        eprintln!("iso: {}", problem);
+    }
+    Ok(problems.is_empty())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("iso: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,561 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Validation and normalization of columns of country or language codes
+//!
+//! A [`Normalizer`] turns each value it is given into the code of the country or language it
+//! identifies within a target code set, accepting more or fewer forms of a value depending on its
+//! [`Strictness`]. Values it cannot normalize are reported alongside what was wrong with them and
+//! the countries or languages they may have been meant to be.
+//!
+//! [`normalize_csv`] and [`normalize_jsonl`] stream a file through a normalizer a row at a time,
+//! normalizing a single column (or field) while leaving the rest of each row as it was. Empty
+//! values are left as they are, as are values which cannot be normalized, so that nothing is lost
+//! from the file, and every one of the latter is reported instead.
+//!
+//! # Basic usage
+//!
+//! ```
+//! # use iso_cli::{
+//! #     normalize::{normalize_csv, Normalizer, Reason, Strictness},
+//! #     CodeSet,
+//! # };
+//! let input = "id,country\n1,USA\n2,us\n3,840\n4,\n5,Untied States\n";
+//! let mut output = Vec::new();
+//! let mut normalizer = Normalizer::new(CodeSet::Alpha2, Strictness::Lenient);
+//!
+//! let report = normalize_csv(input.as_bytes(), &mut output, "country", &mut normalizer).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "id,country\n1,US\n2,US\n3,US\n4,\n5,Untied States\n"
+//! );
+//! assert_eq!((report.rows, report.changed, report.empty), (5, 3, 1));
+//!
+//! let unresolved = &report.unresolved[0];
+//! assert_eq!((unresolved.value.as_str(), unresolved.first_row), ("Untied States", 5));
+//! assert_eq!(unresolved.unresolved.reason, Reason::Unknown);
+//! # #[cfg(not(feature = "without-names"))]
+//! assert_eq!(unresolved.unresolved.suggestions[0].name(), "United States of America");
+//! ```
+
+use iso::country::ExceptionallyReserved;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    error, fmt,
+    io::{self, BufRead, Read, Write},
+    str,
+};
+
+use crate::{
+    code::{self, CodeSet, Entity},
+    search,
+};
+
+/// The most suggestions given for a value which could not be normalized
+const SUGGESTIONS: usize = 3;
+
+/// How strictly values are checked before they are normalized
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub enum Strictness {
+    /// Only codes already in the form they are normalized into are accepted, which checks a column
+    /// without changing it
+    Strict,
+
+    /// Codes within any code set covering the same kind of entity as the target code set are
+    /// accepted, in any case and with any surrounding whitespace. For example, `USA`, `us` and
+    /// `840` are each normalized into `US` (the ISO 3166-1 alpha-2 code of the United States)
+    #[default]
+    Lenient,
+
+    /// Names which identify a single country or language are accepted as well, in any case and
+    /// without their diacritics, as are the exceptionally reserved country codes standing for a
+    /// part of a country (such as `UK`)
+    Permissive,
+}
+
+impl Strictness {
+    /// Returns the name the strictness policy is given on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::Lenient => "lenient",
+            Self::Permissive => "permissive",
+        }
+    }
+
+    /// Parses the name of a strictness policy
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Strict, Self::Lenient, Self::Permissive]
+            .iter()
+            .copied()
+            .find(|strictness| strictness.name() == name)
+    }
+}
+
+/// The reason a value could not be normalized
+#[non_exhaustive]
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Reason {
+    /// The value does not identify any country or language
+    Unknown,
+
+    /// The value identifies a country or language, but is not in a form accepted by the strictness
+    /// policy
+    NotAccepted(Entity),
+
+    /// The value identifies a country or language which has no code within the target code set
+    NoCorrespondingCode(Entity),
+
+    /// The value identifies a language where a country was expected, or the other way around
+    WrongKind(Entity),
+
+    /// The value is a name shared by several countries or languages
+    Ambiguous,
+
+    /// The value is neither text nor a number
+    NotText,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown => formatter.write_str("not a known code"),
+            Self::NotAccepted(entity) => write!(
+                formatter,
+                "stands for {}, but is not in a form the strictness policy accepts",
+                entity.name()
+            ),
+            Self::NoCorrespondingCode(entity) => write!(
+                formatter,
+                "stands for {}, which has no code within the target code set",
+                entity.name()
+            ),
+            Self::WrongKind(Entity::Country(country)) => write!(
+                formatter,
+                "stands for a country ({}) rather than a language",
+                country
+            ),
+            Self::WrongKind(Entity::Language(language)) => write!(
+                formatter,
+                "stands for a language ({}) rather than a country",
+                language
+            ),
+            Self::Ambiguous => formatter.write_str("is the name of several entries"),
+            Self::NotText => formatter.write_str("is neither text nor a number"),
+        }
+    }
+}
+
+/// A value which could not be normalized, alongside the countries or languages it may have been
+/// meant to be (from the likeliest to the least likely)
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Unresolved {
+    /// Why the value could not be normalized
+    pub reason: Reason,
+
+    /// The countries or languages the value may have been meant to be, each of which has a code
+    /// within the target code set
+    pub suggestions: Vec<Entity>,
+}
+
+/// Normalizes values into the codes of a code set, remembering the outcome for each distinct value
+/// so that values repeated throughout a file are only resolved once
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso_cli::{normalize::{Normalizer, Strictness}, CodeSet};
+/// let mut normalizer = Normalizer::new(CodeSet::Iso639_1, Strictness::Lenient);
+///
+/// assert_eq!(normalizer.normalize("eng"), Ok("en".to_string()));
+/// assert_eq!(normalizer.normalize(" EN "), Ok("en".to_string()));
+/// assert!(normalizer.normalize("gsw").is_err());
+/// ```
+#[derive(Debug)]
+pub struct Normalizer {
+    target: CodeSet,
+    strictness: Strictness,
+    outcomes: HashMap<String, Result<String, Unresolved>>,
+}
+
+impl Normalizer {
+    /// Creates a normalizer for a target code set and strictness policy
+    pub fn new(target: CodeSet, strictness: Strictness) -> Self {
+        Self {
+            target,
+            strictness,
+            outcomes: HashMap::new(),
+        }
+    }
+
+    /// Normalizes a value into the code of the country or language it identifies within the target
+    /// code set
+    pub fn normalize(&mut self, value: &str) -> Result<String, Unresolved> {
+        if let Some(outcome) = self.outcomes.get(value) {
+            return outcome.clone();
+        }
+        let outcome = self.resolve(value);
+        self.outcomes.insert(value.to_string(), outcome.clone());
+        outcome
+    }
+
+    /// Returns the code of an entity within the target code set
+    fn code_of(&self, entity: Entity) -> Result<String, Unresolved> {
+        entity.code(self.target).ok_or(Unresolved {
+            reason: Reason::NoCorrespondingCode(entity),
+            suggestions: Vec::new(),
+        })
+    }
+
+    /// Returns the country standing for an exceptionally reserved code (standing for a part of it),
+    /// if the target code set is made up of country codes
+    fn reserved(&self, value: &str) -> Option<Entity> {
+        if !self.target.is_country() {
+            return None;
+        }
+        value
+            .trim()
+            .to_ascii_uppercase()
+            .parse::<ExceptionallyReserved>()
+            .ok()
+            .and_then(|reserved| reserved.country())
+            .map(Entity::Country)
+    }
+
+    /// Returns a value which could not be normalized, with suggestions for what it may have been
+    /// meant to be
+    fn unresolved(&self, reason: Reason, value: &str) -> Unresolved {
+        let (countries, languages) = (self.target.is_country(), !self.target.is_country());
+        let mut suggestions = Vec::new();
+        match &reason {
+            Reason::NotAccepted(entity) => suggestions.push(*entity),
+            Reason::Ambiguous => suggestions.extend(search::named(value, countries, languages)),
+            _ => {
+                suggestions.extend(self.reserved(value));
+                suggestions.extend(search::search(value, countries, languages));
+                if suggestions.is_empty() {
+                    suggestions.extend(search::similar(value, countries, languages));
+                }
+            }
+        }
+
+        let mut unique = Vec::new();
+        for suggestion in suggestions {
+            if !unique.contains(&suggestion) && suggestion.code(self.target).is_some() {
+                unique.push(suggestion);
+            }
+        }
+        unique.truncate(SUGGESTIONS);
+        Unresolved {
+            reason,
+            suggestions: unique,
+        }
+    }
+
+    /// Resolves a value without looking at the outcomes already remembered
+    fn resolve(&self, value: &str) -> Result<String, Unresolved> {
+        if self.strictness == Strictness::Strict {
+            if let Some(entity) = self.target.parse(value) {
+                if entity.code(self.target).as_deref() == Some(value) {
+                    return Ok(value.to_string());
+                }
+            }
+        }
+
+        let found = code::identify(value.trim());
+        if let Some((_, entity)) = found
+            .iter()
+            .find(|(set, _)| set.is_country() == self.target.is_country())
+        {
+            return if self.strictness == Strictness::Strict {
+                Err(self.unresolved(Reason::NotAccepted(*entity), value))
+            } else {
+                self.code_of(*entity)
+            };
+        }
+        // a part of a country given by an exceptionally reserved code (such as `UK`, which is also
+        // the ISO 639-1 code of Ukrainian) stands for the country
+        let named = search::named(value, self.target.is_country(), !self.target.is_country());
+        let entity = match (self.reserved(value), named.as_slice(), found.first()) {
+            (Some(country), _, _) => country,
+            (None, [entity], _) => *entity,
+            (None, [_, _, ..], _) => return Err(self.unresolved(Reason::Ambiguous, value)),
+            (None, [], Some((_, entity))) => {
+                return Err(self.unresolved(Reason::WrongKind(*entity), value))
+            }
+            (None, [], None) => return Err(self.unresolved(Reason::Unknown, value)),
+        };
+        if self.strictness == Strictness::Permissive {
+            self.code_of(entity)
+        } else {
+            Err(self.unresolved(Reason::NotAccepted(entity), value))
+        }
+    }
+}
+
+/// A distinct value which could not be normalized, and where it was found
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct UnresolvedValue {
+    /// The value, as it was found
+    pub value: String,
+
+    /// The number of rows the value was found in
+    pub occurrences: usize,
+
+    /// The first row the value was found in, counting from one (and not counting the header of CSV)
+    pub first_row: usize,
+
+    /// Why the value could not be normalized, and what it may have been meant to be
+    pub unresolved: Unresolved,
+}
+
+/// A report of a file normalized by [`normalize_csv`] or [`normalize_jsonl`]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    /// The number of rows read
+    pub rows: usize,
+
+    /// The number of values which were changed by being normalized
+    pub changed: usize,
+
+    /// The number of empty values (or, within JSON Lines, null or missing ones), which are left as
+    /// they are
+    pub empty: usize,
+
+    /// Each distinct value which could not be normalized, in the order they were first found
+    pub unresolved: Vec<UnresolvedValue>,
+
+    /// The index of each distinct value within `unresolved`
+    indices: HashMap<String, usize>,
+}
+
+impl Report {
+    /// Returns the number of values which could not be normalized, counting every occurrence
+    pub fn unresolved_values(&self) -> usize {
+        self.unresolved.iter().map(|value| value.occurrences).sum()
+    }
+
+    /// Normalizes a value found within the current row, returning what it is to be replaced with
+    /// if it was changed
+    fn normalize(&mut self, normalizer: &mut Normalizer, value: &str) -> Option<String> {
+        if value.trim().is_empty() {
+            self.empty += 1;
+            return None;
+        }
+        match normalizer.normalize(value) {
+            Ok(normalized) if normalized == value => None,
+            Ok(normalized) => {
+                self.changed += 1;
+                Some(normalized)
+            }
+            Err(unresolved) => {
+                self.unresolve(value, unresolved);
+                None
+            }
+        }
+    }
+
+    /// Records a value found within the current row which could not be normalized
+    fn unresolve(&mut self, value: &str, unresolved: Unresolved) {
+        if let Some(index) = self.indices.get(value) {
+            self.unresolved[*index].occurrences += 1;
+        } else {
+            self.indices
+                .insert(value.to_string(), self.unresolved.len());
+            self.unresolved.push(UnresolvedValue {
+                value: value.to_string(),
+                occurrences: 1,
+                first_row: self.rows,
+                unresolved,
+            });
+        }
+    }
+}
+
+/// An error encountered while normalizing a file
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    /// An error returned when the input could not be read or the output could not be written
+    Io(io::Error),
+
+    /// An error returned when the input is not valid CSV
+    Csv(csv::Error),
+
+    /// An error returned when a row of JSON Lines is not a JSON object
+    Json {
+        /// The row, counting from one
+        row: usize,
+
+        /// The error encountered while parsing the row
+        error: serde_json::Error,
+    },
+
+    /// An error returned when the input has no column (or field) with the provided name
+    MissingColumn(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(formatter, "{}", e),
+            Self::Csv(e) => write!(formatter, "the input is not valid csv: {}", e),
+            Self::Json { row, error } => write!(
+                formatter,
+                "row {} of the input is not a json object: {}",
+                row, error
+            ),
+            Self::MissingColumn(column) => {
+                write!(formatter, "the input has no `{}` column", column)
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Csv(e) => Some(e),
+            Self::Json { error, .. } => Some(error),
+            Self::MissingColumn(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Self {
+        Self::Csv(e)
+    }
+}
+
+/// Normalizes a column of CSV with a header, a row at a time, writing each row out as it goes
+///
+/// Every row must have as many fields as the header does, and a row is only rewritten (with any
+/// quoting left as the writer sees fit) if its value was changed. Values which are not valid UTF-8
+/// are reported as being neither text nor a number.
+pub fn normalize_csv<R, W>(
+    input: R,
+    output: W,
+    column: &str,
+    normalizer: &mut Normalizer,
+) -> Result<Report, Error>
+where
+    R: Read,
+    W: Write,
+{
+    let mut reader = csv::Reader::from_reader(input);
+    let mut writer = csv::Writer::from_writer(output);
+    let header = reader.byte_headers()?.clone();
+    let index = header
+        .iter()
+        .position(|name| name == column.as_bytes())
+        .ok_or_else(|| Error::MissingColumn(column.to_string()))?;
+    writer.write_byte_record(&header)?;
+
+    let mut report = Report::default();
+    let mut row = csv::ByteRecord::new();
+    while reader.read_byte_record(&mut row)? {
+        report.rows += 1;
+        let value = row.get(index).unwrap_or_default();
+        let normalized = if let Ok(value) = str::from_utf8(value) {
+            report.normalize(normalizer, value)
+        } else {
+            let value = String::from_utf8_lossy(value);
+            let unresolved = normalizer.unresolved(Reason::NotText, &value);
+            report.unresolve(&value, unresolved);
+            None
+        };
+        match normalized {
+            Some(normalized) => writer.write_record(row.iter().enumerate().map(|(i, field)| {
+                if i == index {
+                    normalized.as_bytes()
+                } else {
+                    field
+                }
+            }))?,
+            None => writer.write_byte_record(&row)?,
+        }
+    }
+    writer.flush()?;
+    Ok(report)
+}
+
+/// Normalizes a field of each object of JSON Lines, a row at a time, writing each row out as it
+/// goes
+///
+/// Numbers are normalized as though they were text (so `840` is taken to be a numeric country
+/// code), and rows are only rewritten if their value was changed, in which case the order of their
+/// fields is kept. Blank lines are passed through without counting as a row, and it is an error
+/// for none of the rows to have the field at all.
+pub fn normalize_jsonl<R, W>(
+    input: R,
+    mut output: W,
+    field: &str,
+    normalizer: &mut Normalizer,
+) -> Result<Report, Error>
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut report = Report::default();
+    let mut found = false;
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            writeln!(output, "{}", line)?;
+            continue;
+        }
+        report.rows += 1;
+        let mut object =
+            serde_json::from_str::<serde_json::Map<String, Value>>(&line).map_err(|error| {
+                Error::Json {
+                    row: report.rows,
+                    error,
+                }
+            })?;
+
+        found |= object.contains_key(field);
+        let normalized = match object.get(field) {
+            None | Some(Value::Null) => {
+                report.empty += 1;
+                None
+            }
+            Some(Value::String(value)) => report.normalize(normalizer, value),
+            Some(Value::Number(value)) => report.normalize(normalizer, &value.to_string()),
+            Some(value) => {
+                let value = value.to_string();
+                let unresolved = normalizer.unresolved(Reason::NotText, &value);
+                report.unresolve(&value, unresolved);
+                None
+            }
+        };
+        match normalized {
+            Some(normalized) => {
+                object.insert(field.to_string(), Value::String(normalized));
+                serde_json::to_writer(&mut output, &object).map_err(io::Error::from)?;
+                writeln!(output)?;
+            }
+            None => writeln!(output, "{}", line)?,
+        }
+    }
+    output.flush()?;
+    if report.rows > 0 && !found {
+        return Err(Error::MissingColumn(field.to_string()));
+    }
+    Ok(report)
+}
@@ -0,0 +1,270 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! The records the tool outputs, and the formats they can be written in
+//!
+//! Every record is of a kind (such as `country`), and every record of a kind has the same fields in
+//! the same order. As tables and CSV can only hold one set of columns, records of each kind are
+//! written as a table of their own (separated by a blank line) when records of several kinds are
+//! written at once, while JSON is written as a single array of objects, each with a `kind` field.
+//! Fields holding names are left out when names are (by the `without-names` feature).
+
+#[cfg(not(feature = "without-names"))]
+use iso::language::Language;
+use iso::{
+    country::{Country, Iso3166_1_alpha_2, Iso3166_1_alpha_3},
+    language::{Iso639_1, Iso639_2b, Iso639_2t, Iso639_3},
+};
+use serde_json::Value;
+use std::{
+    convert::TryFrom,
+    io::{self, Write},
+};
+
+/// A format records can be written in
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+impl Format {
+    /// Parses the name of a format
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "table" => Some(Self::Table),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// A record to be written, holding its fields in order
+pub(crate) struct Record {
+    pub(crate) kind: &'static str,
+    pub(crate) fields: Vec<(&'static str, Value)>,
+}
+
+impl Record {
+    /// Returns a record describing a country
+    pub(crate) fn country(country: Iso3166_1_alpha_2) -> Self {
+        let mut fields = vec![
+            ("alpha-2", country.code().into()),
+            ("alpha-3", Iso3166_1_alpha_3::from(country).code().into()),
+            ("numeric", format!("{:03}", country.numeric()).into()),
+        ];
+        fields.extend(country_names(country));
+        fields.extend(vec![
+            (
+                "calling-codes",
+                country.calling_codes().codes().to_vec().into(),
+            ),
+            ("cctld", country.cctld().into()),
+            ("independent", country.independent().into()),
+            (
+                "sovereign",
+                country.sovereign().map(|country| country.code()).into(),
+            ),
+        ]);
+        Self {
+            kind: "country",
+            fields,
+        }
+    }
+
+    /// Returns a record describing a language
+    pub(crate) fn language(language: Iso639_3) -> Self {
+        let code = |code: Option<&'static str>| Value::from(code);
+        let mut fields = vec![
+            ("639-3", language.code().into()),
+            (
+                "639-2b",
+                code(Iso639_2b::try_from(language).ok().map(|l| l.code())),
+            ),
+            (
+                "639-2t",
+                code(Iso639_2t::try_from(language).ok().map(|l| l.code())),
+            ),
+            (
+                "639-1",
+                code(Iso639_1::try_from(language).ok().map(|l| l.code())),
+            ),
+        ];
+        fields.extend(language_names(language));
+        Self {
+            kind: "language",
+            fields,
+        }
+    }
+}
+
+/// Returns the fields holding the names of a country
+#[cfg(not(feature = "without-names"))]
+fn country_names(country: Iso3166_1_alpha_2) -> Vec<(&'static str, Value)> {
+    vec![
+        ("name", country.name().into()),
+        ("common-name", country.common_name().into()),
+        ("name-fr", country.name_fr().into()),
+    ]
+}
+
+/// Returns the fields holding the names of a country, of which there are none when names are left
+/// out
+#[cfg(feature = "without-names")]
+fn country_names(_: Iso3166_1_alpha_2) -> Vec<(&'static str, Value)> {
+    Vec::new()
+}
+
+/// Returns the fields holding the names of a language
+#[cfg(not(feature = "without-names"))]
+fn language_names(language: Iso639_3) -> Vec<(&'static str, Value)> {
+    vec![
+        ("name", language.name().into()),
+        ("name-fr", language.name_fr().into()),
+        ("native-name", language.native_name(None).into()),
+    ]
+}
+
+/// Returns the fields holding the names of a language, of which there are none when names are left
+/// out
+#[cfg(feature = "without-names")]
+fn language_names(_: Iso639_3) -> Vec<(&'static str, Value)> {
+    Vec::new()
+}
+
+/// Returns a value as it is written within a table or CSV
+fn cell(value: &Value, list_separator: &str) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(string) => string.clone(),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| cell(value, list_separator))
+            .collect::<Vec<String>>()
+            .join(list_separator),
+        value => value.to_string(),
+    }
+}
+
+/// Quotes a cell of CSV if it holds anything which must be quoted
+fn quote(cell: &str) -> String {
+    if cell.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// Groups records by their kind, in the order each kind first appears
+fn by_kind(records: &[Record]) -> Vec<Vec<&Record>> {
+    let mut groups: Vec<Vec<&Record>> = Vec::new();
+    for record in records {
+        match groups.iter_mut().find(|group| group[0].kind == record.kind) {
+            Some(group) => group.push(record),
+            None => groups.push(vec![record]),
+        }
+    }
+    groups
+}
+
+/// Writes records in a format
+pub(crate) fn write(output: &mut impl Write, format: Format, records: &[Record]) -> io::Result<()> {
+    match format {
+        Format::Json => {
+            let records = records
+                .iter()
+                .map(|record| {
+                    let fields = record
+                        .fields
+                        .iter()
+                        .map(|(field, value)| format!("    {}: {}", Value::from(*field), value))
+                        .collect::<Vec<String>>()
+                        .join(",\n");
+                    format!(
+                        "  {{\n    \"kind\": {},\n{}\n  }}",
+                        Value::from(record.kind),
+                        fields
+                    )
+                })
+                .collect::<Vec<String>>();
+            if records.is_empty() {
+                writeln!(output, "[]")
+            } else {
+                writeln!(output, "[\n{}\n]", records.join(",\n"))
+            }
+        }
+        Format::Csv => {
+            for (index, group) in by_kind(records).into_iter().enumerate() {
+                if index > 0 {
+                    writeln!(output)?;
+                }
+                let header = group[0]
+                    .fields
+                    .iter()
+                    .map(|(field, _)| quote(field))
+                    .collect::<Vec<String>>();
+                writeln!(output, "{}", header.join(","))?;
+                for record in group {
+                    let row = record
+                        .fields
+                        .iter()
+                        .map(|(_, value)| quote(&cell(value, ";")))
+                        .collect::<Vec<String>>();
+                    writeln!(output, "{}", row.join(","))?;
+                }
+            }
+            Ok(())
+        }
+        Format::Table => {
+            for (index, group) in by_kind(records).into_iter().enumerate() {
+                if index > 0 {
+                    writeln!(output)?;
+                }
+                let mut rows = vec![group[0]
+                    .fields
+                    .iter()
+                    .map(|(field, _)| field.to_string())
+                    .collect::<Vec<String>>()];
+                rows.extend(group.iter().map(|record| {
+                    record
+                        .fields
+                        .iter()
+                        .map(|(_, value)| cell(value, ", "))
+                        .collect::<Vec<String>>()
+                }));
+
+                // note: widths are counted in characters, which lines up every column other than
+                // those holding characters wider (or narrower) than a single column
+                let widths = (0..rows[0].len())
+                    .map(|column| {
+                        rows.iter()
+                            .map(|row| row[column].chars().count())
+                            .max()
+                            .unwrap_or_default()
+                    })
+                    .collect::<Vec<usize>>();
+                for row in rows {
+                    let line = row
+                        .iter()
+                        .zip(&widths)
+                        .map(|(cell, width)| {
+                            format!("{}{}", cell, " ".repeat(width - cell.chars().count()))
+                        })
+                        .collect::<Vec<String>>()
+                        .join("  ");
+                    writeln!(output, "{}", line.trim_end())?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
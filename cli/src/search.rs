@@ -0,0 +1,218 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Searching for countries and languages by name
+//!
+//! Names are compared ignoring case and diacritics, so `aland` finds the Åland Islands. Every name
+//! the library gives a country or language is searched (such as its French name, or the name a
+//! language is given by its own speakers), and matches are ranked with exact matches first, then
+//! names beginning with the query, then names holding it as a word, then names holding a word
+//! beginning with it, and then names holding it anywhere. Matches ranked alike are ordered by the
+//! name they were found through, with the common name first, then the English and short names, and
+//! then the others (so `kingdom` finds the United Kingdom before the countries whose formal names
+//! hold the word), and only then by name. When names are left out by the `without-names` feature,
+//! codes stand in for them.
+
+#[cfg(not(feature = "without-names"))]
+use iso::{country::Country, language::Language};
+use iso::{country::Iso3166_1_alpha_2, fold_character, language::Iso639_3};
+
+use crate::code::Entity;
+
+/// Folds a name for comparison, lowercasing it and replacing any letter bearing a diacritic with
+/// its base letter, as the library folds names for the `ascii-names` data subset
+fn fold(name: &str) -> String {
+    name.chars()
+        .flat_map(char::to_lowercase)
+        .fold(String::new(), |mut folded, character| {
+            match fold_character(character) {
+                Some(replacement) => folded.push_str(replacement),
+                None => folded.push(character),
+            }
+            folded
+        })
+}
+
+/// Returns how closely a name matches a folded query, with lower ranks being closer matches
+fn rank(name: &str, query: &str) -> Option<u8> {
+    let name = fold(name);
+    if name == query {
+        Some(0)
+    } else if name.starts_with(query) {
+        Some(1)
+    } else if name
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word == query)
+    {
+        Some(2)
+    } else if name
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(query))
+    {
+        Some(3)
+    } else if name.contains(query) {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+/// Returns every name of a country, alongside the tier of the name (with the common name first,
+/// then the English and short names, and then the others)
+#[cfg(not(feature = "without-names"))]
+fn country_names(country: Iso3166_1_alpha_2) -> Vec<(u8, &'static str)> {
+    let mut names = vec![
+        (0, country.common_name()),
+        (1, country.name()),
+        (1, country.short_name()),
+        (2, country.name_fr()),
+    ];
+    names.extend(country.full_name().map(|name| (2, name)));
+    names
+}
+
+/// Returns every name of a language, alongside the tier of the name (with the English name first,
+/// then the name given by its own speakers, and then the French name)
+#[cfg(not(feature = "without-names"))]
+fn language_names(language: Iso639_3) -> Vec<(u8, &'static str)> {
+    let mut names = vec![(0, language.name())];
+    names.extend(language.native_name(None).map(|name| (1, name)));
+    names.extend(language.name_fr().map(|name| (2, name)));
+    names
+}
+
+/// Returns the code of a country, which stands in for its names when they are left out
+#[cfg(feature = "without-names")]
+fn country_names(country: Iso3166_1_alpha_2) -> Vec<(u8, &'static str)> {
+    vec![(0, Entity::Country(country).name())]
+}
+
+/// Returns the code of a language, which stands in for its names when they are left out
+#[cfg(feature = "without-names")]
+fn language_names(language: Iso639_3) -> Vec<(u8, &'static str)> {
+    vec![(0, Entity::Language(language).name())]
+}
+
+/// Returns every country and language (or those of either one) with a name matching a folded
+/// query, alongside how closely their closest name matches it (and the tier of that name) and the
+/// name they are sorted by
+fn matches(query: &str, countries: bool, languages: bool) -> Vec<((u8, u8), &'static str, Entity)> {
+    let best = |names: Vec<(u8, &'static str)>| {
+        names
+            .iter()
+            .filter_map(|(tier, name)| Some((rank(name, query)?, *tier)))
+            .min()
+    };
+
+    let mut matches = Vec::new();
+    if countries {
+        matches.extend(Iso3166_1_alpha_2::iter().filter_map(|country| {
+            Some((
+                best(country_names(country))?,
+                Entity::Country(country).name(),
+                Entity::Country(country),
+            ))
+        }));
+    }
+    if languages {
+        matches.extend(Iso639_3::iter().filter_map(|language| {
+            Some((
+                best(language_names(language))?,
+                Entity::Language(language).name(),
+                Entity::Language(language),
+            ))
+        }));
+    }
+    matches.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    matches
+}
+
+/// Searches the countries and languages (or either one) by name, returning those matching the
+/// query from the closest match to the furthest
+///
+/// ```
+/// # use iso::country::Iso3166_1_alpha_2;
+/// # use iso_cli::{search, Entity};
+/// # #[cfg(not(feature = "without-names"))]
+/// # {
+/// let found = search("aland", true, false);
+/// assert_eq!(found[0], Entity::Country(Iso3166_1_alpha_2::Ax));
+///
+/// let found = search("kingdom", true, false);
+/// assert_eq!(found[0], Entity::Country(Iso3166_1_alpha_2::Gb));
+/// # }
+/// ```
+pub fn search(query: &str, countries: bool, languages: bool) -> Vec<Entity> {
+    matches(&fold(query.trim()), countries, languages)
+        .into_iter()
+        .map(|(_, _, entity)| entity)
+        .collect()
+}
+
+/// Returns the countries and languages (or those of either one) with a name exactly matching the
+/// provided one, ignoring case and diacritics
+pub(crate) fn named(name: &str, countries: bool, languages: bool) -> Vec<Entity> {
+    matches(&fold(name.trim()), countries, languages)
+        .into_iter()
+        .take_while(|((rank, _), _, _)| *rank == 0)
+        .map(|(_, _, entity)| entity)
+        .collect()
+}
+
+/// Returns the number of single character insertions, deletions and substitutions needed to turn
+/// one string into another
+fn distance(a: &[char], b: &[char]) -> usize {
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, a) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Returns the countries and languages (or those of either one) with a name within a few typos of
+/// the provided one, from the closest to the furthest, for when searching finds nothing
+pub(crate) fn similar(name: &str, countries: bool, languages: bool) -> Vec<Entity> {
+    let name = fold(name.trim()).chars().collect::<Vec<char>>();
+    let allowed = (name.len() / 4).max(1);
+    let closest = |names: Vec<(u8, &'static str)>| {
+        names
+            .iter()
+            .map(|(_, candidate)| distance(&name, &fold(candidate).chars().collect::<Vec<char>>()))
+            .min()
+            .filter(|distance| *distance <= allowed)
+    };
+
+    let mut similar = Vec::new();
+    if countries {
+        similar.extend(Iso3166_1_alpha_2::iter().filter_map(|country| {
+            Some((
+                closest(country_names(country))?,
+                Entity::Country(country).name(),
+                Entity::Country(country),
+            ))
+        }));
+    }
+    if languages {
+        similar.extend(Iso639_3::iter().filter_map(|language| {
+            Some((
+                closest(language_names(language))?,
+                Entity::Language(language).name(),
+                Entity::Language(language),
+            ))
+        }));
+    }
+    similar.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    similar.into_iter().map(|(_, _, entity)| entity).collect()
+}
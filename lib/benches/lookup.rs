@@ -0,0 +1,145 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Compares the tables the code enumerations are backed by against the `match` expressions they
+//! replaced, which are still generated here for comparison
+//!
+//! Run with `cargo bench -p iso`. Each lookup is first checked to give the same result either way.
+//! The lookup of names is skipped when the `without-names` data subset leaves them out
+
+#[cfg(not(feature = "without-names"))]
+use iso::language::Language;
+use iso::{
+    country::{Country, Iso3166_1_alpha_2},
+    language::{Iso639_1, Iso639_3},
+};
+use iso_macro::{country_identifiers_from_table, language_identifiers_from_table};
+use std::{
+    convert::TryFrom,
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+language_identifiers_from_table!(static LANGUAGES: "iso639_3" => [iso639_3]);
+country_identifiers_from_table!(static COUNTRIES: "iso3166_1_alpha_2" => [iso3166_1_alpha_2, "iso3166_1_numeric"]);
+
+fn iso639_3_from_str(s: &str) -> Option<Iso639_3> {
+    language_identifiers_from_table!(match s: "iso639_3" => iso639_3)
+}
+
+#[cfg(not(feature = "without-names"))]
+fn iso639_3_name(language: Iso639_3) -> &'static str {
+    language_identifiers_from_table!(match language: iso639_3 => "name")
+}
+
+fn iso639_1_from_iso639_3(language: Iso639_3) -> Option<Iso639_1> {
+    language_identifiers_from_table!(match language: iso639_3 => iso639_1)
+}
+
+fn alpha_2_from_str(s: &str) -> Option<Iso3166_1_alpha_2> {
+    country_identifiers_from_table!(match s: "iso3166_1_alpha_2" => iso3166_1_alpha_2)
+}
+
+fn alpha_2_from_numeric(numeric: u16) -> Option<Iso3166_1_alpha_2> {
+    country_identifiers_from_table!(match numeric: "iso3166_1_numeric" => iso3166_1_alpha_2)
+}
+
+/// Runs a function over every input repeatedly, returning the average time taken per input
+fn measure<I: Copy, T>(inputs: &[I], mut function: impl FnMut(I) -> T) -> Duration {
+    const ROUNDS: u32 = 200;
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for input in inputs {
+            black_box(function(black_box(*input)));
+        }
+    }
+    start.elapsed() / (ROUNDS * inputs.len() as u32)
+}
+
+fn compare<I: Copy, T>(
+    name: &str,
+    inputs: &[I],
+    table: impl FnMut(I) -> T,
+    matched: impl FnMut(I) -> T,
+) {
+    let table = measure(inputs, table);
+    let matched = measure(inputs, matched);
+    println!(
+        "{:<32} table: {:>9.2?}  match: {:>9.2?}",
+        name, table, matched
+    );
+}
+
+fn main() {
+    let codes = LANGUAGES.iter().map(|(code, _)| *code).collect::<Vec<_>>();
+    let languages = LANGUAGES
+        .iter()
+        .map(|(_, language)| *language)
+        .collect::<Vec<_>>();
+    let alpha_2_codes = COUNTRIES.iter().map(|(code, ..)| *code).collect::<Vec<_>>();
+    let numeric_codes = COUNTRIES
+        .iter()
+        .map(|(_, _, numeric)| *numeric)
+        .collect::<Vec<_>>();
+
+    for (code, language) in LANGUAGES.iter() {
+        assert_eq!(Iso639_3::from_str(code).ok(), iso639_3_from_str(code));
+        #[cfg(not(feature = "without-names"))]
+        assert_eq!(language.name(), iso639_3_name(*language));
+        assert_eq!(
+            Iso639_1::try_from(*language).ok(),
+            iso639_1_from_iso639_3(*language)
+        );
+    }
+    for (code, country, numeric) in COUNTRIES.iter() {
+        assert_eq!(
+            Iso3166_1_alpha_2::from_str(code).ok(),
+            alpha_2_from_str(code)
+        );
+        assert_eq!(
+            Iso3166_1_alpha_2::try_from(*numeric).ok(),
+            alpha_2_from_numeric(*numeric)
+        );
+        assert_eq!(country.numeric(), *numeric);
+    }
+
+    compare(
+        "Iso639_3::from_str",
+        &codes,
+        |code| Iso639_3::from_str(code).ok(),
+        iso639_3_from_str,
+    );
+    #[cfg(not(feature = "without-names"))]
+    compare(
+        "Iso639_3::name",
+        &languages,
+        |language| language.name(),
+        iso639_3_name,
+    );
+    compare(
+        "Iso639_1::try_from(Iso639_3)",
+        &languages,
+        |language| Iso639_1::try_from(language).ok(),
+        iso639_1_from_iso639_3,
+    );
+    compare(
+        "Iso3166_1_alpha_2::from_str",
+        &alpha_2_codes,
+        |code| Iso3166_1_alpha_2::from_str(code).ok(),
+        alpha_2_from_str,
+    );
+    compare(
+        "Iso3166_1_alpha_2::try_from(u16)",
+        &numeric_codes,
+        |numeric| Iso3166_1_alpha_2::try_from(numeric).ok(),
+        alpha_2_from_numeric,
+    );
+}
@@ -0,0 +1,43 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Forwards the enabled data subset and name storage features to the code generation macros,
+//! which are unable to see the features enabled on the crate invoking them
+
+use std::env::var_os;
+
+/// The features selecting a subset of the datasets which the macros need to know of
+const SUBSETS: &[&str] = &["iso639-1-languages", "living-languages", "ascii-names"];
+
+/// Returns whether or not a feature is enabled on the crate being built
+fn enabled(feature: &str) -> bool {
+    var_os(format!(
+        "CARGO_FEATURE_{}",
+        feature.to_uppercase().replace('-', "_")
+    ))
+    .is_some()
+}
+
+fn main() {
+    let subsets = SUBSETS
+        .iter()
+        .copied()
+        .filter(|feature| enabled(feature))
+        .collect::<Vec<&str>>();
+    println!("cargo:rustc-env=ISO_DATA_SUBSETS={}", subsets.join(","));
+    println!(
+        "cargo:rustc-env=ISO_NAME_STORAGE={}",
+        if enabled("packed-names") {
+            "packed"
+        } else {
+            "inline"
+        }
+    );
+    println!("cargo:rerun-if-changed=build.rs");
+}
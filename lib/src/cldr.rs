@@ -0,0 +1,57 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Locale resolution for the localized names vendored from the Unicode CLDR
+
+use iso_macro::cldr_from_table;
+
+cldr_from_table!(static LOCALES: locales);
+cldr_from_table!(static PARENTS: parents);
+
+/// Returns whether two locale identifiers are the same, ignoring case and treating `_` as `-`
+fn same_locale(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes().zip(b.bytes()).all(|(a, b)| {
+            let normalize = |byte: u8| match byte {
+                b'_' => b'-',
+                byte => byte.to_ascii_lowercase(),
+            };
+            normalize(a) == normalize(b)
+        })
+}
+
+/// Returns the locale a locale falls back to, if it does not fall back to the root locale (which
+/// is named `und` by later releases of the CLDR)
+fn parent(locale: &str) -> Option<&str> {
+    match PARENTS.iter().find(|(child, _)| same_locale(child, locale)) {
+        Some((_, "root")) | Some((_, "und")) => None,
+        Some((_, parent)) => Some(parent),
+        None => locale.rfind(['-', '_']).map(|end| &locale[..end]),
+    }
+}
+
+/// Looks up a name within the provided locale, falling back through its parent locales until one
+/// of the locales compiled in gives one
+pub(crate) fn localized(
+    locale: &str,
+    lookup: impl Fn(&'static str) -> Option<&'static str>,
+) -> Option<&'static str> {
+    let mut current = Some(locale);
+    while let Some(locale) = current {
+        let name = LOCALES
+            .iter()
+            .find(|(name, compiled)| *compiled && same_locale(name, locale))
+            .and_then(|(name, _)| lookup(name));
+        if name.is_some() {
+            return name;
+        }
+        current = parent(locale);
+    }
+    None
+}
@@ -22,46 +22,160 @@
 //! // the variant representing the united states
 //! let united_states = Iso3166_1_alpha_2::Us;
 //!
+//! # #[cfg(not(feature = "without-names"))]
 //! println!(
 //!     "The name of the country represented by the ISO 3166-1 alpha-3 code of {} is {}!",
 //!     TryInto::<Iso3166_1_alpha_3>::try_into(united_states).unwrap().code(),
 //!     united_states.name()
 //! );
 //!
+//! # #[cfg(not(feature = "without-names"))]
 //! assert_eq!(united_states.name(), "United States of America");
 //! assert_eq!(united_states.code(), "US");
 //! assert_eq!(united_states.try_into(), Ok(Iso3166_1_alpha_2::Us));
 //! assert_eq!(united_states.try_into(), Ok(Iso3166_1_alpha_3::Usa));
 //! ```
+//!
+//! # Constant contexts
+//!
+//! Codes may be checked at compile time with [`country!`](crate::country!), and most information
+//! about a country can also be had within a constant context
+//!
+//! ```
+//! # use iso::{country, country::Iso3166_1_alpha_2};
+//! const GERMANY: Iso3166_1_alpha_2 = country!("DE");
+//! # #[cfg(not(feature = "without-names"))]
+//! const NAME: &str = GERMANY.name();
+//! const FRANCE: Option<Iso3166_1_alpha_2> = Iso3166_1_alpha_2::from_bytes(b"FR");
+//!
+//! # #[cfg(not(feature = "without-names"))]
+//! assert_eq!(NAME, "Germany");
+//! assert_eq!(GERMANY.numeric(), 276);
+//! assert_eq!(FRANCE, Some(Iso3166_1_alpha_2::Fr));
+//! ```
+//!
+//! # Iterating over a code set
+//!
+//! Each code enumeration can be iterated over, in order of its codes
+//!
+//! ```
+//! # use iso::country::{Iso3166_1_alpha_2, Country};
+//! assert_eq!(Iso3166_1_alpha_2::iter().next(), Some(Iso3166_1_alpha_2::Ad));
+//!
+//! let mut dependencies = Iso3166_1_alpha_2::iter().filter(|country| !country.independent());
+//! assert!(dependencies.any(|country| country == Iso3166_1_alpha_2::Gl));
+//! ```
 
 use core::{
     convert::TryFrom,
     fmt,
     str::{self, FromStr},
 };
-use iso_macro::country_identifiers_from_table;
+use iso_macro::{
+    country_identifiers_from_table, dataset_from_environment, literal_macro_from_table,
+};
+
+#[cfg(feature = "cldr")]
+use iso_macro::cldr_from_table;
+
+#[cfg(feature = "cldr")]
+use crate::cldr;
+
+use crate::{CodeEnumeration, DatasetInfo, InlineCode};
 
 #[cfg(feature = "std")]
 use std::error;
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use ::serde::{Deserialize, Serialize};
+
+mod border;
+mod calling_code;
+mod cctld;
+mod exceptionally_reserved;
+#[cfg(not(feature = "without-names"))]
+mod name;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod sovereignty;
+mod user_assigned;
+
+pub use border::{shortest_land_route, LandRoute};
+pub use calling_code::{resolve_calling_code, CallingCodes};
+pub use cctld::resolve_cctld;
+pub use exceptionally_reserved::ExceptionallyReserved;
+pub use sovereignty::Dependencies;
+pub use user_assigned::{ExtendedAlpha2, ExtendedAlpha3, UserAssignedAlpha2, UserAssignedAlpha3};
+
+/// The version of the ISO 3166-1 dataset the code enumerations were generated from, as given by
+/// [`DATASET`]
+///
+/// For the dataset bundled with the crate, this is the upstream release (or commit) recorded for it
+/// within `assets/sources.tab`, or `None` if none is recorded. The dataset may be replaced at build
+/// time by setting the `ISO_COUNTRY_DATASET` environment variable to the path of another, in which
+/// case this is the value of `ISO_COUNTRY_DATASET_VERSION` (or `None` if it is unset).
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{DATASET, DATASET_VERSION};
+/// assert_eq!(DATASET_VERSION, DATASET.version);
+/// println!("built from version {}", DATASET_VERSION.unwrap_or("unknown"));
+/// ```
+pub const DATASET_VERSION: Option<&str> = DATASET.version;
+
+/// The SHA-256 checksum of the ISO 3166-1 dataset the code enumerations were generated from, as a
+/// lowercase hexadecimal string given by [`DATASET`]
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::DATASET_CHECKSUM;
+/// assert_eq!(DATASET_CHECKSUM.len(), 64);
+/// ```
+pub const DATASET_CHECKSUM: &str = DATASET.checksum;
+
+/// Information about the ISO 3166-1 dataset the code enumerations were generated from, including
+/// where it was taken from and the licence it is distributed under
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{DATASET, DATASET_CHECKSUM};
+/// assert_eq!(DATASET.checksum, DATASET_CHECKSUM);
+/// assert!(DATASET.rows > 0);
+/// ```
+pub const DATASET: DatasetInfo = dataset_from_environment!(country);
 
 /// A list of all possible errors encountered while working with the country code enumerations
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::{country::{Error, Iso3166_1_alpha_2}, InlineCode};
+/// # use std::convert::TryFrom;
+/// assert_eq!(Iso3166_1_alpha_2::try_from(&b"US"[..]), Ok(Iso3166_1_alpha_2::Us));
+/// assert_eq!(
+///     Iso3166_1_alpha_2::try_from(&b"ZZ"[..]),
+///     Err(Error::InvalidCountryCode(InlineCode::new(b"ZZ")))
+/// );
+/// ```
 #[non_exhaustive]
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub enum Error {
     /// An error returned when the provided country code is invalid
-    InvalidCountryCode(String),
+    InvalidCountryCode(InlineCode),
+
+    /// An error returned when there is no corresponding country code in the target code set
+    NoCorrespondingCountryCode(InlineCode),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self {
-            Self::InvalidCountryCode(c) => {
-                formatter.write_str("`")?;
-                formatter.write_str(c)?;
-                formatter.write_str("` is an invalid country code")
+            Self::InvalidCountryCode(c) => write!(formatter, "`{}` is an invalid country code", c),
+            Self::NoCorrespondingCountryCode(c) => {
+                write!(formatter, "`{}` has no corresponding country code", c)
             }
         }
     }
@@ -73,35 +187,256 @@ impl error::Error for Error {}
 /// An abstraction over a country code providing ways to extract information as well as convert
 pub trait Country {
     /// Returns the country's name
+    #[cfg(not(feature = "without-names"))]
     fn name(&self) -> &'static str;
 
+    /// Returns the country's name in French, which is its French short name within ISO 3166-1
+    ///
+    /// ```
+    /// # use iso::country::{Iso3166_1_alpha_2, Country};
+    /// assert_eq!(Iso3166_1_alpha_2::De.name_fr(), "Allemagne");
+    /// # #[cfg(not(feature = "ascii-names"))]
+    /// assert_eq!(Iso3166_1_alpha_2::Ci.name_fr(), "Côte d'Ivoire");
+    /// ```
+    #[cfg(not(feature = "without-names"))]
+    fn name_fr(&self) -> &'static str;
+
+    /// Returns the country's name within the provided locale (such as `fr` or `en-AU`), as given by
+    /// the Unicode CLDR
+    ///
+    /// The name is looked up within the locale's parent locales when the locale itself does not
+    /// give one, and only locales enabled through the `cldr-<locale>` features are searched
+    ///
+    /// ```
+    /// # use iso::country::{Iso3166_1_alpha_2, Country};
+    /// assert_eq!(Iso3166_1_alpha_2::De.localized_name("fr"), Some("Allemagne"));
+    /// # #[cfg(not(feature = "ascii-names"))]
+    /// assert_eq!(Iso3166_1_alpha_2::Vn.localized_name("fr_CA"), Some("Viêt Nam"));
+    /// assert_eq!(Iso3166_1_alpha_2::Um.localized_name("en"), Some("U.S. Outlying Islands"));
+    /// assert_eq!(Iso3166_1_alpha_2::Um.localized_name("en-AU"), Some("US Outlying Islands"));
+    /// assert_eq!(Iso3166_1_alpha_2::De.localized_name("xx"), None);
+    /// ```
+    #[cfg(feature = "cldr")]
+    fn localized_name(&self, locale: &str) -> Option<&'static str>;
+
+    /// Returns the country's English short name, exactly as it is published by ISO 3166-1
+    ///
+    /// ```
+    /// # use iso::country::{Iso3166_1_alpha_2, Country};
+    /// assert_eq!(Iso3166_1_alpha_2::Bs.short_name(), "Bahamas (the)");
+    /// ```
+    #[cfg(not(feature = "without-names"))]
+    fn short_name(&self) -> &'static str;
+
+    /// Returns the country's full formal name, if ISO 3166-1 gives it one
+    ///
+    /// ```
+    /// # use iso::country::{Iso3166_1_alpha_2, Country};
+    /// assert_eq!(Iso3166_1_alpha_2::Fr.full_name(), Some("the French Republic"));
+    /// assert_eq!(Iso3166_1_alpha_2::Jp.full_name(), None);
+    /// ```
+    #[cfg(not(feature = "without-names"))]
+    fn full_name(&self) -> Option<&'static str>;
+
+    /// Returns the name the country is commonly known by, suitable for display
+    ///
+    /// ```
+    /// # use iso::country::{Iso3166_1_alpha_2, Country};
+    /// assert_eq!(Iso3166_1_alpha_2::Gb.common_name(), "United Kingdom");
+    /// assert_eq!(Iso3166_1_alpha_2::Tw.common_name(), "Taiwan");
+    /// ```
+    #[cfg(not(feature = "without-names"))]
+    fn common_name(&self) -> &'static str;
+
+    /// Returns the name the country is commonly known by without any leading article, for use when
+    /// sorting by name
+    ///
+    /// ```
+    /// # use iso::country::{Iso3166_1_alpha_2, Country};
+    /// assert_eq!(Iso3166_1_alpha_2::Gm.common_name(), "The Gambia");
+    /// assert_eq!(Iso3166_1_alpha_2::Gm.sort_name(), "Gambia");
+    /// ```
+    #[cfg(not(feature = "without-names"))]
+    fn sort_name(&self) -> &'static str;
+
     /// Returns the country's ISO 3166-1 numeric code
     fn numeric(&self) -> u16;
 
     /// Returns the country's corresponding langugae code as a `&str` based on the underlying format
     fn code(&self) -> &'static str;
+
+    /// Returns the country's ITU-T E.164 calling codes as well as the prefixes dialed within it
+    fn calling_codes(&self) -> CallingCodes;
+
+    /// Returns the country code top-level domain in use by the country, if one has been delegated
+    fn cctld(&self) -> Option<&'static str>;
+
+    /// Returns the countries sharing a land border with the country
+    fn neighbors(&self) -> &'static [Iso3166_1_alpha_2];
+
+    /// Returns whether or not the country is independent, per ISO 3166-1
+    fn independent(&self) -> bool;
+
+    /// Returns the country administering the country, if it is not independent and is administered
+    /// by one
+    fn sovereign(&self) -> Option<Iso3166_1_alpha_2>;
+
+    /// Returns an iterator over the territories the country administers
+    fn dependencies(&self) -> Dependencies;
 }
 
+// note: the country table is indexed by the discriminant of an `Iso3166_1_alpha_2`, and each of
+// its rows holds the country's alpha-2 and alpha-3 codes, its numeric code, its name and its
+// french name. each code table is indexed by the discriminant of its code, and each of its rows
+// holds the code's variant, the code as a string and the country's alpha-2 code, while the numeric
+// code table is sorted by numeric code
+#[cfg(not(feature = "without-names"))]
+country_identifiers_from_table!(static COUNTRIES: iso3166_1_alpha_2 => [iso3166_1_alpha_3, "iso3166_1_numeric", "name", "name_fr"]);
+#[cfg(feature = "without-names")]
+country_identifiers_from_table!(static COUNTRIES: iso3166_1_alpha_2 => [iso3166_1_alpha_3, "iso3166_1_numeric"]);
+country_identifiers_from_table!(static ALPHA_2: iso3166_1_alpha_2 => ["iso3166_1_alpha_2", iso3166_1_alpha_2]);
+country_identifiers_from_table!(static ALPHA_3: iso3166_1_alpha_3 => ["iso3166_1_alpha_3", iso3166_1_alpha_2]);
+country_identifiers_from_table!(static NUMERIC: "iso3166_1_numeric" => [iso3166_1_alpha_2]);
+
 //TODO: consider making this into a derive macro like what is said in the language file
 macro_rules! country_impl {
-    ($country:ident, $country_as_string:literal) => {
+    ($country:ident, $codes:ident, $length:literal) => {
+        impl $country {
+            /// Returns the index of the country's row within the country table
+            const fn table_index(self) -> usize {
+                $codes[self as usize].2 as usize
+            }
+
+            /// Returns the country's name, like [`Country::name`] but within a constant context
+            #[cfg(not(feature = "without-names"))]
+            pub const fn name(self) -> &'static str {
+                table_name!(COUNTRIES_NAMES, COUNTRIES[self.table_index()].3)
+            }
+
+            /// Returns the country's name in French, like [`Country::name_fr`] but within a
+            /// constant context
+            #[cfg(not(feature = "without-names"))]
+            pub const fn name_fr(self) -> &'static str {
+                table_name!(COUNTRIES_NAMES, COUNTRIES[self.table_index()].4)
+            }
+
+            /// Returns the country's ISO 3166-1 numeric code, like [`Country::numeric`] but within
+            /// a constant context
+            pub const fn numeric(self) -> u16 {
+                COUNTRIES[self.table_index()].2
+            }
+
+            /// Returns the country's corresponding country code, like [`Country::code`] but within
+            /// a constant context
+            pub const fn code(self) -> &'static str {
+                $codes[self as usize].1
+            }
+
+            /// Parses a country code given as bytes, like [`FromStr::from_str`] but within a
+            /// constant context
+            pub const fn from_bytes(code: &[u8]) -> Option<Self> {
+                match find_code!($codes, code) {
+                    Some(index) => Some($codes[index].0),
+                    None => None,
+                }
+            }
+
+            /// Returns an iterator over every code within the code set, in order of their codes
+            pub fn iter() -> impl ExactSizeIterator<Item = Self> + DoubleEndedIterator + Clone {
+                $codes.iter().map(|row| row.0)
+            }
+        }
+
         impl Country for $country {
+            #[cfg(not(feature = "without-names"))]
             fn name(&self) -> &'static str {
-                country_identifiers_from_table!(match &self: $country => "name")
+                $country::name(*self)
+            }
+
+            #[cfg(not(feature = "without-names"))]
+            fn name_fr(&self) -> &'static str {
+                $country::name_fr(*self)
+            }
+
+            #[cfg(feature = "cldr")]
+            fn localized_name(&self, locale: &str) -> Option<&'static str> {
+                let country: Iso3166_1_alpha_2 = (*self).into();
+                cldr::localized(locale, |locale| {
+                    cldr_from_table!(match locale, country: Iso3166_1_alpha_2)
+                })
+            }
+
+            #[cfg(not(feature = "without-names"))]
+            fn short_name(&self) -> &'static str {
+                name::short_name_of((*self).into())
+            }
+
+            #[cfg(not(feature = "without-names"))]
+            fn full_name(&self) -> Option<&'static str> {
+                name::full_name_of((*self).into())
+            }
+
+            #[cfg(not(feature = "without-names"))]
+            fn common_name(&self) -> &'static str {
+                name::common_name_of((*self).into())
+            }
+
+            #[cfg(not(feature = "without-names"))]
+            fn sort_name(&self) -> &'static str {
+                name::sort_name_of((*self).into())
             }
 
             fn numeric(&self) -> u16 {
-                country_identifiers_from_table!(match &self: $country => "Iso3166_1_numeric")
+                $country::numeric(*self)
             }
 
             fn code(&self) -> &'static str {
-                country_identifiers_from_table!(match &self: $country => $country_as_string)
+                $country::code(*self)
+            }
+
+            fn calling_codes(&self) -> CallingCodes {
+                CallingCodes::of((*self).into())
+            }
+
+            fn cctld(&self) -> Option<&'static str> {
+                cctld::cctld_of((*self).into())
+            }
+
+            fn neighbors(&self) -> &'static [Iso3166_1_alpha_2] {
+                border::neighbors_of((*self).into())
+            }
+
+            fn independent(&self) -> bool {
+                sovereignty::independent_of((*self).into())
+            }
+
+            fn sovereign(&self) -> Option<Iso3166_1_alpha_2> {
+                sovereignty::sovereign_of((*self).into())
+            }
+
+            fn dependencies(&self) -> Dependencies {
+                sovereignty::dependencies_of((*self).into())
+            }
+        }
+
+        impl CodeEnumeration for $country {
+            fn is_well_formed(code: &[u8]) -> bool {
+                code.len() == $length && code.iter().all(u8::is_ascii_uppercase)
+            }
+
+            fn to_code(self) -> &'static str {
+                self.code()
             }
         }
 
         impl fmt::Display for $country {
             fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                formatter.write_str(self.name())
+                // the code stands in for the name when names are left out
+                #[cfg(feature = "without-names")]
+                let name = self.code();
+                #[cfg(not(feature = "without-names"))]
+                let name = self.name();
+                formatter.write_str(name)
             }
         }
 
@@ -109,7 +444,10 @@ macro_rules! country_impl {
             type Error = Error;
 
             fn try_from(c: u16) -> Result<Self, <Self as TryFrom<u16>>::Error> {
-                country_identifiers_from_table!(match c: "Iso3166_1_numeric" => $country).ok_or(Error::InvalidCountryCode(c.to_string()))
+                NUMERIC
+                    .binary_search_by_key(&c, |(numeric, _)| *numeric)
+                    .map(|index| NUMERIC[index].1.into())
+                    .map_err(|_| Error::InvalidCountryCode(InlineCode::from_numeric(c)))
             }
         }
 
@@ -117,26 +455,56 @@ macro_rules! country_impl {
             type Err = Error;
 
             fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
-                country_identifiers_from_table!(match s: $country_as_string => $country).ok_or(Error::InvalidCountryCode(s.to_string()))
+                Self::try_from(s.as_bytes())
             }
         }
-    }
-}
 
-macro_rules! country_impl_from {
-    ($from:ident, $to:ident) => {
-        impl From<$from> for $to {
-            fn from(c: $from) -> Self {
-                country_identifiers_from_table!(match c: $from => $to)
+        impl TryFrom<&[u8]> for $country {
+            type Error = Error;
+
+            fn try_from(c: &[u8]) -> Result<Self, <Self as TryFrom<&[u8]>>::Error> {
+                Self::from_bytes(c).ok_or_else(|| Error::InvalidCountryCode(InlineCode::new(c)))
             }
         }
     }
 }
 
 country_identifiers_from_table!(enum Iso3166_1_alpha_2: iso3166_1_alpha_2);
-country_impl!(Iso3166_1_alpha_2, "Iso3166_1_alpha_2");
-country_impl_from!(Iso3166_1_alpha_3, Iso3166_1_alpha_2);
+country_impl!(Iso3166_1_alpha_2, ALPHA_2, 2);
+
+impl From<Iso3166_1_alpha_3> for Iso3166_1_alpha_2 {
+    fn from(c: Iso3166_1_alpha_3) -> Self {
+        ALPHA_3[c as usize].2
+    }
+}
 
 country_identifiers_from_table!(enum Iso3166_1_alpha_3: iso3166_1_alpha_3);
-country_impl!(Iso3166_1_alpha_3, "Iso3166_1_alpha_3");
-country_impl_from!(Iso3166_1_alpha_2, Iso3166_1_alpha_3);
+country_impl!(Iso3166_1_alpha_3, ALPHA_3, 3);
+
+impl From<Iso3166_1_alpha_2> for Iso3166_1_alpha_3 {
+    fn from(c: Iso3166_1_alpha_2) -> Self {
+        COUNTRIES[c as usize].1
+    }
+}
+
+literal_macro_from_table! {
+    /// Turns an ISO 3166-1 alpha-2 or alpha-3 code, given as a string literal, into the variant of
+    /// [`Iso3166_1_alpha_2`](crate::country::Iso3166_1_alpha_2) or
+    /// [`Iso3166_1_alpha_3`](crate::country::Iso3166_1_alpha_3) it corresponds to
+    ///
+    /// The code is checked at compile time, and an invalid one is reported as an error. As the
+    /// macro expands to the variant itself, it may be used within a constant context
+    ///
+    /// ```
+    /// # use iso::{country, country::{Iso3166_1_alpha_2, Iso3166_1_alpha_3}};
+    /// const UNITED_STATES: Iso3166_1_alpha_2 = country!("US");
+    /// assert_eq!(UNITED_STATES, Iso3166_1_alpha_2::Us);
+    /// assert_eq!(country!("DEU"), Iso3166_1_alpha_3::Deu);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let country = iso::country!("ZZ");
+    /// ```
+    #[macro_export]
+    country: iso3166_1_alpha_2, iso3166_1_alpha_3
+}
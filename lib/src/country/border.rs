@@ -0,0 +1,120 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Type definitions related to the land borders between countries
+
+use iso_macro::data_from_table;
+
+use super::Iso3166_1_alpha_2;
+
+data_from_table!(static BORDERS: "border.tab" => ["neighbors"]);
+
+/// The number of countries within the border table, each of which the route finding below keeps
+/// track of
+const COUNTRIES: usize = BORDERS.len();
+
+/// A marker for countries which have not yet been reached while finding a route
+const UNVISITED: u16 = u16::MAX;
+
+// countries are tracked by their index within the border table, which must fit within a `u16`
+// without being mistaken for the marker above
+const _: () = assert!(COUNTRIES < UNVISITED as usize);
+
+pub(crate) fn neighbors_of(country: Iso3166_1_alpha_2) -> &'static [Iso3166_1_alpha_2] {
+    data_from_table!(match country: "border.tab" => "neighbors")
+}
+
+fn index_of(country: Iso3166_1_alpha_2) -> Option<usize> {
+    BORDERS.iter().position(|(c, _)| *c == country)
+}
+
+/// A route over land between two countries, passing through as few countries as possible
+///
+/// A route has room for every country within the border table, so it is not `Copy` and is best
+/// passed around by reference
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{self, Iso3166_1_alpha_2, Country};
+/// assert_eq!(Iso3166_1_alpha_2::Pt.neighbors(), &[Iso3166_1_alpha_2::Es]);
+/// assert!(Iso3166_1_alpha_2::Jp.neighbors().is_empty());
+///
+/// let route = country::shortest_land_route(Iso3166_1_alpha_2::Pt, Iso3166_1_alpha_2::Pl).unwrap();
+///
+/// assert_eq!(route.crossings(), 4);
+/// assert_eq!(route.countries().next(), Some(Iso3166_1_alpha_2::Pt));
+/// assert_eq!(route.countries().last(), Some(Iso3166_1_alpha_2::Pl));
+/// assert!(country::shortest_land_route(Iso3166_1_alpha_2::Gb, Iso3166_1_alpha_2::Fr).is_none());
+/// ```
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub struct LandRoute {
+    /// The indices of the countries along the route within the border table
+    path: [u16; COUNTRIES],
+    length: usize,
+}
+
+impl LandRoute {
+    /// Returns the countries along the route, beginning with the country the route starts from and
+    /// ending with the country it leads to
+    pub fn countries(&self) -> impl Iterator<Item = Iso3166_1_alpha_2> + '_ {
+        self.path[..self.length]
+            .iter()
+            .map(|index| BORDERS[usize::from(*index)].0)
+    }
+
+    /// Returns the number of borders crossed along the route
+    pub fn crossings(&self) -> usize {
+        self.length - 1
+    }
+}
+
+/// Returns the route over land between two countries which crosses the fewest borders, if there is
+/// one
+pub fn shortest_land_route(from: Iso3166_1_alpha_2, to: Iso3166_1_alpha_2) -> Option<LandRoute> {
+    let from = index_of(from)?;
+    let to = index_of(to)?;
+
+    // a breadth-first search, recording the country each country was first reached from
+    let mut previous = [UNVISITED; COUNTRIES];
+    let mut queue = [0; COUNTRIES];
+    let (mut head, mut tail) = (0, 1);
+    queue[0] = from;
+    previous[from] = from as u16;
+    while head < tail && previous[to] == UNVISITED {
+        let current = queue[head];
+        head += 1;
+        for neighbor in BORDERS[current].1.iter().copied().filter_map(index_of) {
+            if previous[neighbor] == UNVISITED {
+                previous[neighbor] = current as u16;
+                queue[tail] = neighbor;
+                tail += 1;
+            }
+        }
+    }
+
+    if previous[to] == UNVISITED {
+        return None;
+    }
+
+    let mut path = [0; COUNTRIES];
+    let mut length = 0;
+    let mut current = to;
+    loop {
+        path[length] = current as u16;
+        length += 1;
+        if current == from {
+            break;
+        }
+        current = usize::from(previous[current]);
+    }
+    path[..length].reverse();
+
+    Some(LandRoute { path, length })
+}
@@ -0,0 +1,122 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Type definitions related to the ITU-T E.164 country calling codes
+
+use iso_macro::data_from_table;
+
+use super::Iso3166_1_alpha_2;
+
+data_from_table!(static CALLING_CODES: "calling_code.tab" => ["calling-codes"]);
+
+/// The ITU-T E.164 calling codes and dialing prefixes used within a country
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{Iso3166_1_alpha_2, Country};
+/// let jamaica = Iso3166_1_alpha_2::Jm.calling_codes();
+///
+/// assert_eq!(jamaica.codes(), &["1 876", "1 658"]);
+/// assert_eq!(jamaica.trunk_prefix(), Some("1"));
+/// assert_eq!(jamaica.international_prefix(), Some("011"));
+/// ```
+#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
+pub struct CallingCodes {
+    codes: &'static [&'static str],
+    trunk_prefix: Option<&'static str>,
+    international_prefix: Option<&'static str>,
+}
+
+impl CallingCodes {
+    pub(crate) fn of(country: Iso3166_1_alpha_2) -> Self {
+        Self {
+            codes: data_from_table!(match country: "calling_code.tab" => "calling-codes"),
+            trunk_prefix: data_from_table!(match country: "calling_code.tab" => "trunk-prefix"),
+            international_prefix: data_from_table!(match country: "calling_code.tab" => "international-prefix"),
+        }
+    }
+
+    /// Returns the calling codes assigned to the country
+    ///
+    /// Where a calling code is shared between several countries and the country has been given
+    /// its own area codes within it, each area code is included, separated from the calling code
+    /// by a space (e.g. `1 876` for Jamaica)
+    pub fn codes(&self) -> &'static [&'static str] {
+        self.codes
+    }
+
+    /// Returns the prefix dialed before a number from within the same country, if one is used
+    pub fn trunk_prefix(&self) -> Option<&'static str> {
+        self.trunk_prefix
+    }
+
+    /// Returns the prefix dialed before a calling code when calling out of the country, if one is
+    /// used
+    pub fn international_prefix(&self) -> Option<&'static str> {
+        self.international_prefix
+    }
+}
+
+/// Returns the number of digits of the provided number matched by the calling code, if it is a
+/// prefix of the number
+fn matched_digits(code: &str, number: &str) -> Option<usize> {
+    let mut digits = number.chars().filter(char::is_ascii_digit);
+    let mut matched = 0;
+    for digit in code.chars().filter(char::is_ascii_digit) {
+        if digits.next()? != digit {
+            return None;
+        }
+        matched += 1;
+    }
+    Some(matched)
+}
+
+/// Returns the countries which may be dialed by an E.164 number, by finding the longest calling
+/// code (including any area codes) it begins with
+///
+/// Any characters in the number besides digits (such as the leading `+`, spaces or dashes) are
+/// ignored. More than one country is returned if the calling code matched is shared by all of them
+/// and they are not distinguished by area code, as is the case for the United States and Canada.
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{self, Iso3166_1_alpha_2};
+/// assert_eq!(
+///     country::resolve_calling_code("+1 876 555 0100").collect::<Vec<_>>(),
+///     [Iso3166_1_alpha_2::Jm]
+/// );
+/// assert_eq!(
+///     country::resolve_calling_code("+1 212 555 0100").collect::<Vec<_>>(),
+///     [Iso3166_1_alpha_2::Ca, Iso3166_1_alpha_2::Us]
+/// );
+/// assert_eq!(
+///     country::resolve_calling_code("+7 727 000 0000").collect::<Vec<_>>(),
+///     [Iso3166_1_alpha_2::Kz]
+/// );
+/// assert_eq!(country::resolve_calling_code("+999").next(), None);
+/// ```
+pub fn resolve_calling_code(number: &str) -> impl Iterator<Item = Iso3166_1_alpha_2> + '_ {
+    let longest = CALLING_CODES
+        .iter()
+        .flat_map(|(_, codes)| codes.iter())
+        .filter_map(|code| matched_digits(code, number))
+        .max();
+
+    CALLING_CODES
+        .iter()
+        .filter(move |(_, codes)| {
+            longest.is_some()
+                && codes
+                    .iter()
+                    .any(|code| matched_digits(code, number) == longest)
+        })
+        .map(|(country, _)| *country)
+}
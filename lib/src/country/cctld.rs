@@ -0,0 +1,108 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Type definitions related to country code top-level domains
+
+use core::{convert::TryFrom, str};
+use iso_macro::data_from_table;
+
+use super::{ExceptionallyReserved, Iso3166_1_alpha_2};
+
+data_from_table!(static CCTLDS: "cctld.tab" => ["cctlds", "idn-cctlds", "idn-cctlds-ascii"]);
+
+/// Returns whether or not every top-level domain within a list is written with a leading dot and
+/// is not empty otherwise
+const fn dotted(tlds: &[&str]) -> bool {
+    let mut index = 0;
+    while index < tlds.len() {
+        let tld = tlds[index].as_bytes();
+        if tld.len() < 2 || tld[0] != b'.' {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+// the top-level domains are compared below without their leading dot, which is checked to be there
+// as the crate is built
+const _: () = {
+    let mut row = 0;
+    while row < CCTLDS.len() {
+        let (_, cctlds, idn_cctlds, idn_cctlds_ascii) = CCTLDS[row];
+        assert!(
+            dotted(cctlds) && dotted(idn_cctlds) && dotted(idn_cctlds_ascii),
+            "every top-level domain within cctld.tab must be written with a leading dot"
+        );
+        row += 1;
+    }
+};
+
+pub(crate) fn cctld_of(country: Iso3166_1_alpha_2) -> Option<&'static str> {
+    let cctlds: &'static [&'static str] = data_from_table!(match country: "cctld.tab" => "cctlds");
+    cctlds.first().copied()
+}
+
+/// Returns the country a domain name (or a lone top-level domain) belongs to, based on its
+/// top-level domain
+///
+/// Internationalized top-level domains are recognized in both their unicode and ascii-compatible
+/// forms (compared after lowercasing either, so that `.РФ` is `.рф`), and top-level domains
+/// matching an exceptionally reserved code (such as `.ac` and `.uk`) are resolved to the country
+/// the area they are reserved for is a part of. Top-level domains which do not belong to any one
+/// country, like `.eu` or `.su`, do not resolve to anything.
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{self, Iso3166_1_alpha_2, Country};
+/// assert_eq!(Iso3166_1_alpha_2::Gb.cctld(), Some(".uk"));
+/// assert_eq!(country::resolve_cctld("www.example.co.uk"), Some(Iso3166_1_alpha_2::Gb));
+/// assert_eq!(country::resolve_cctld(".AC"), Some(Iso3166_1_alpha_2::Sh));
+/// assert_eq!(country::resolve_cctld("пример.рф"), Some(Iso3166_1_alpha_2::Ru));
+/// assert_eq!(country::resolve_cctld("ПРИМЕР.РФ"), Some(Iso3166_1_alpha_2::Ru));
+/// assert_eq!(country::resolve_cctld("xn--e1afmkfd.xn--p1ai."), Some(Iso3166_1_alpha_2::Ru));
+/// assert_eq!(country::resolve_cctld("例子.中国"), Some(Iso3166_1_alpha_2::Cn));
+/// assert_eq!(country::resolve_cctld("europa.eu"), None);
+/// assert_eq!(country::resolve_cctld("example.su"), None);
+/// assert_eq!(country::resolve_cctld("example.com"), None);
+/// ```
+pub fn resolve_cctld(domain: &str) -> Option<Iso3166_1_alpha_2> {
+    let domain = domain.strip_suffix('.').unwrap_or(domain);
+    let label = domain
+        .rsplit('.')
+        .next()
+        .filter(|label| !label.is_empty())?;
+    let matches = |tld: &&str| {
+        tld[1..]
+            .chars()
+            .flat_map(char::to_lowercase)
+            .eq(label.chars().flat_map(char::to_lowercase))
+    };
+
+    CCTLDS
+        .iter()
+        .find(|(_, cctlds, idn_cctlds, idn_cctlds_ascii)| {
+            cctlds
+                .iter()
+                .chain(idn_cctlds.iter())
+                .chain(idn_cctlds_ascii.iter())
+                .any(matches)
+        })
+        .map(|(country, ..)| *country)
+        .or_else(|| {
+            let mut code = <[u8; 2]>::try_from(label.as_bytes()).ok()?;
+            code.make_ascii_uppercase();
+            str::from_utf8(&code)
+                .ok()?
+                .parse::<ExceptionallyReserved>()
+                .ok()?
+                .country()
+        })
+}
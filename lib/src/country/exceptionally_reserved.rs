@@ -0,0 +1,135 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Type definitions for the exceptionally reserved ISO 3166-1 code elements
+//!
+//! The ISO 3166 maintenance agency reserves a handful of alpha-2 codes at the request of other
+//! organizations, for areas which are either a part of a country already within ISO 3166-1 or are
+//! not countries at all. Many of these codes see use outside of the standard (`UK` and `AC` are
+//! both top-level domains, for example), so they are enumerated here along with the country they
+//! are a part of, where there is one.
+
+use core::{
+    fmt,
+    str::{self, FromStr},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Error, Iso3166_1_alpha_2};
+use crate::InlineCode;
+
+/// Enumeration over all exceptionally reserved ISO 3166-1 alpha-2 codes
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{ExceptionallyReserved, Iso3166_1_alpha_2};
+/// let united_kingdom: ExceptionallyReserved = "UK".parse().unwrap();
+///
+/// assert_eq!(united_kingdom.name(), "United Kingdom");
+/// assert_eq!(united_kingdom.country(), Some(Iso3166_1_alpha_2::Gb));
+/// assert_eq!(ExceptionallyReserved::Eu.country(), None);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
+#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
+pub enum ExceptionallyReserved {
+    Ac,
+    Cp,
+    Dg,
+    Ea,
+    Eu,
+    Ez,
+    Fx,
+    Ic,
+    Su,
+    Ta,
+    Uk,
+    Un,
+}
+
+impl ExceptionallyReserved {
+    /// Returns the name of the area the code is reserved for
+    pub fn name(&self) -> &'static str {
+        match &self {
+            Self::Ac => "Ascension Island",
+            Self::Cp => "Clipperton Island",
+            Self::Dg => "Diego Garcia",
+            Self::Ea => "Ceuta, Melilla",
+            Self::Eu => "European Union",
+            Self::Ez => "Eurozone",
+            Self::Fx => "France, Metropolitan",
+            Self::Ic => "Canary Islands",
+            Self::Su => "USSR",
+            Self::Ta => "Tristan da Cunha",
+            Self::Uk => "United Kingdom",
+            Self::Un => "United Nations",
+        }
+    }
+
+    /// Returns the code as a `&str`
+    pub fn code(&self) -> &'static str {
+        match &self {
+            Self::Ac => "AC",
+            Self::Cp => "CP",
+            Self::Dg => "DG",
+            Self::Ea => "EA",
+            Self::Eu => "EU",
+            Self::Ez => "EZ",
+            Self::Fx => "FX",
+            Self::Ic => "IC",
+            Self::Su => "SU",
+            Self::Ta => "TA",
+            Self::Uk => "UK",
+            Self::Un => "UN",
+        }
+    }
+
+    /// Returns the country the area the code is reserved for is a part of, if it is a part of one
+    pub fn country(&self) -> Option<Iso3166_1_alpha_2> {
+        Some(match &self {
+            Self::Ac | Self::Ta => Iso3166_1_alpha_2::Sh,
+            Self::Cp | Self::Fx => Iso3166_1_alpha_2::Fr,
+            Self::Dg => Iso3166_1_alpha_2::Io,
+            Self::Ea | Self::Ic => Iso3166_1_alpha_2::Es,
+            Self::Uk => Iso3166_1_alpha_2::Gb,
+            Self::Eu | Self::Ez | Self::Su | Self::Un => return None,
+        })
+    }
+}
+
+impl fmt::Display for ExceptionallyReserved {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+impl FromStr for ExceptionallyReserved {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+        Ok(match s {
+            "AC" => Self::Ac,
+            "CP" => Self::Cp,
+            "DG" => Self::Dg,
+            "EA" => Self::Ea,
+            "EU" => Self::Eu,
+            "EZ" => Self::Ez,
+            "FX" => Self::Fx,
+            "IC" => Self::Ic,
+            "SU" => Self::Su,
+            "TA" => Self::Ta,
+            "UK" => Self::Uk,
+            "UN" => Self::Un,
+            _ => return Err(Error::InvalidCountryCode(InlineCode::new(s.as_bytes()))),
+        })
+    }
+}
@@ -0,0 +1,31 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! The various names given to each country
+
+use iso_macro::data_from_table;
+
+use super::Iso3166_1_alpha_2;
+
+pub(crate) fn short_name_of(country: Iso3166_1_alpha_2) -> &'static str {
+    data_from_table!(match country: "name.tab" => "short-name")
+}
+
+pub(crate) fn full_name_of(country: Iso3166_1_alpha_2) -> Option<&'static str> {
+    data_from_table!(match country: "name.tab" => "full-name")
+}
+
+pub(crate) fn common_name_of(country: Iso3166_1_alpha_2) -> &'static str {
+    data_from_table!(match country: "name.tab" => "common-name")
+}
+
+pub(crate) fn sort_name_of(country: Iso3166_1_alpha_2) -> &'static str {
+    let name = common_name_of(country);
+    name.strip_prefix("The ").unwrap_or(name)
+}
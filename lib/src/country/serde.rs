@@ -0,0 +1,643 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Alternative representations of the country code enumerations, for use with `#[serde(with)]`
+//!
+//! The code enumerations are (de)serialized as their own code by default, so an
+//! [`Iso3166_1_alpha_2`] is always written as `"US"`. Each module within this one (de)serializes
+//! a field holding either code enumeration in some other way instead:
+//!
+//! - [`numeric`]: as its numeric code, such as `840`
+//! - [`alpha_2`] and [`alpha_3`]: as its alpha-2 or alpha-3 code, whichever the field holds
+//! - [`object`]: as an object holding its code and name, such as
+//!   `{"code": "US", "name": "United States of America"}`
+//! - [`lenient`]: as its code, while accepting any of its codes in any case when deserializing
+//! - [`compact`]: as its code within human-readable formats (such as JSON), and as its numeric code
+//!   (a `u16`) within any other format (such as bincode)
+//!
+//! # Basic usage
+//!
+//! ```
+//! # use iso::country::{self, Iso3166_1_alpha_2, Iso3166_1_alpha_3};
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Address {
+//!     #[serde(with = "country::serde::alpha_3")]
+//!     country: Iso3166_1_alpha_2,
+//!     #[serde(with = "country::serde::numeric")]
+//!     billing: Iso3166_1_alpha_3,
+//!     #[serde(with = "country::serde::lenient")]
+//!     shipping: Iso3166_1_alpha_2,
+//! }
+//!
+//! let address: Address =
+//!     serde_json::from_str(r#"{"country": "DEU", "billing": 840, "shipping": "fra"}"#).unwrap();
+//! assert_eq!(
+//!     address,
+//!     Address {
+//!         country: Iso3166_1_alpha_2::De,
+//!         billing: Iso3166_1_alpha_3::Usa,
+//!         shipping: Iso3166_1_alpha_2::Fr,
+//!     }
+//! );
+//! assert_eq!(
+//!     serde_json::to_string(&address).unwrap(),
+//!     r#"{"country":"DEU","billing":840,"shipping":"FR"}"#
+//! );
+//! ```
+
+use core::{convert::TryFrom, fmt, str};
+use serde::de;
+
+use super::{Error, Iso3166_1_alpha_2, Iso3166_1_alpha_3};
+
+/// Parses a numeric code given as a string of one to three digits
+fn parse_numeric(code: &str) -> Option<u16> {
+    if (1..=3).contains(&code.len()) && code.bytes().all(|c| c.is_ascii_digit()) {
+        code.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Parses an alpha-2, alpha-3 or numeric code, in any case and with any surrounding whitespace
+fn parse_any(code: &str) -> Result<Iso3166_1_alpha_2, Error> {
+    let code = code.trim();
+    if let Some(numeric) = parse_numeric(code) {
+        return Iso3166_1_alpha_2::try_from(numeric);
+    }
+
+    let mut uppercase = [0; 3];
+    let length = code.len();
+    if length == 2 || length == 3 {
+        uppercase[..length].copy_from_slice(code.as_bytes());
+        uppercase.make_ascii_uppercase();
+        let uppercase = &uppercase[..length];
+        if let Some(country) = Iso3166_1_alpha_2::from_bytes(uppercase) {
+            return Ok(country);
+        }
+        if let Some(country) = Iso3166_1_alpha_3::from_bytes(uppercase) {
+            return Ok(country.into());
+        }
+    }
+    code.parse::<Iso3166_1_alpha_2>()
+}
+
+/// A visitor deserializing a country from a string, parsed by the provided function, or from a
+/// numeric code given as an integer (if a function parsing those is provided)
+struct Visitor<T> {
+    expecting: &'static str,
+    parse: fn(&str) -> Result<T, Error>,
+    numeric: Option<fn(u16) -> Result<T, Error>>,
+}
+
+impl<'de, T> de::Visitor<'de> for Visitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        (self.parse)(v).map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match str::from_utf8(v) {
+            Ok(v) => self.visit_str(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match (self.numeric, u16::try_from(v)) {
+            (Some(numeric), Ok(v)) => numeric(v).map_err(de::Error::custom),
+            _ => Err(de::Error::invalid_value(de::Unexpected::Unsigned(v), &self)),
+        }
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Signed(v), &self)),
+        }
+    }
+}
+
+/// (De)serializes a country as its ISO 3166-1 numeric code
+///
+/// Numeric codes are written as integers, and may be read either as integers or as strings of
+/// digits (with or without their leading zeroes)
+///
+/// ```
+/// # use iso::country::{self, Iso3166_1_alpha_2};
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Phone(#[serde(with = "country::serde::numeric")] Iso3166_1_alpha_2);
+///
+/// assert_eq!(serde_json::to_string(&Phone(Iso3166_1_alpha_2::Af)).unwrap(), "4");
+/// assert_eq!(serde_json::from_str::<Phone>("\"004\"").unwrap(), Phone(Iso3166_1_alpha_2::Af));
+/// assert!(serde_json::from_str::<Phone>("999").is_err());
+/// ```
+pub mod numeric {
+    use core::convert::TryFrom;
+    use serde::{Deserializer, Serializer};
+
+    use super::{parse_numeric, Visitor};
+    use crate::{
+        country::{Country, Error, Iso3166_1_alpha_2},
+        InlineCode,
+    };
+
+    /// Serializes a country as its numeric code
+    pub fn serialize<T, S>(country: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Country,
+        S: Serializer,
+    {
+        serializer.serialize_u16(country.numeric())
+    }
+
+    /// Deserializes a country from its numeric code
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: From<Iso3166_1_alpha_2>,
+        D: Deserializer<'de>,
+    {
+        let visitor = Visitor {
+            expecting: "an ISO 3166-1 numeric code",
+            parse: |code| {
+                parse_numeric(code)
+                    .ok_or_else(|| Error::InvalidCountryCode(InlineCode::new(code.as_bytes())))
+                    .and_then(Iso3166_1_alpha_2::try_from)
+            },
+            numeric: Some(Iso3166_1_alpha_2::try_from),
+        };
+
+        // note: formats which are not self-describing cannot be asked for any value
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor).map(T::from)
+        } else {
+            deserializer.deserialize_u16(visitor).map(T::from)
+        }
+    }
+}
+
+macro_rules! code_module {
+    ($(#[$attribute:meta])* $module:ident, $code:ident, $expecting:literal) => {
+        $(#[$attribute])*
+        pub mod $module {
+            use serde::{Deserializer, Serializer};
+
+            use super::Visitor;
+            use crate::country::$code;
+
+            /// Serializes a country as its code within the code set
+            pub fn serialize<T, S>(country: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: Copy + Into<$code>,
+                S: Serializer,
+            {
+                serializer.serialize_str((*country).into().code())
+            }
+
+            /// Deserializes a country from its code within the code set
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: From<$code>,
+                D: Deserializer<'de>,
+            {
+                deserializer
+                    .deserialize_str(Visitor {
+                        expecting: $expecting,
+                        parse: str::parse::<$code>,
+                        numeric: None,
+                    })
+                    .map(T::from)
+            }
+        }
+    };
+}
+
+code_module!(
+    /// (De)serializes a country as its ISO 3166-1 alpha-2 code, whichever code enumeration holds it
+    ///
+    /// ```
+    /// # use iso::country::{self, Iso3166_1_alpha_3};
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    /// struct Record(#[serde(with = "country::serde::alpha_2")] Iso3166_1_alpha_3);
+    ///
+    /// assert_eq!(serde_json::to_string(&Record(Iso3166_1_alpha_3::Jpn)).unwrap(), "\"JP\"");
+    /// assert_eq!(
+    ///     serde_json::from_str::<Record>("\"JP\"").unwrap(),
+    ///     Record(Iso3166_1_alpha_3::Jpn)
+    /// );
+    /// ```
+    alpha_2,
+    Iso3166_1_alpha_2,
+    "an ISO 3166-1 alpha-2 code"
+);
+
+code_module!(
+    /// (De)serializes a country as its ISO 3166-1 alpha-3 code, whichever code enumeration holds it
+    ///
+    /// ```
+    /// # use iso::country::{self, Iso3166_1_alpha_2};
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    /// struct Record(#[serde(with = "country::serde::alpha_3")] Iso3166_1_alpha_2);
+    ///
+    /// assert_eq!(serde_json::to_string(&Record(Iso3166_1_alpha_2::Jp)).unwrap(), "\"JPN\"");
+    /// assert!(serde_json::from_str::<Record>("\"JP\"").is_err());
+    /// ```
+    alpha_3,
+    Iso3166_1_alpha_3,
+    "an ISO 3166-1 alpha-3 code"
+);
+
+/// (De)serializes a country as an object holding its code and its name
+///
+/// Only the code is read when deserializing, so the name may be left out (or be in any language),
+/// and any other fields of the object are ignored
+///
+/// ```
+/// # use iso::country::{self, Iso3166_1_alpha_2};
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Record(#[serde(with = "country::serde::object")] Iso3166_1_alpha_2);
+///
+/// assert_eq!(
+///     serde_json::to_string(&Record(Iso3166_1_alpha_2::Fr)).unwrap(),
+///     r#"{"code":"FR","name":"France"}"#
+/// );
+/// assert_eq!(
+///     serde_json::from_str::<Record>(r#"{"code": "FR"}"#).unwrap(),
+///     Record(Iso3166_1_alpha_2::Fr)
+/// );
+///
+/// // the code may be read from an owned string (as it is from a reader), and from within formats
+/// // which are not self-describing
+/// let reader = &br#"{"code": "FR", "name": "France"}"#[..];
+/// assert_eq!(
+///     serde_json::from_reader::<_, Record>(reader).unwrap(),
+///     Record(Iso3166_1_alpha_2::Fr)
+/// );
+/// let bytes = bincode::serialize(&Record(Iso3166_1_alpha_2::Fr)).unwrap();
+/// assert_eq!(
+///     bincode::deserialize::<Record>(&bytes).unwrap(),
+///     Record(Iso3166_1_alpha_2::Fr)
+/// );
+/// ```
+#[cfg(not(feature = "without-names"))]
+pub mod object {
+    use core::{
+        fmt,
+        marker::PhantomData,
+        str::{self, FromStr},
+    };
+    use serde::{
+        de::{self, IgnoredAny, MapAccess, SeqAccess},
+        ser::SerializeStruct,
+        Deserialize, Deserializer, Serializer,
+    };
+
+    use crate::country::{Country, Error};
+
+    /// Serializes a country as an object holding its code and name
+    pub fn serialize<T, S>(country: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Country,
+        S: Serializer,
+    {
+        let mut object = serializer.serialize_struct("Country", 2)?;
+        object.serialize_field("code", country.code())?;
+        object.serialize_field("name", country.name())?;
+        object.end()
+    }
+
+    /// A field of the object, of which only the code is read
+    enum Field {
+        Code,
+        Other,
+    }
+
+    /// A visitor deserializing a field of the object from its name (or its index)
+    struct FieldVisitor;
+
+    impl<'de> de::Visitor<'de> for FieldVisitor {
+        type Value = Field;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a field name")
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(if v == 0 { Field::Code } else { Field::Other })
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_bytes(v.as_bytes())
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(if v == b"code" {
+                Field::Code
+            } else {
+                Field::Other
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    /// A visitor (and seed) deserializing the code field of the object, parsing it as it is read
+    struct Code<T>(PhantomData<T>);
+
+    impl<'de, T> de::Visitor<'de> for Code<T>
+    where
+        T: FromStr<Err = Error>,
+    {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a code")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            v.parse().map_err(de::Error::custom)
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match str::from_utf8(v) {
+                Ok(v) => self.visit_str(v),
+                Err(_) => Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &self)),
+            }
+        }
+    }
+
+    impl<'de, T> de::DeserializeSeed<'de> for Code<T>
+    where
+        T: FromStr<Err = Error>,
+    {
+        type Value = T;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_str(self)
+        }
+    }
+
+    /// A visitor deserializing the name field of the object, which is read and ignored (as formats
+    /// which are not self-describing cannot skip over a value of an unknown type)
+    struct Name;
+
+    impl<'de> de::Visitor<'de> for Name {
+        type Value = Name;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a name")
+        }
+
+        fn visit_str<E>(self, _: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(Name)
+        }
+
+        fn visit_bytes<E>(self, _: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(Name)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Name {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_str(Name)
+        }
+    }
+
+    /// A visitor deserializing a country from an object holding its code
+    struct Visitor<T>(PhantomData<T>);
+
+    impl<'de, T> de::Visitor<'de> for Visitor<T>
+    where
+        T: FromStr<Err = Error>,
+    {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an object holding an ISO 3166-1 code")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut country = None;
+            while let Some(key) = map.next_key::<Field>()? {
+                match key {
+                    Field::Other => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                    Field::Code if country.is_some() => {
+                        return Err(de::Error::duplicate_field("code"))
+                    }
+                    Field::Code => country = Some(map.next_value_seed(Code(PhantomData))?),
+                }
+            }
+            country.ok_or_else(|| de::Error::missing_field("code"))
+        }
+
+        // note: formats which are not self-describing hold structs as a sequence of their fields,
+        // which must each be read in turn
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let country = seq
+                .next_element_seed(Code(PhantomData))?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            seq.next_element::<Name>()?;
+            Ok(country)
+        }
+    }
+
+    /// Deserializes a country from an object holding its code
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr<Err = Error>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("Country", &["code", "name"], Visitor(PhantomData))
+    }
+}
+
+/// (De)serializes a country as its code, accepting any of its codes when deserializing
+///
+/// Alpha-2 and alpha-3 codes are accepted in any case, numeric codes are accepted either as
+/// integers or as strings of digits, and any surrounding whitespace is ignored. Countries are
+/// always serialized as the code the field holds, as they are by default
+///
+/// ```
+/// # use iso::country::{self, Iso3166_1_alpha_2};
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Record(#[serde(with = "country::serde::lenient")] Iso3166_1_alpha_2);
+///
+/// for input in &[r#""US""#, r#""us""#, r#"" usa ""#, r#""840""#, "840"] {
+///     assert_eq!(serde_json::from_str::<Record>(input).unwrap(), Record(Iso3166_1_alpha_2::Us));
+/// }
+/// assert_eq!(serde_json::to_string(&Record(Iso3166_1_alpha_2::Us)).unwrap(), "\"US\"");
+/// ```
+pub mod lenient {
+    use core::convert::TryFrom;
+    use serde::{Deserializer, Serializer};
+
+    use super::{parse_any, Visitor};
+    use crate::country::{Country, Iso3166_1_alpha_2};
+
+    /// Serializes a country as its code
+    pub fn serialize<T, S>(country: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Country,
+        S: Serializer,
+    {
+        serializer.serialize_str(country.code())
+    }
+
+    /// Deserializes a country from any of its codes
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: From<Iso3166_1_alpha_2>,
+        D: Deserializer<'de>,
+    {
+        let visitor = Visitor {
+            expecting: "an ISO 3166-1 alpha-2, alpha-3 or numeric code",
+            parse: parse_any,
+            numeric: Some(Iso3166_1_alpha_2::try_from),
+        };
+
+        // note: formats which are not self-describing cannot be asked for any value, so only codes
+        // (which are what is serialized) are accepted within them
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor).map(T::from)
+        } else {
+            deserializer.deserialize_str(visitor).map(T::from)
+        }
+    }
+}
+
+/// (De)serializes a country as its code within human-readable formats, and as its numeric code
+/// within any other format
+///
+/// Whether or not a format is human-readable is left to the format itself to say (through
+/// [`Serializer::is_human_readable`](serde::Serializer::is_human_readable)), and a numeric code
+/// takes up two bytes at most rather than the length of a code followed by the code itself
+///
+/// ```
+/// # use iso::country::{self, Iso3166_1_alpha_2};
+/// # use serde::{Deserialize, Serialize};
+/// # use serde_test::{assert_tokens, Configure, Token};
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Record(#[serde(with = "country::serde::compact")] Iso3166_1_alpha_2);
+///
+/// let record = Record(Iso3166_1_alpha_2::Us);
+/// assert_eq!(serde_json::to_string(&record).unwrap(), "\"US\"");
+/// assert_tokens(
+///     &record.compact(),
+///     &[Token::NewtypeStruct { name: "Record" }, Token::U16(840)],
+/// );
+/// ```
+pub mod compact {
+    use core::{convert::TryFrom, str::FromStr};
+    use serde::{Deserializer, Serializer};
+
+    use super::Visitor;
+    use crate::{
+        country::{Country, Error},
+        InlineCode,
+    };
+
+    /// Serializes a country as its code or as its numeric code, depending on the format
+    pub fn serialize<T, S>(country: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Country,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(country.code())
+        } else {
+            serializer.serialize_u16(country.numeric())
+        }
+    }
+
+    /// Deserializes a country from its code or from its numeric code, depending on the format
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr<Err = Error> + TryFrom<u16, Error = Error>,
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Visitor {
+                expecting: "an ISO 3166-1 code",
+                parse: T::from_str,
+                numeric: None,
+            })
+        } else {
+            deserializer.deserialize_u16(Visitor {
+                expecting: "an ISO 3166-1 numeric code",
+                parse: |code| Err(Error::InvalidCountryCode(InlineCode::new(code.as_bytes()))),
+                numeric: Some(T::try_from),
+            })
+        }
+    }
+}
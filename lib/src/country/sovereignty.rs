@@ -0,0 +1,65 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Type definitions related to the independence of countries and the territories they administer
+
+use core::slice;
+use iso_macro::data_from_table;
+
+use super::Iso3166_1_alpha_2;
+
+data_from_table!(static SOVEREIGNS: "sovereignty.tab" => ["sovereign"]);
+
+pub(crate) fn independent_of(country: Iso3166_1_alpha_2) -> bool {
+    data_from_table!(match country: "sovereignty.tab" => "independent")
+}
+
+pub(crate) fn sovereign_of(country: Iso3166_1_alpha_2) -> Option<Iso3166_1_alpha_2> {
+    data_from_table!(match country: "sovereignty.tab" => "sovereign")
+}
+
+pub(crate) fn dependencies_of(sovereign: Iso3166_1_alpha_2) -> Dependencies {
+    Dependencies {
+        sovereign,
+        entries: SOVEREIGNS.iter(),
+    }
+}
+
+/// An iterator over the territories administered by a country
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{Iso3166_1_alpha_2, Country};
+/// assert!(!Iso3166_1_alpha_2::Gl.independent());
+/// assert_eq!(Iso3166_1_alpha_2::Gl.sovereign(), Some(Iso3166_1_alpha_2::Dk));
+/// assert_eq!(Iso3166_1_alpha_2::Hk.sovereign(), Some(Iso3166_1_alpha_2::Cn));
+/// assert_eq!(Iso3166_1_alpha_2::Us.sovereign(), None);
+/// assert_eq!(
+///     Iso3166_1_alpha_2::Dk.dependencies().collect::<Vec<_>>(),
+///     [Iso3166_1_alpha_2::Fo, Iso3166_1_alpha_2::Gl]
+/// );
+/// assert!(Iso3166_1_alpha_2::Us.dependencies().any(|c| c == Iso3166_1_alpha_2::Pr));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Dependencies {
+    sovereign: Iso3166_1_alpha_2,
+    entries: slice::Iter<'static, (Iso3166_1_alpha_2, Option<Iso3166_1_alpha_2>)>,
+}
+
+impl Iterator for Dependencies {
+    type Item = Iso3166_1_alpha_2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sovereign = self.sovereign;
+        self.entries
+            .find(|(_, s)| *s == Some(sovereign))
+            .map(|(country, _)| *country)
+    }
+}
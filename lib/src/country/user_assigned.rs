@@ -0,0 +1,390 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Type definitions for the user-assigned ISO 3166-1 code elements
+//!
+//! ISO 3166-1 sets aside `AA`, `QM` through `QZ`, `XA` through `XZ` and `ZZ` (as well as the
+//! alpha-3 codes beginning with `AA`, `QM` through `QZ`, `X` and `ZZ`) for use by anyone who needs
+//! to represent names not covered by the standard. These will never be assigned to a country, so
+//! they are represented separately from the generated enumerations, which can be combined with
+//! them through [`ExtendedAlpha2`] and [`ExtendedAlpha3`].
+//!
+//! Neither of these implement [`Country`], as a user-assigned code has no numeric code, calling
+//! codes, independence or anything else the trait must answer for every country. Instead, their
+//! accessors answer with an `Option` and [`ExtendedAlpha2::assigned`] gives the country behind an
+//! assigned code, through which the rest of the trait can be reached
+
+use core::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::{self, FromStr},
+};
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Country, Error, Iso3166_1_alpha_2, Iso3166_1_alpha_3};
+use crate::InlineCode;
+
+/// A user-assigned ISO 3166-1 alpha-2 code (`AA`, `QM` through `QZ`, `XA` through `XZ` or `ZZ`)
+#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct UserAssignedAlpha2([u8; 2]);
+
+/// A user-assigned ISO 3166-1 alpha-3 code (`AAA` through `AAZ`, `QMA` through `QZZ`, `XAA`
+/// through `XZZ` or `ZZA` through `ZZZ`)
+#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct UserAssignedAlpha3([u8; 3]);
+
+impl UserAssignedAlpha2 {
+    /// Returns the user-assigned code corresponding to the provided bytes, if they are within one
+    /// of the user-assigned ranges
+    pub fn from_bytes(bytes: [u8; 2]) -> Option<Self> {
+        match bytes {
+            [b'A', b'A'] | [b'Q', b'M'..=b'Z'] | [b'X', b'A'..=b'Z'] | [b'Z', b'Z'] => {
+                Some(Self(bytes))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the name given to this code by a widely agreed upon convention, if there is one
+    ///
+    /// ```
+    /// # use iso::country::UserAssignedAlpha2;
+    /// let stateless: UserAssignedAlpha2 = "XX".parse().unwrap();
+    /// let unassigned: UserAssignedAlpha2 = "QM".parse().unwrap();
+    ///
+    /// #[cfg(feature = "user-assigned-conventions")]
+    /// assert_eq!(stateless.name(), Some("Unknown state or stateless"));
+    /// assert_eq!(unassigned.name(), None);
+    /// ```
+    pub fn name(&self) -> Option<&'static str> {
+        #[cfg(feature = "user-assigned-conventions")]
+        {
+            Some(match &self.0 {
+                b"XK" => "Kosovo",
+                b"XX" => "Unknown state or stateless",
+                b"ZZ" => "Unknown or unspecified",
+                _ => return None,
+            })
+        }
+
+        #[cfg(not(feature = "user-assigned-conventions"))]
+        None
+    }
+}
+
+impl UserAssignedAlpha3 {
+    /// Returns the user-assigned code corresponding to the provided bytes, if they are within one
+    /// of the user-assigned ranges
+    pub fn from_bytes(bytes: [u8; 3]) -> Option<Self> {
+        match bytes {
+            [b'A', b'A', b'A'..=b'Z']
+            | [b'Q', b'M'..=b'Z', b'A'..=b'Z']
+            | [b'X', b'A'..=b'Z', b'A'..=b'Z']
+            | [b'Z', b'Z', b'A'..=b'Z'] => Some(Self(bytes)),
+            _ => None,
+        }
+    }
+
+    /// Returns the name given to this code by a widely agreed upon convention, if there is one
+    pub fn name(&self) -> Option<&'static str> {
+        #[cfg(feature = "user-assigned-conventions")]
+        {
+            Some(match &self.0 {
+                b"XKX" => "Kosovo",
+                b"XXA" => "Stateless person",
+                b"XXB" => "Refugee (1951 Convention)",
+                b"XXC" => "Refugee (other)",
+                b"XXX" => "Unspecified nationality",
+                b"ZZZ" => "Unknown or unspecified",
+                _ => return None,
+            })
+        }
+
+        #[cfg(not(feature = "user-assigned-conventions"))]
+        None
+    }
+}
+
+impl TryFrom<UserAssignedAlpha2> for UserAssignedAlpha3 {
+    type Error = Error;
+
+    fn try_from(
+        c: UserAssignedAlpha2,
+    ) -> Result<Self, <Self as TryFrom<UserAssignedAlpha2>>::Error> {
+        match &c.0 {
+            #[cfg(feature = "user-assigned-conventions")]
+            b"XK" => Ok(Self(*b"XKX")),
+            #[cfg(feature = "user-assigned-conventions")]
+            b"ZZ" => Ok(Self(*b"ZZZ")),
+            _ => Err(Error::NoCorrespondingCountryCode(InlineCode::new(&c.0))),
+        }
+    }
+}
+
+impl TryFrom<UserAssignedAlpha3> for UserAssignedAlpha2 {
+    type Error = Error;
+
+    fn try_from(
+        c: UserAssignedAlpha3,
+    ) -> Result<Self, <Self as TryFrom<UserAssignedAlpha3>>::Error> {
+        match &c.0 {
+            #[cfg(feature = "user-assigned-conventions")]
+            b"XKX" => Ok(Self(*b"XK")),
+            #[cfg(feature = "user-assigned-conventions")]
+            b"ZZZ" => Ok(Self(*b"ZZ")),
+            _ => Err(Error::NoCorrespondingCountryCode(InlineCode::new(&c.0))),
+        }
+    }
+}
+
+macro_rules! user_assigned_impl {
+    ($user_assigned:ident, $length:literal) => {
+        impl $user_assigned {
+            /// Returns the user-assigned code as a `&str`
+            pub fn code(&self) -> &str {
+                // the constructors only accept uppercase ascii letters
+                str::from_utf8(&self.0).unwrap_or_default()
+            }
+        }
+
+        impl fmt::Display for $user_assigned {
+            fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str(self.name().unwrap_or_else(|| self.code()))
+            }
+        }
+
+        impl FromStr for $user_assigned {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+                <[u8; $length]>::try_from(s.as_bytes())
+                    .ok()
+                    .and_then(Self::from_bytes)
+                    .ok_or_else(|| Error::InvalidCountryCode(InlineCode::new(s.as_bytes())))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl Serialize for $user_assigned {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(self.code())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> Deserialize<'de> for $user_assigned {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_str(CodeVisitor::new())
+            }
+        }
+    };
+}
+
+user_assigned_impl!(UserAssignedAlpha2, 2);
+user_assigned_impl!(UserAssignedAlpha3, 3);
+
+/// An ISO 3166-1 alpha-2 code which is either assigned to a country or is user-assigned
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{ExtendedAlpha2, Iso3166_1_alpha_2};
+/// let united_states: ExtendedAlpha2 = "US".parse().unwrap();
+/// let unknown: ExtendedAlpha2 = "ZZ".parse().unwrap();
+///
+/// assert_eq!(united_states, ExtendedAlpha2::Assigned(Iso3166_1_alpha_2::Us));
+/// assert_eq!(united_states.numeric(), Some(840));
+/// assert_eq!(united_states.assigned(), Some(Iso3166_1_alpha_2::Us));
+/// assert_eq!(unknown.code(), "ZZ");
+/// assert_eq!(unknown.numeric(), None);
+/// assert_eq!(unknown.assigned(), None);
+/// assert!("XL".parse::<ExtendedAlpha2>().is_ok());
+/// assert!("QA".parse::<ExtendedAlpha2>().is_ok());
+/// assert!("QB".parse::<ExtendedAlpha2>().is_err());
+/// ```
+#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
+pub enum ExtendedAlpha2 {
+    /// A code assigned to a country by ISO 3166-1
+    Assigned(Iso3166_1_alpha_2),
+
+    /// A code from one of the user-assigned ranges
+    UserAssigned(UserAssignedAlpha2),
+}
+
+/// An ISO 3166-1 alpha-3 code which is either assigned to a country or is user-assigned
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::country::{ExtendedAlpha3, Iso3166_1_alpha_3};
+/// let united_states: ExtendedAlpha3 = "USA".parse().unwrap();
+/// let stateless: ExtendedAlpha3 = "XXA".parse().unwrap();
+///
+/// assert_eq!(united_states, ExtendedAlpha3::Assigned(Iso3166_1_alpha_3::Usa));
+/// assert_eq!(stateless.code(), "XXA");
+/// assert!("USB".parse::<ExtendedAlpha3>().is_err());
+/// ```
+#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
+pub enum ExtendedAlpha3 {
+    /// A code assigned to a country by ISO 3166-1
+    Assigned(Iso3166_1_alpha_3),
+
+    /// A code from one of the user-assigned ranges
+    UserAssigned(UserAssignedAlpha3),
+}
+
+macro_rules! extended_impl {
+    ($extended:ident, $country:ident, $user_assigned:ident) => {
+        impl $extended {
+            /// Returns the name of the country or, for user-assigned codes, the name given to it by
+            /// a widely agreed upon convention if there is one
+            #[cfg(not(feature = "without-names"))]
+            pub fn name(&self) -> Option<&'static str> {
+                match &self {
+                    Self::Assigned(c) => Some(c.name()),
+                    Self::UserAssigned(c) => c.name(),
+                }
+            }
+
+            /// Returns the country the code is assigned to, if it is not user-assigned
+            pub fn assigned(&self) -> Option<$country> {
+                match &self {
+                    Self::Assigned(c) => Some(*c),
+                    Self::UserAssigned(_) => None,
+                }
+            }
+
+            /// Returns the country's ISO 3166-1 numeric code, which user-assigned codes do not have
+            pub fn numeric(&self) -> Option<u16> {
+                match &self {
+                    Self::Assigned(c) => Some(c.numeric()),
+                    Self::UserAssigned(_) => None,
+                }
+            }
+
+            /// Returns the code as a `&str`
+            pub fn code(&self) -> &str {
+                match &self {
+                    Self::Assigned(c) => c.code(),
+                    Self::UserAssigned(c) => c.code(),
+                }
+            }
+        }
+
+        impl From<$country> for $extended {
+            fn from(c: $country) -> Self {
+                Self::Assigned(c)
+            }
+        }
+
+        impl From<$user_assigned> for $extended {
+            fn from(c: $user_assigned) -> Self {
+                Self::UserAssigned(c)
+            }
+        }
+
+        impl fmt::Display for $extended {
+            fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match &self {
+                    Self::Assigned(c) => fmt::Display::fmt(c, formatter),
+                    Self::UserAssigned(c) => fmt::Display::fmt(c, formatter),
+                }
+            }
+        }
+
+        impl FromStr for $extended {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+                s.parse::<$country>()
+                    .map(Self::Assigned)
+                    .or_else(|_| s.parse::<$user_assigned>().map(Self::UserAssigned))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl Serialize for $extended {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(self.code())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> Deserialize<'de> for $extended {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_str(CodeVisitor::new())
+            }
+        }
+    };
+}
+
+extended_impl!(ExtendedAlpha2, Iso3166_1_alpha_2, UserAssignedAlpha2);
+extended_impl!(ExtendedAlpha3, Iso3166_1_alpha_3, UserAssignedAlpha3);
+
+macro_rules! extended_impl_try_from {
+    ($from:ident, $to:ident) => {
+        impl TryFrom<$from> for $to {
+            type Error = Error;
+
+            fn try_from(c: $from) -> Result<Self, <Self as TryFrom<$from>>::Error> {
+                Ok(match c {
+                    $from::Assigned(c) => Self::Assigned(c.into()),
+                    $from::UserAssigned(c) => Self::UserAssigned(c.try_into()?),
+                })
+            }
+        }
+    };
+}
+
+extended_impl_try_from!(ExtendedAlpha2, ExtendedAlpha3);
+extended_impl_try_from!(ExtendedAlpha3, ExtendedAlpha2);
+
+/// A visitor deserializing any of the above types from their code
+#[cfg(feature = "serde")]
+struct CodeVisitor<T>(core::marker::PhantomData<T>);
+
+#[cfg(feature = "serde")]
+impl<T> CodeVisitor<T> {
+    fn new() -> Self {
+        Self(core::marker::PhantomData)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> de::Visitor<'de> for CodeVisitor<T>
+where
+    T: FromStr<Err = Error>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an ISO 3166-1 code")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse().map_err(de::Error::custom)
+    }
+}
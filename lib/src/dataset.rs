@@ -0,0 +1,66 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Information about the datasets the crate was built from
+
+/// Information about one of the primary datasets the crate was built from, for tracing the data it
+/// holds back to where it came from
+///
+/// The source, licence and attribution are those recorded for the dataset bundled with the crate,
+/// and are `None` when the dataset was replaced at build time (as described by
+/// [`country::DATASET_VERSION`](crate::country::DATASET_VERSION) and
+/// [`language::DATASET_VERSION`](crate::language::DATASET_VERSION)). Each module gives the
+/// information for its dataset as a `DATASET` constant
+///
+/// # Basic usage
+///
+/// ```
+/// # #[cfg(feature = "country")]
+/// # {
+/// # use iso::country::DATASET;
+/// if DATASET.bundled {
+///     assert_eq!(DATASET.licence, Some("CC-BY-SA-4.0"));
+/// }
+/// println!(
+///     "{} ({} rows, sha-256 {}), from {}",
+///     DATASET.description,
+///     DATASET.rows,
+///     DATASET.checksum,
+///     DATASET.source.unwrap_or("an unknown source")
+/// );
+/// # }
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub struct DatasetInfo {
+    /// A description of the dataset, such as `ISO 3166-1 country codes`
+    pub description: &'static str,
+
+    /// Whether or not the dataset is the one bundled with the crate
+    pub bundled: bool,
+
+    /// Where the dataset was taken from, if it is known
+    pub source: Option<&'static str>,
+
+    /// The upstream release (or commit) the dataset was taken from, if it is known. For a
+    /// replacement, this is the version it was given at build time
+    pub version: Option<&'static str>,
+
+    /// The number of entries within the dataset, which includes any left out by a data subset
+    pub rows: usize,
+
+    /// The SHA-256 checksum of the dataset, as a lowercase hexadecimal string
+    pub checksum: &'static str,
+
+    /// The licence the dataset is distributed under, if it is known
+    pub licence: Option<&'static str>,
+
+    /// The attribution the licence of the dataset requires, if it is known
+    pub attribution: Option<&'static str>,
+}
@@ -0,0 +1,36 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! The folding of names into ASCII, as the `ascii-names` data subset folds them
+
+use iso_macro::fold_from_subset;
+
+/// Returns the ASCII replacement for a character, exactly as the `ascii-names` data subset folds
+/// it, or `None` if it has none
+///
+/// ASCII characters are kept as they are, letters bearing diacritics are replaced with their base
+/// letter (or letters) and combining diacritical marks are dropped. This allows a name to be
+/// compared against those the crate holds regardless of whether or not the data subset is enabled
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::fold_character;
+/// let folded = "Åland Straße"
+///     .chars()
+///     .map(|character| fold_character(character).unwrap_or("?"))
+///     .collect::<String>();
+///
+/// assert_eq!(folded, "Aland Strasse");
+/// assert_eq!(fold_character('\u{301}'), Some(""));
+/// assert_eq!(fold_character('日'), None);
+/// ```
+pub fn fold_character(character: char) -> Option<&'static str> {
+    fold_from_subset!(character)
+}
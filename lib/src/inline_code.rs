@@ -0,0 +1,123 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! A code held inline within an error, so that errors can be constructed without allocating
+
+use core::fmt;
+
+/// The number of bytes of a code kept by an [`InlineCode`], which is enough to hold any code the
+/// crate knows of (as well as any numeric code) in full
+const CAPACITY: usize = 8;
+
+/// A code (or input mistaken for one) held inline, with any bytes beyond the first eight dropped
+///
+/// The input may not be valid UTF-8 when it was parsed from bytes, so any bytes which are not are
+/// escaped when the code is displayed
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::InlineCode;
+/// let code = InlineCode::new(b"ZZ");
+/// assert_eq!(code.as_bytes(), b"ZZ");
+/// assert_eq!(code.as_str(), Some("ZZ"));
+/// assert!(!code.is_truncated());
+///
+/// let code = InlineCode::new(b"not a country code");
+/// assert_eq!(code.as_bytes(), b"not a co");
+/// assert!(code.is_truncated());
+///
+/// assert_eq!(InlineCode::from_numeric(999).as_str(), Some("999"));
+/// ```
+#[derive(Clone, Copy, Hash, Eq, PartialEq)]
+pub struct InlineCode {
+    bytes: [u8; CAPACITY],
+
+    // note: this is the length of the code as it was given, which may exceed the capacity
+    length: usize,
+}
+
+impl InlineCode {
+    /// Creates an inline code from a code given as bytes, keeping its first eight bytes
+    pub const fn new(code: &[u8]) -> Self {
+        let mut bytes = [0; CAPACITY];
+        let mut index = 0;
+        while index < code.len() && index < CAPACITY {
+            bytes[index] = code[index];
+            index += 1;
+        }
+        Self {
+            bytes,
+            length: code.len(),
+        }
+    }
+
+    /// Creates an inline code from a numeric code, written out in decimal
+    pub const fn from_numeric(mut code: u16) -> Self {
+        let mut bytes = [0; CAPACITY];
+        let mut length = 0;
+        loop {
+            bytes[length] = b'0' + (code % 10) as u8;
+            length += 1;
+            code /= 10;
+            if code == 0 {
+                break;
+            }
+        }
+
+        // the digits were written from least to most significant
+        let mut index = 0;
+        while index < length / 2 {
+            let digit = bytes[index];
+            bytes[index] = bytes[length - index - 1];
+            bytes[length - index - 1] = digit;
+            index += 1;
+        }
+        Self { bytes, length }
+    }
+
+    /// Returns the bytes of the code which were kept
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.length.min(CAPACITY)]
+    }
+
+    /// Returns the code as a `&str`, if it was kept in full and is valid UTF-8
+    pub fn as_str(&self) -> Option<&str> {
+        if self.is_truncated() {
+            return None;
+        }
+        core::str::from_utf8(self.as_bytes()).ok()
+    }
+
+    /// Returns whether or not any bytes of the code were dropped
+    pub fn is_truncated(&self) -> bool {
+        self.length > CAPACITY
+    }
+}
+
+impl fmt::Display for InlineCode {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.as_bytes().utf8_chunks() {
+            formatter.write_str(chunk.valid())?;
+            for byte in chunk.invalid() {
+                write!(formatter, "\\x{:02x}", byte)?;
+            }
+        }
+        if self.is_truncated() {
+            formatter.write_str("…")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for InlineCode {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "InlineCode(\"{}\")", self)
+    }
+}
@@ -23,12 +23,14 @@
 //! // the variant representing the english language
 //! let english = Iso639_1::En;
 //!
+//! # #[cfg(not(feature = "without-names"))]
 //! println!(
 //!     "The name of the language represented by the ISO 639-3 code of {} is {}!",
 //!     TryInto::<Iso639_3>::try_into(english).unwrap().code(),
 //!     english.name()
 //! );
 //!
+//! # #[cfg(not(feature = "without-names"))]
 //! assert_eq!(english.name(), "English");
 //! assert_eq!(english.code(), "en");
 //! assert_eq!(english.try_into(), Ok(Iso639_1::En));
@@ -36,43 +38,133 @@
 //! assert_eq!(english.try_into(), Ok(Iso639_2t::Eng));
 //! assert_eq!(english.try_into(), Ok(Iso639_3::Eng));
 //! ```
+//!
+//! # Constant contexts
+//!
+//! Codes may be checked at compile time with [`language!`](crate::language!), and most
+//! information about a language can also be had within a constant context
+//!
+//! ```
+//! # use iso::{language, language::{Iso639_1, Iso639_3}};
+//! const JAPANESE: Iso639_1 = language!("ja");
+//! # #[cfg(not(feature = "without-names"))]
+//! const NAME: &str = JAPANESE.name();
+//! const BASQUE: Option<Iso639_3> = Iso639_3::from_bytes(b"eus");
+//!
+//! # #[cfg(not(feature = "without-names"))]
+//! assert_eq!(NAME, "Japanese");
+//! assert_eq!(BASQUE, Some(Iso639_3::Eus));
+//! ```
+//!
+//! # Iterating over a code set
+//!
+//! Each code enumeration can be iterated over, in order of its codes
+//!
+//! ```
+//! # use iso::language::Iso639_1;
+//! assert_eq!(Iso639_1::iter().next(), Some(Iso639_1::Aa));
+//! assert_eq!(Iso639_1::iter().rev().next(), Some(Iso639_1::Zu));
+//! ```
 
 use core::{
     convert::TryFrom,
     fmt,
     str::{self, FromStr},
 };
-use iso_macro::language_identifiers_from_table;
+use iso_macro::{
+    dataset_from_environment, language_identifiers_from_table, literal_macro_from_table,
+};
+
+#[cfg(feature = "cldr")]
+use iso_macro::cldr_from_table;
+
+#[cfg(feature = "cldr")]
+use crate::cldr;
+
+use crate::{CodeEnumeration, DatasetInfo, InlineCode};
 
 #[cfg(feature = "std")]
 use std::error;
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use ::serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "without-names"))]
+mod native_name;
+#[cfg(feature = "serde")]
+pub mod serde;
+
+/// The version of the ISO 639 dataset the code enumerations were generated from, as given by
+/// [`DATASET`]
+///
+/// For the dataset bundled with the crate, this is the upstream release (or commit) recorded for it
+/// within `assets/sources.tab`, or `None` if none is recorded. The dataset may be replaced at build
+/// time by setting the `ISO_LANGUAGE_TABLE` environment variable to the path of another, in which
+/// case this is the value of `ISO_LANGUAGE_TABLE_VERSION` (or `None` if it is unset).
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::language::{DATASET, DATASET_VERSION};
+/// assert_eq!(DATASET_VERSION, DATASET.version);
+/// println!("built from version {}", DATASET_VERSION.unwrap_or("unknown"));
+/// ```
+pub const DATASET_VERSION: Option<&str> = DATASET.version;
+
+/// The SHA-256 checksum of the ISO 639 dataset the code enumerations were generated from, as a
+/// lowercase hexadecimal string given by [`DATASET`]
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::language::DATASET_CHECKSUM;
+/// assert_eq!(DATASET_CHECKSUM.len(), 64);
+/// ```
+pub const DATASET_CHECKSUM: &str = DATASET.checksum;
+
+/// Information about the ISO 639 dataset the code enumerations were generated from, including where
+/// it was taken from and the licence it is distributed under
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::language::{DATASET, DATASET_CHECKSUM};
+/// assert_eq!(DATASET.checksum, DATASET_CHECKSUM);
+/// assert!(DATASET.rows > 0);
+/// ```
+pub const DATASET: DatasetInfo = dataset_from_environment!(language);
 
 /// A list of all possible errors encountered while working with the language code enumerations
+///
+/// # Basic usage
+///
+/// ```
+/// # use iso::{language::{Error, Iso639_1}, InlineCode};
+/// # use std::convert::TryFrom;
+/// assert_eq!(Iso639_1::try_from(&b"en"[..]), Ok(Iso639_1::En));
+/// assert_eq!(
+///     Iso639_1::try_from(&b"zz"[..]),
+///     Err(Error::InvalidLanguageCode(InlineCode::new(b"zz")))
+/// );
+/// ```
 #[non_exhaustive]
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub enum Error {
     /// An error returned when the provided language code is invalid
-    InvalidLanguageCode(String),
+    InvalidLanguageCode(InlineCode),
 
     /// An error returned when there is no corresponding language code in the target code set
-    NoCorrespondingLanguageCode(&'static str),
+    NoCorrespondingLanguageCode(InlineCode),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self {
             Self::InvalidLanguageCode(c) => {
-                formatter.write_str("`")?;
-                formatter.write_str(c)?;
-                formatter.write_str("` is an invalid language code")
+                write!(formatter, "`{}` is an invalid language code", c)
             }
             Self::NoCorrespondingLanguageCode(c) => {
-                formatter.write_str("`")?;
-                formatter.write_str(c)?;
-                formatter.write_str("` has no corresponding language code")
+                write!(formatter, "`{}` has no corresponding language code", c)
             }
         }
     }
@@ -84,28 +176,167 @@ impl error::Error for Error {}
 /// An abstraction over a language code providing ways to extract information as well as convert
 pub trait Language {
     /// Returns the language's name
+    #[cfg(not(feature = "without-names"))]
     fn name(&self) -> &'static str;
 
+    /// Returns the language's name in French, if it has been given one by ISO 639-2
+    ///
+    /// ```
+    /// # use iso::language::{Iso639_1, Iso639_3, Language};
+    /// assert_eq!(Iso639_1::De.name_fr(), Some("allemand"));
+    /// assert_eq!(Iso639_1::Es.name_fr(), Some("espagnol"));
+    /// assert_eq!(Iso639_3::Cmn.name_fr(), None);
+    /// ```
+    #[cfg(not(feature = "without-names"))]
+    fn name_fr(&self) -> Option<&'static str>;
+
+    /// Returns the language's name within the provided locale (such as `ja` or `es-MX`), as given
+    /// by the Unicode CLDR
+    ///
+    /// The name is looked up within the locale's parent locales when the locale itself does not
+    /// give one, and only locales enabled through the `cldr-<locale>` features are searched
+    ///
+    /// ```
+    /// # use iso::language::{Iso639_1, Iso639_3, Language};
+    /// # #[cfg(not(feature = "ascii-names"))]
+    /// assert_eq!(Iso639_1::Ja.localized_name("ja"), Some("日本語"));
+    /// assert_eq!(Iso639_3::Eus.localized_name("es-MX"), Some("euskera"));
+    /// assert_eq!(Iso639_3::Eus.localized_name("fr"), Some("basque"));
+    /// assert_eq!(Iso639_1::Ja.localized_name("xx"), None);
+    /// ```
+    #[cfg(feature = "cldr")]
+    fn localized_name(&self, locale: &str) -> Option<&'static str>;
+
+    /// Returns the name the language is given by its own speakers, as given by the Unicode CLDR, if
+    /// it has a well-established one
+    ///
+    /// Languages written in several scripts may be given a different name in each, so an ISO 15924
+    /// script code (such as `Latn` or `Cyrl`) may be provided as a hint. The name in the script the
+    /// language is most commonly written in is returned when no hint is provided, or when the
+    /// language is not given a name in the script hinted at
+    ///
+    /// ```
+    /// # use iso::language::{Iso639_1, Iso639_3, Language};
+    /// assert_eq!(Iso639_1::De.native_name(None), Some("Deutsch"));
+    /// # #[cfg(not(feature = "ascii-names"))]
+    /// assert_eq!(Iso639_1::Ja.native_name(None), Some("日本語"));
+    /// # #[cfg(not(feature = "ascii-names"))]
+    /// assert_eq!(Iso639_1::Ru.native_name(None), Some("Русский"));
+    /// # #[cfg(not(feature = "ascii-names"))]
+    /// assert_eq!(Iso639_1::Sr.native_name(None), Some("Српски"));
+    /// assert_eq!(Iso639_1::Sr.native_name(Some("Latn")), Some("Srpski"));
+    /// assert_eq!(Iso639_1::De.native_name(Some("Cyrl")), Some("Deutsch"));
+    /// assert_eq!(Iso639_3::Ang.native_name(None), None);
+    /// ```
+    #[cfg(not(feature = "without-names"))]
+    fn native_name(&self, script: Option<&str>) -> Option<&'static str>;
+
     /// Returns the language's corresponding language code as a `&str` based on the underlying format
     fn code(&self) -> &'static str;
 }
 
+// note: the language table is indexed by the discriminant of an `Iso639_3`, and each of its rows
+// holds the language's iso 639-3, 639-2b, 639-2t and 639-1 codes (in that order) followed by its
+// name and french name. each code table is indexed by the discriminant of its code, and each of
+// its rows holds the code's variant, the code as a string and the language's iso 639-3 code
+#[cfg(not(feature = "without-names"))]
+language_identifiers_from_table!(static LANGUAGES: iso639_3 => [iso639_2b, iso639_2t, iso639_1, "name", "name_fr"]);
+#[cfg(feature = "without-names")]
+language_identifiers_from_table!(static LANGUAGES: iso639_3 => [iso639_2b, iso639_2t, iso639_1]);
+language_identifiers_from_table!(static ISO639_1: iso639_1 => ["iso639_1", iso639_3]);
+language_identifiers_from_table!(static ISO639_2B: iso639_2b => ["iso639_2b", iso639_3]);
+language_identifiers_from_table!(static ISO639_2T: iso639_2t => ["iso639_2t", iso639_3]);
+language_identifiers_from_table!(static ISO639_3: iso639_3 => ["iso639_3", iso639_3]);
+
 //TODO(superwhiskers): consider making this into a derive macro when Copy and Clone can be used within a constant context
 macro_rules! language_impl {
-    ($language:ident, $language_as_string:literal) =>  {
+    ($language:ident, $codes:ident, $length:literal) =>  {
+        impl $language {
+            /// Returns the index of the language's row within the language table
+            const fn table_index(self) -> usize {
+                $codes[self as usize].2 as usize
+            }
+
+            /// Returns the language's name, like [`Language::name`] but within a constant context
+            #[cfg(not(feature = "without-names"))]
+            pub const fn name(self) -> &'static str {
+                table_name!(LANGUAGES_NAMES, LANGUAGES[self.table_index()].4)
+            }
+
+            /// Returns the language's name in French, like [`Language::name_fr`] but within a
+            /// constant context
+            #[cfg(not(feature = "without-names"))]
+            pub const fn name_fr(self) -> Option<&'static str> {
+                table_name!(optional LANGUAGES_NAMES, LANGUAGES[self.table_index()].5)
+            }
+
+            /// Returns the language's corresponding language code, like [`Language::code`] but
+            /// within a constant context
+            pub const fn code(self) -> &'static str {
+                $codes[self as usize].1
+            }
+
+            /// Parses a language code given as bytes, like [`FromStr::from_str`] but within a
+            /// constant context
+            pub const fn from_bytes(code: &[u8]) -> Option<Self> {
+                match find_code!($codes, code) {
+                    Some(index) => Some($codes[index].0),
+                    None => None,
+                }
+            }
+
+            /// Returns an iterator over every code within the code set, in order of their codes
+            pub fn iter() -> impl ExactSizeIterator<Item = Self> + DoubleEndedIterator + Clone {
+                $codes.iter().map(|row| row.0)
+            }
+        }
+
         impl Language for $language {
+            #[cfg(not(feature = "without-names"))]
             fn name(&self) -> &'static str {
-                language_identifiers_from_table!(match &self: $language => "name")
+                $language::name(*self)
+            }
+
+            #[cfg(not(feature = "without-names"))]
+            fn name_fr(&self) -> Option<&'static str> {
+                $language::name_fr(*self)
+            }
+
+            #[cfg(feature = "cldr")]
+            fn localized_name(&self, locale: &str) -> Option<&'static str> {
+                cldr::localized(locale, |locale| {
+                    cldr_from_table!(match locale, self: $language)
+                })
+            }
+
+            #[cfg(not(feature = "without-names"))]
+            fn native_name(&self, script: Option<&str>) -> Option<&'static str> {
+                native_name::native_name_of(LANGUAGES[self.table_index()].0, script)
             }
 
             fn code(&self) -> &'static str {
-                language_identifiers_from_table!(match &self: $language => $language_as_string)
+                $language::code(*self)
+            }
+        }
+
+        impl CodeEnumeration for $language {
+            fn is_well_formed(code: &[u8]) -> bool {
+                code.len() == $length && code.iter().all(u8::is_ascii_lowercase)
+            }
+
+            fn to_code(self) -> &'static str {
+                self.code()
             }
         }
 
         impl fmt::Display for $language {
             fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                formatter.write_str(self.name())
+                // the code stands in for the name when names are left out
+                #[cfg(feature = "without-names")]
+                let name = self.code();
+                #[cfg(not(feature = "without-names"))]
+                let name = self.name();
+                formatter.write_str(name)
             }
         }
 
@@ -113,44 +344,86 @@ macro_rules! language_impl {
             type Err = Error;
 
             fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
-                language_identifiers_from_table!(match s: $language_as_string => $language).ok_or(Error::InvalidLanguageCode(s.to_string()))
+                Self::try_from(s.as_bytes())
+            }
+        }
+
+        impl TryFrom<&[u8]> for $language {
+            type Error = Error;
+
+            fn try_from(c: &[u8]) -> Result<Self, <Self as TryFrom<&[u8]>>::Error> {
+                Self::from_bytes(c).ok_or_else(|| Error::InvalidLanguageCode(InlineCode::new(c)))
             }
         }
     }
 }
 
+// note: the column is the index of the target code within a row of the language table
 macro_rules! language_impl_try_from {
-    ($from:ident, $to:ident) => {
+    ($from:ident, $to:ident, 0) => {
         impl TryFrom<$from> for $to {
             type Error = Error;
 
             fn try_from(c: $from) -> Result<Self, <Self as TryFrom<$from>>::Error> {
-                language_identifiers_from_table!(match c: $from => $to).ok_or(Error::NoCorrespondingLanguageCode(c.code()))
+                Ok(LANGUAGES[c.table_index()].0)
             }
         }
-    }
+    };
+    ($from:ident, $to:ident, $column:tt) => {
+        impl TryFrom<$from> for $to {
+            type Error = Error;
+
+            fn try_from(c: $from) -> Result<Self, <Self as TryFrom<$from>>::Error> {
+                LANGUAGES[c.table_index()].$column.ok_or_else(|| {
+                    Error::NoCorrespondingLanguageCode(InlineCode::new(c.code().as_bytes()))
+                })
+            }
+        }
+    };
 }
 
 language_identifiers_from_table!(enum Iso639_1: iso639_1);
-language_impl!(Iso639_1, "Iso639_1");
-language_impl_try_from!(Iso639_2b, Iso639_1);
-language_impl_try_from!(Iso639_2t, Iso639_1);
-language_impl_try_from!(Iso639_3, Iso639_1);
+language_impl!(Iso639_1, ISO639_1, 2);
+language_impl_try_from!(Iso639_2b, Iso639_1, 3);
+language_impl_try_from!(Iso639_2t, Iso639_1, 3);
+language_impl_try_from!(Iso639_3, Iso639_1, 3);
 
 language_identifiers_from_table!(enum Iso639_2b: iso639_2b);
-language_impl!(Iso639_2b, "Iso639_2b");
-language_impl_try_from!(Iso639_1, Iso639_2b);
-language_impl_try_from!(Iso639_2t, Iso639_2b);
-language_impl_try_from!(Iso639_3, Iso639_2b);
+language_impl!(Iso639_2b, ISO639_2B, 3);
+language_impl_try_from!(Iso639_1, Iso639_2b, 1);
+language_impl_try_from!(Iso639_2t, Iso639_2b, 1);
+language_impl_try_from!(Iso639_3, Iso639_2b, 1);
 
 language_identifiers_from_table!(enum Iso639_2t: iso639_2t);
-language_impl!(Iso639_2t, "Iso639_2t");
-language_impl_try_from!(Iso639_1, Iso639_2t);
-language_impl_try_from!(Iso639_2b, Iso639_2t);
-language_impl_try_from!(Iso639_3, Iso639_2t);
+language_impl!(Iso639_2t, ISO639_2T, 3);
+language_impl_try_from!(Iso639_1, Iso639_2t, 2);
+language_impl_try_from!(Iso639_2b, Iso639_2t, 2);
+language_impl_try_from!(Iso639_3, Iso639_2t, 2);
 
 language_identifiers_from_table!(enum Iso639_3: iso639_3);
-language_impl!(Iso639_3, "Iso639_3");
-language_impl_try_from!(Iso639_1, Iso639_3);
-language_impl_try_from!(Iso639_2b, Iso639_3);
-language_impl_try_from!(Iso639_2t, Iso639_3);
+language_impl!(Iso639_3, ISO639_3, 3);
+language_impl_try_from!(Iso639_1, Iso639_3, 0);
+language_impl_try_from!(Iso639_2b, Iso639_3, 0);
+language_impl_try_from!(Iso639_2t, Iso639_3, 0);
+
+literal_macro_from_table! {
+    /// Turns an ISO 639-1 or 639-3 code, given as a string literal, into the variant of
+    /// [`Iso639_1`](crate::language::Iso639_1) or [`Iso639_3`](crate::language::Iso639_3) it
+    /// corresponds to
+    ///
+    /// The code is checked at compile time, and an invalid one is reported as an error. As the
+    /// macro expands to the variant itself, it may be used within a constant context
+    ///
+    /// ```
+    /// # use iso::{language, language::{Iso639_1, Iso639_3}};
+    /// const ENGLISH: Iso639_1 = language!("en");
+    /// assert_eq!(ENGLISH, Iso639_1::En);
+    /// assert_eq!(language!("cmn"), Iso639_3::Cmn);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let language = iso::language!("zz");
+    /// ```
+    #[macro_export]
+    language: iso639_1, iso639_3
+}
@@ -0,0 +1,22 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Type definitions related to the names languages are given by their own speakers
+
+use iso_macro::data_from_table;
+
+use super::Iso639_3;
+
+// note: the script columns of the table are named after the iso 15924 code of their script, so the
+// script is looked up as the name of a column directly
+pub(crate) fn native_name_of(language: Iso639_3, script: Option<&str>) -> Option<&'static str> {
+    script
+        .and_then(|script| data_from_table!(match language in script: "native_name.tab"))
+        .or(data_from_table!(match language: "native_name.tab" => "native-name"))
+}
@@ -0,0 +1,615 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Alternative representations of the language code enumerations, for use with `#[serde(with)]`
+//!
+//! The code enumerations are (de)serialized as their own code by default, so an [`Iso639_3`] is
+//! always written as `"eng"`. Each module within this one (de)serializes a field holding any of the
+//! code enumerations in some other way instead:
+//!
+//! - [`iso639_1`], [`iso639_2b`], [`iso639_2t`] and [`iso639_3`]: as its code within that code set,
+//!   whichever the field holds (which fails for a language without a code there)
+//! - [`object`]: as an object holding its code and name, like `{"code": "en", "name": "English"}`
+//! - [`lenient`]: as its code, while accepting any of its codes in any case when deserializing
+//! - [`compact`]: as its code within human-readable formats (such as JSON), and as its code packed
+//!   into a `u16` within any other format (such as bincode)
+//!
+//! # Basic usage
+//!
+//! ```
+//! # use iso::language::{self, Iso639_1, Iso639_3};
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Document {
+//!     #[serde(with = "language::serde::iso639_1")]
+//!     language: Iso639_3,
+//!     #[serde(with = "language::serde::lenient")]
+//!     translation: Iso639_1,
+//! }
+//!
+//! let document: Document =
+//!     serde_json::from_str(r#"{"language": "de", "translation": "FRE"}"#).unwrap();
+//! assert_eq!(
+//!     document,
+//!     Document {
+//!         language: Iso639_3::Deu,
+//!         translation: Iso639_1::Fr,
+//!     }
+//! );
+//! assert_eq!(
+//!     serde_json::to_string(&document).unwrap(),
+//!     r#"{"language":"de","translation":"fr"}"#
+//! );
+//! ```
+
+use core::{convert::TryFrom, fmt, str};
+use serde::de;
+
+use super::{Error, Iso639_1, Iso639_2b, Iso639_3};
+use crate::InlineCode;
+
+/// Parses a code within any of the code sets, in any case and with any surrounding whitespace
+fn parse_any(code: &str) -> Result<Iso639_3, Error> {
+    let code = code.trim();
+    let mut lowercase = [0; 3];
+    let length = code.len();
+    if length == 2 || length == 3 {
+        lowercase[..length].copy_from_slice(code.as_bytes());
+        lowercase.make_ascii_lowercase();
+        let lowercase = &lowercase[..length];
+
+        // note: every ISO 639-2/T code is also an ISO 639-3 code
+        if let Some(language) = Iso639_3::from_bytes(lowercase) {
+            return Ok(language);
+        }
+        if let Some(language) = Iso639_1::from_bytes(lowercase) {
+            return Iso639_3::try_from(language);
+        }
+        if let Some(language) = Iso639_2b::from_bytes(lowercase) {
+            return Iso639_3::try_from(language);
+        }
+    }
+    Err(Error::InvalidLanguageCode(InlineCode::new(code.as_bytes())))
+}
+
+/// Packs a code of two or three lowercase letters into a `u16`, as five bits per letter
+fn pack(code: &str) -> u16 {
+    code.bytes()
+        .fold(0, |packed, c| (packed << 5) | u16::from(c - b'a' + 1))
+}
+
+/// Unpacks a code packed by [`pack`], returning it alongside its length
+fn unpack(mut packed: u16) -> Option<([u8; 3], usize)> {
+    let mut code = [0; 3];
+    let mut length = 0;
+    while packed != 0 {
+        let letter = (packed & 0b11111) as u8;
+        if length == 3 || !(1..=26).contains(&letter) {
+            return None;
+        }
+        code[length] = b'a' + letter - 1;
+        length += 1;
+        packed >>= 5;
+    }
+    code[..length].reverse();
+    Some((code, length))
+}
+
+/// A visitor deserializing a language from a string, parsed by the provided function, or (if
+/// enabled) from a code packed into an integer
+struct Visitor<T> {
+    expecting: &'static str,
+    parse: fn(&str) -> Result<T, Error>,
+    packed: bool,
+}
+
+impl<'de, T> de::Visitor<'de> for Visitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        (self.parse)(v).map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match str::from_utf8(v) {
+            Ok(v) => self.visit_str(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match u16::try_from(v)
+            .ok()
+            .filter(|_| self.packed)
+            .and_then(unpack)
+        {
+            Some((code, length)) => self.visit_bytes(&code[..length]),
+            None => Err(de::Error::invalid_value(de::Unexpected::Unsigned(v), &self)),
+        }
+    }
+}
+
+macro_rules! code_module {
+    ($(#[$attribute:meta])* $module:ident, $code:ident, $expecting:literal) => {
+        $(#[$attribute])*
+        pub mod $module {
+            use core::{convert::TryFrom, fmt};
+            use serde::{ser, Deserializer, Serializer};
+
+            use super::Visitor;
+            use crate::language::$code;
+
+            /// Serializes a language as its code within the code set, failing if it has none there
+            pub fn serialize<T, S>(language: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: Copy,
+                $code: TryFrom<T>,
+                <$code as TryFrom<T>>::Error: fmt::Display,
+                S: Serializer,
+            {
+                let language = $code::try_from(*language).map_err(ser::Error::custom)?;
+                serializer.serialize_str(language.code())
+            }
+
+            /// Deserializes a language from its code within the code set
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: TryFrom<$code>,
+                T::Error: fmt::Display,
+                D: Deserializer<'de>,
+            {
+                let language = deserializer.deserialize_str(Visitor {
+                    expecting: $expecting,
+                    parse: str::parse::<$code>,
+                    packed: false,
+                })?;
+                T::try_from(language).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+code_module!(
+    /// (De)serializes a language as its ISO 639-1 code, whichever code enumeration holds it
+    ///
+    /// ```
+    /// # use iso::language::{self, Iso639_3};
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    /// struct Record(#[serde(with = "language::serde::iso639_1")] Iso639_3);
+    ///
+    /// assert_eq!(serde_json::to_string(&Record(Iso639_3::Eng)).unwrap(), "\"en\"");
+    /// assert_eq!(serde_json::from_str::<Record>("\"en\"").unwrap(), Record(Iso639_3::Eng));
+    /// assert!(serde_json::to_string(&Record(Iso639_3::Gsw)).is_err());
+    /// ```
+    iso639_1,
+    Iso639_1,
+    "an ISO 639-1 code"
+);
+
+code_module!(
+    /// (De)serializes a language as its ISO 639-2/B code, whichever code enumeration holds it
+    ///
+    /// ```
+    /// # use iso::language::{self, Iso639_1};
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    /// struct Record(#[serde(with = "language::serde::iso639_2b")] Iso639_1);
+    ///
+    /// assert_eq!(serde_json::to_string(&Record(Iso639_1::De)).unwrap(), "\"ger\"");
+    /// assert_eq!(serde_json::from_str::<Record>("\"ger\"").unwrap(), Record(Iso639_1::De));
+    /// ```
+    iso639_2b,
+    Iso639_2b,
+    "an ISO 639-2/B code"
+);
+
+code_module!(
+    /// (De)serializes a language as its ISO 639-2/T code, whichever code enumeration holds it
+    ///
+    /// ```
+    /// # use iso::language::{self, Iso639_1};
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    /// struct Record(#[serde(with = "language::serde::iso639_2t")] Iso639_1);
+    ///
+    /// assert_eq!(serde_json::to_string(&Record(Iso639_1::De)).unwrap(), "\"deu\"");
+    /// ```
+    iso639_2t,
+    Iso639_2t,
+    "an ISO 639-2/T code"
+);
+
+code_module!(
+    /// (De)serializes a language as its ISO 639-3 code, whichever code enumeration holds it
+    ///
+    /// ```
+    /// # use iso::language::{self, Iso639_1};
+    /// # use serde::{Deserialize, Serialize};
+    /// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    /// struct Record(#[serde(with = "language::serde::iso639_3")] Iso639_1);
+    ///
+    /// assert_eq!(serde_json::to_string(&Record(Iso639_1::En)).unwrap(), "\"eng\"");
+    /// assert!(serde_json::from_str::<Record>("\"gsw\"").is_err());
+    /// ```
+    iso639_3,
+    Iso639_3,
+    "an ISO 639-3 code"
+);
+
+/// (De)serializes a language as an object holding its code and its name
+///
+/// Only the code is read when deserializing, so the name may be left out (or be in any language),
+/// and any other fields of the object are ignored
+///
+/// ```
+/// # use iso::language::{self, Iso639_1};
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Record(#[serde(with = "language::serde::object")] Iso639_1);
+///
+/// assert_eq!(
+///     serde_json::to_string(&Record(Iso639_1::En)).unwrap(),
+///     r#"{"code":"en","name":"English"}"#
+/// );
+/// assert_eq!(
+///     serde_json::from_str::<Record>(r#"{"name": "anglais", "code": "en"}"#).unwrap(),
+///     Record(Iso639_1::En)
+/// );
+///
+/// // the code may be read from an owned string (as it is from a value), and from within formats
+/// // which are not self-describing
+/// let value = serde_json::json!({"code": "en", "name": "English"});
+/// assert_eq!(
+///     serde_json::from_value::<Record>(value).unwrap(),
+///     Record(Iso639_1::En)
+/// );
+/// let bytes = bincode::serialize(&Record(Iso639_1::En)).unwrap();
+/// assert_eq!(bincode::deserialize::<Record>(&bytes).unwrap(), Record(Iso639_1::En));
+/// ```
+#[cfg(not(feature = "without-names"))]
+pub mod object {
+    use core::{
+        fmt,
+        marker::PhantomData,
+        str::{self, FromStr},
+    };
+    use serde::{
+        de::{self, IgnoredAny, MapAccess, SeqAccess},
+        ser::SerializeStruct,
+        Deserialize, Deserializer, Serializer,
+    };
+
+    use crate::language::{Error, Language};
+
+    /// Serializes a language as an object holding its code and name
+    pub fn serialize<T, S>(language: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Language,
+        S: Serializer,
+    {
+        let mut object = serializer.serialize_struct("Language", 2)?;
+        object.serialize_field("code", language.code())?;
+        object.serialize_field("name", language.name())?;
+        object.end()
+    }
+
+    /// A field of the object, of which only the code is read
+    enum Field {
+        Code,
+        Other,
+    }
+
+    /// A visitor deserializing a field of the object from its name (or its index)
+    struct FieldVisitor;
+
+    impl<'de> de::Visitor<'de> for FieldVisitor {
+        type Value = Field;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a field name")
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(if v == 0 { Field::Code } else { Field::Other })
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_bytes(v.as_bytes())
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(if v == b"code" {
+                Field::Code
+            } else {
+                Field::Other
+            })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    /// A visitor (and seed) deserializing the code field of the object, parsing it as it is read
+    struct Code<T>(PhantomData<T>);
+
+    impl<'de, T> de::Visitor<'de> for Code<T>
+    where
+        T: FromStr<Err = Error>,
+    {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a code")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            v.parse().map_err(de::Error::custom)
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match str::from_utf8(v) {
+                Ok(v) => self.visit_str(v),
+                Err(_) => Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &self)),
+            }
+        }
+    }
+
+    impl<'de, T> de::DeserializeSeed<'de> for Code<T>
+    where
+        T: FromStr<Err = Error>,
+    {
+        type Value = T;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_str(self)
+        }
+    }
+
+    /// A visitor deserializing the name field of the object, which is read and ignored (as formats
+    /// which are not self-describing cannot skip over a value of an unknown type)
+    struct Name;
+
+    impl<'de> de::Visitor<'de> for Name {
+        type Value = Name;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a name")
+        }
+
+        fn visit_str<E>(self, _: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(Name)
+        }
+
+        fn visit_bytes<E>(self, _: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(Name)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Name {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_str(Name)
+        }
+    }
+
+    /// A visitor deserializing a language from an object holding its code
+    struct Visitor<T>(PhantomData<T>);
+
+    impl<'de, T> de::Visitor<'de> for Visitor<T>
+    where
+        T: FromStr<Err = Error>,
+    {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an object holding an ISO 639 code")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut language = None;
+            while let Some(key) = map.next_key::<Field>()? {
+                match key {
+                    Field::Other => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                    Field::Code if language.is_some() => {
+                        return Err(de::Error::duplicate_field("code"))
+                    }
+                    Field::Code => language = Some(map.next_value_seed(Code(PhantomData))?),
+                }
+            }
+            language.ok_or_else(|| de::Error::missing_field("code"))
+        }
+
+        // note: formats which are not self-describing hold structs as a sequence of their fields,
+        // which must each be read in turn
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let language = seq
+                .next_element_seed(Code(PhantomData))?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            seq.next_element::<Name>()?;
+            Ok(language)
+        }
+    }
+
+    /// Deserializes a language from an object holding its code
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr<Err = Error>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("Language", &["code", "name"], Visitor(PhantomData))
+    }
+}
+
+/// (De)serializes a language as its code, accepting any of its codes when deserializing
+///
+/// Codes within any of the code sets are accepted in any case, and any surrounding whitespace is
+/// ignored. Languages are always serialized as the code the field holds, as they are by default,
+/// and deserializing fails for a language without a code within it
+///
+/// ```
+/// # use iso::language::{self, Iso639_1};
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Record(#[serde(with = "language::serde::lenient")] Iso639_1);
+///
+/// for input in &[r#""de""#, r#""DE""#, r#"" deu ""#, r#""ger""#] {
+///     assert_eq!(serde_json::from_str::<Record>(input).unwrap(), Record(Iso639_1::De));
+/// }
+/// assert_eq!(serde_json::to_string(&Record(Iso639_1::De)).unwrap(), "\"de\"");
+/// assert!(serde_json::from_str::<Record>("\"gsw\"").is_err());
+/// ```
+pub mod lenient {
+    use core::{convert::TryFrom, fmt};
+    use serde::{de, Deserializer, Serializer};
+
+    use super::{parse_any, Visitor};
+    use crate::language::{Iso639_3, Language};
+
+    /// Serializes a language as its code
+    pub fn serialize<T, S>(language: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Language,
+        S: Serializer,
+    {
+        serializer.serialize_str(language.code())
+    }
+
+    /// Deserializes a language from any of its codes
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: TryFrom<Iso639_3>,
+        T::Error: fmt::Display,
+        D: Deserializer<'de>,
+    {
+        let language = deserializer.deserialize_str(Visitor {
+            expecting: "an ISO 639 code",
+            parse: parse_any,
+            packed: false,
+        })?;
+        T::try_from(language).map_err(de::Error::custom)
+    }
+}
+
+/// (De)serializes a language as its code within human-readable formats, and as its code packed into
+/// a `u16` within any other format
+///
+/// Whether or not a format is human-readable is left to the format itself to say (through
+/// [`Serializer::is_human_readable`](serde::Serializer::is_human_readable)). Codes are packed as
+/// five bits per letter (with `a` as `1`), so the packed form of a code does not change as
+/// languages are added to or removed from the dataset
+///
+/// ```
+/// # use iso::language::{self, Iso639_3};
+/// # use serde::{Deserialize, Serialize};
+/// # use serde_test::{assert_tokens, Configure, Token};
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Record(#[serde(with = "language::serde::compact")] Iso639_3);
+///
+/// let record = Record(Iso639_3::Eng);
+/// assert_eq!(serde_json::to_string(&record).unwrap(), "\"eng\"");
+/// assert_tokens(
+///     &record.compact(),
+///     &[Token::NewtypeStruct { name: "Record" }, Token::U16(5 << 10 | 14 << 5 | 7)],
+/// );
+/// ```
+pub mod compact {
+    use core::str::FromStr;
+    use serde::{Deserializer, Serializer};
+
+    use super::{pack, Visitor};
+    use crate::language::{Error, Language};
+
+    /// Serializes a language as its code or as its packed code, depending on the format
+    pub fn serialize<T, S>(language: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Language,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(language.code())
+        } else {
+            serializer.serialize_u16(pack(language.code()))
+        }
+    }
+
+    /// Deserializes a language from its code or from its packed code, depending on the format
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr<Err = Error>,
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Visitor {
+                expecting: "an ISO 639 code",
+                parse: T::from_str,
+                packed: false,
+            })
+        } else {
+            deserializer.deserialize_u16(Visitor {
+                expecting: "an ISO 639 code packed into an integer",
+                parse: T::from_str,
+                packed: true,
+            })
+        }
+    }
+}
@@ -7,6 +7,53 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
 
+//! Implementations of datatypes related to common ISO standards
+//!
+//! # Features
+//!
+//! - `std` (enabled by default): implements `std::error::Error` for the error types
+//! - `country` (enabled by default): the [`country`](mod@country) module, covering ISO 3166-1
+//! - `language` (enabled by default): the [`language`](mod@language) module, covering ISO 639
+//! - `user-assigned-conventions`: names for the user-assigned country codes given a meaning by a
+//!   widely agreed upon convention
+//! - `serde`: implementations of `Serialize` and `Deserialize` for the code enumerations, which
+//!   (de)serialize them as their code, alongside modules for use with `#[serde(with)]` which
+//!   (de)serialize them in other ways (`country::serde` and `language::serde`)
+//! - `registry`: the [`registry`](mod@registry) module, holding a registry of countries and
+//!   languages which can load newer datasets at runtime (enables `std`, `country` and `language`)
+//! - `cldr-<locale>`: names within the locale, as given by the Unicode CLDR
+//! - `packed-names`: the names of each table are packed into a single string rather than each
+//!   compiled in as a string of its own, and each name is held as a four byte offset and length
+//!   rather than as a pointer and a length. Nearly all of the saving comes from the tables no
+//!   longer holding pointers (each of which also needs a relocation within a position-independent
+//!   binary), as the names themselves only shrink by those found within or overlapping with
+//!   another (by about 6%). The tables move from `.data.rel.ro` into `.rodata`, which grows as a
+//!   result: for a release build of `iso-cli` on `x86_64` Linux, the binary shrinks from 1977328 to
+//!   1555880 bytes, as `.data.rel.ro` shrinks from 577232 to 246400 bytes and `.rela.dyn` from
+//!   478992 to 254952, while `.rodata` grows from 205872 to 333384. Names are still returned as a
+//!   `&'static str`, at the cost of a bounds check
+//!
+//! Without `std`, the crate is `no_std` and needs no allocator.
+//!
+//! ## Data subsets
+//!
+//! The following features leave parts of the datasets out, for targets where binary size matters.
+//! Unlike the features above, they take parts of the API away rather than add to it, so they are
+//! best left for the final binary to enable.
+//!
+//! - `iso639-1-languages`: only languages with an ISO 639-1 code are compiled in
+//! - `living-languages`: only living languages are compiled in
+//! - `without-names`: no names are compiled in, which removes every method returning a name other
+//!   than those of the CLDR (the code enumerations are displayed as their code instead)
+//! - `ascii-names`: every name is folded into ASCII, with letters bearing diacritics replaced by
+//!   their base letter. Names which must be present have any other characters replaced with `?`,
+//!   while names which are optional (such as native names in other scripts) are left out
+//!
+//! When both language subsets are enabled, the languages within either one are compiled in. Every
+//! language code enumeration, conversion and macro is restricted to the languages compiled in, so
+//! (for example) every `Iso639_3` can be converted into an `Iso639_1` when only languages with an
+//! ISO 639-1 code are, though the conversion is still fallible in its signature.
+
 #![allow(clippy::cognitive_complexity)]
 #![warn(clippy::cargo_common_metadata)]
 #![warn(clippy::dbg_macro)]
@@ -20,7 +67,7 @@
 #![deny(clippy::cast_lossless)]
 #![deny(clippy::clone_on_ref_ptr)]
 #![deny(clippy::doc_markdown)]
-#![deny(clippy::empty_enum)]
+#![deny(clippy::empty_enums)]
 #![deny(clippy::enum_glob_use)]
 #![deny(clippy::exit)]
 #![deny(clippy::explicit_into_iter_loop)]
@@ -36,8 +83,122 @@
 #![deny(clippy::mut_mut)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+/// Finds the index of a code (as bytes) within a table sorted by its codes, which are held by the
+/// second cell of each row and are each made up of the same number of ASCII letters
+///
+/// Codes are compared as integers (with each byte of the code as a digit), which orders them
+/// exactly as they would be ordered as strings but avoids comparing them a byte at a time. This is
+/// a macro rather than a function as closures cannot be called within a constant context
+#[cfg(any(feature = "country", feature = "language"))]
+macro_rules! find_code {
+    ($table:expr, $code:expr) => {{
+        let code: &[u8] = $code;
+        let mut found = None;
+        if code.len() <= 4 && !$table.is_empty() && code.len() == $table[0].1.len() {
+            let packed = crate::pack_code(code);
+            let (mut low, mut high) = (0, $table.len());
+            while low < high {
+                let middle = low + (high - low) / 2;
+                let candidate = crate::pack_code($table[middle].1.as_bytes());
+                if candidate < packed {
+                    low = middle + 1;
+                } else if candidate > packed {
+                    high = middle;
+                } else {
+                    found = Some(middle);
+                    break;
+                }
+            }
+        }
+        found
+    }};
+}
+
+/// Returns a name held by a `static` table generated by the macros, which is unpacked from the
+/// string generated alongside the table (named after it, with a `_NAMES` suffix) when names are
+/// packed. Names which may be absent are unpacked with `optional`
+#[cfg(all(
+    any(feature = "country", feature = "language"),
+    not(feature = "without-names"),
+    feature = "packed-names"
+))]
+macro_rules! table_name {
+    ($names:ident, $name:expr) => {
+        crate::packed::unpack($names, $name)
+    };
+    (optional $names:ident, $name:expr) => {
+        crate::packed::unpack_optional($names, $name)
+    };
+}
+
+#[cfg(all(
+    any(feature = "country", feature = "language"),
+    not(feature = "without-names"),
+    not(feature = "packed-names")
+))]
+macro_rules! table_name {
+    ($names:ident, $name:expr) => {
+        $name
+    };
+    (optional $names:ident, $name:expr) => {
+        $name
+    };
+}
+
+#[cfg(feature = "cldr")]
+mod cldr;
+
 #[cfg(feature = "country")]
 pub mod country;
 
 #[cfg(feature = "language")]
 pub mod language;
+
+#[cfg(feature = "registry")]
+pub mod registry;
+
+#[cfg(any(feature = "country", feature = "language"))]
+mod dataset;
+
+mod fold;
+
+#[cfg(any(feature = "country", feature = "language"))]
+mod inline_code;
+
+#[cfg(any(feature = "country", feature = "language"))]
+mod lossless;
+
+// note: the cldr names are packed as well, so these are needed even when no other names are
+#[cfg(all(
+    any(feature = "country", feature = "language"),
+    any(not(feature = "without-names"), feature = "cldr"),
+    feature = "packed-names"
+))]
+mod packed;
+
+#[cfg(any(feature = "country", feature = "language"))]
+pub use dataset::DatasetInfo;
+
+pub use fold::fold_character;
+
+#[cfg(any(feature = "country", feature = "language"))]
+pub use inline_code::InlineCode;
+
+#[cfg(any(feature = "country", feature = "language"))]
+pub use lossless::{CodeEnumeration, Lossless, UnknownCode};
+
+#[cfg(any(feature = "country", feature = "language"))]
+#[doc(hidden)]
+pub use iso_macro::invalid_code_literal as __invalid_code_literal;
+
+/// Packs a code of at most four bytes into an integer, for use by `find_code!`
+#[cfg(any(feature = "country", feature = "language"))]
+const fn pack_code(code: &[u8]) -> u32 {
+    let mut packed = 0;
+    let mut index = 0;
+    while index < code.len() {
+        packed = (packed << 8) | code[index] as u32;
+        index += 1;
+    }
+    packed
+}
@@ -0,0 +1,228 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! A wrapper over the code enumerations which keeps codes they do not know of
+//!
+//! The code enumerations only know of the codes within the dataset they were generated from, so a
+//! binary built against an older dataset rejects any code added to the standard since. Wrapping a
+//! code enumeration within a [`Lossless`] keeps such codes instead, as long as they are well-formed
+//! (of the same length and case as every other code within the code set), and writes them back out
+//! exactly as they were read.
+
+use core::{
+    fmt,
+    str::{self, FromStr},
+};
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::InlineCode;
+
+/// A code enumeration, whose codes are all of a single form
+///
+/// This is implemented by each code enumeration, and is what allows them to be wrapped within a
+/// [`Lossless`]
+///
+/// ```
+/// # #[cfg(feature = "country")]
+/// # {
+/// # use iso::{country::Iso3166_1_alpha_3, CodeEnumeration};
+/// assert!(Iso3166_1_alpha_3::is_well_formed(b"ZZZ"));
+/// assert!(!Iso3166_1_alpha_3::is_well_formed(b"zzz"));
+/// assert_eq!(Iso3166_1_alpha_3::Deu.to_code(), "DEU");
+/// # }
+/// ```
+pub trait CodeEnumeration: Copy + FromStr {
+    /// Returns whether or not a code is of the form every code within the code set is, whether or
+    /// not it is known
+    fn is_well_formed(code: &[u8]) -> bool;
+
+    /// Returns the code, as a `&str`
+    fn to_code(self) -> &'static str;
+}
+
+/// A code within a code set, which is kept even when it is not known, as long as it is well-formed
+///
+/// Known codes are held as the code enumeration they are within, while unknown ones are held as an
+/// [`UnknownCode`]. Either is displayed (and serialized) as its code, so any code parsed (or
+/// deserialized) into a `Lossless` is written back out unchanged. An `UnknownCode` is only ever
+/// made by parsing, so `Unknown` never holds a code which is known or is not well-formed, and two
+/// `Lossless` are only equal when they hold the same code.
+///
+/// # Basic usage
+///
+/// ```
+/// # #[cfg(feature = "country")]
+/// # {
+/// # use iso::{country::Iso3166_1_alpha_2, Lossless};
+/// let known: Lossless<Iso3166_1_alpha_2> = "DE".parse().unwrap();
+/// assert_eq!(known, Lossless::Known(Iso3166_1_alpha_2::De));
+///
+/// let unknown: Lossless<Iso3166_1_alpha_2> = "QZ".parse().unwrap();
+/// assert!(matches!(unknown, Lossless::Unknown(code) if code.as_str() == "QZ"));
+/// assert_eq!(unknown.to_string(), "QZ");
+/// assert_eq!(unknown.known(), None);
+///
+/// // codes which could never be within the code set are still rejected
+/// assert!("qz".parse::<Lossless<Iso3166_1_alpha_2>>().is_err());
+/// assert!("QZZ".parse::<Lossless<Iso3166_1_alpha_2>>().is_err());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum Lossless<T> {
+    /// A code known to the code enumeration
+    Known(T),
+
+    /// A well-formed code not known to the code enumeration
+    Unknown(UnknownCode),
+}
+
+/// A well-formed code not known to the code enumeration it was parsed as, held as the bytes it was
+/// made up of
+///
+/// This can only be made by parsing a [`Lossless`], which ensures that the code is not known
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub struct UnknownCode(InlineCode);
+
+impl UnknownCode {
+    /// Returns the code, as a `&str`
+    pub fn as_str(&self) -> &str {
+        // note: well-formed codes are made up of ASCII letters, and are never truncated
+        self.0.as_str().unwrap_or_default()
+    }
+}
+
+impl<T> Lossless<T>
+where
+    T: CodeEnumeration,
+{
+    /// Returns the code, as a `&str`
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Known(known) => known.to_code(),
+            Self::Unknown(unknown) => unknown.as_str(),
+        }
+    }
+
+    /// Returns the code enumeration's variant, if the code is known
+    pub fn known(self) -> Option<T> {
+        match self {
+            Self::Known(known) => Some(known),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// Returns whether or not the code is known
+    pub fn is_known(&self) -> bool {
+        matches!(self, Self::Known(_))
+    }
+}
+
+impl<T> From<T> for Lossless<T> {
+    fn from(known: T) -> Self {
+        Self::Known(known)
+    }
+}
+
+impl<T> fmt::Display for Lossless<T>
+where
+    T: CodeEnumeration,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.code())
+    }
+}
+
+impl<T> FromStr for Lossless<T>
+where
+    T: CodeEnumeration,
+{
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+        match s.parse() {
+            Ok(known) => Ok(Self::Known(known)),
+            Err(_) if T::is_well_formed(s.as_bytes()) => {
+                Ok(Self::Unknown(UnknownCode(InlineCode::new(s.as_bytes()))))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Serialize for Lossless<T>
+where
+    T: CodeEnumeration,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+/// A visitor deserializing a [`Lossless`] from its code
+#[cfg(feature = "serde")]
+struct Visitor<T>(core::marker::PhantomData<T>);
+
+#[cfg(feature = "serde")]
+impl<'de, T> de::Visitor<'de> for Visitor<T>
+where
+    T: CodeEnumeration,
+    T::Err: fmt::Display,
+{
+    type Value = Lossless<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a well-formed code")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse().map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match str::from_utf8(v) {
+            Ok(v) => self.visit_str(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+}
+
+/// Deserializes a code, keeping it if it is well-formed but unknown
+///
+/// ```
+/// # use iso::{language::Iso639_3, Lossless};
+/// let languages: Vec<Lossless<Iso639_3>> = serde_json::from_str(r#"["eng", "qqq"]"#).unwrap();
+/// assert_eq!(languages[0], Lossless::Known(Iso639_3::Eng));
+/// assert!(!languages[1].is_known());
+/// assert_eq!(serde_json::to_string(&languages).unwrap(), r#"["eng","qqq"]"#);
+/// ```
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for Lossless<T>
+where
+    T: CodeEnumeration,
+    T::Err: fmt::Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Visitor(core::marker::PhantomData))
+    }
+}
@@ -0,0 +1,36 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Unpacking of the names packed into a single string per table by the `packed-names` feature
+//!
+//! Each packed name is a `u32` holding its offset within the string in its upper 22 bits and its
+//! length in its lower 10, while `u32::MAX` stands in for an absent name. These must be kept in
+//! step with the `packed` module of `iso-macro`, which packs them.
+
+/// The number of bits of a packed name holding its length
+const LENGTH_BITS: u32 = 10;
+
+/// The packed name standing in for an absent name
+const ABSENT: u32 = u32::MAX;
+
+/// Returns a name packed into a string of names
+pub(crate) const fn unpack(names: &'static str, name: u32) -> &'static str {
+    let offset = (name >> LENGTH_BITS) as usize;
+    let length = (name & ((1 << LENGTH_BITS) - 1)) as usize;
+    names.split_at(offset).1.split_at(length).0
+}
+
+/// Returns an optional name packed into a string of names
+pub(crate) const fn unpack_optional(names: &'static str, name: u32) -> Option<&'static str> {
+    if name == ABSENT {
+        None
+    } else {
+        Some(unpack(names, name))
+    }
+}
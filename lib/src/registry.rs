@@ -0,0 +1,892 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! A registry of countries and languages which can be extended at runtime
+//!
+//! The code enumerations only know of the codes within the datasets they were generated from, so
+//! recognizing codes added to either standard since means rebuilding. A [`Registry`] instead loads
+//! datasets at runtime (in the same formats the crate is built from: the `country.json` and
+//! `language.tab` files found within `lib/assets`), and answers lookups from them before falling
+//! back to the code enumerations. Every entry it returns says which of the two it came from.
+//!
+//! Each entry answers the same questions the [`Country`] and [`Language`] traits do, though the
+//! datasets only hold the codes and names of each entry. Its codes (within every code set, as well
+//! as conversions between them) and its name are known of every entry, while the answers to every
+//! other question (such as its borders or calling codes) come from the code enumerations. These
+//! return an `Option` rather than what the traits return, which is `None` for entries that only
+//! exist at runtime (those which [`RegisteredCountry::known`] or [`RegisteredLanguage::known`]
+//! returns `None` for).
+//!
+//! # Basic usage
+//!
+//! ```
+//! # use iso::{language::Iso639_3, registry::{Registry, Source}};
+//! let mut registry = Registry::new();
+//! registry
+//!     .add_language_table(
+//!         "update.tab",
+//!         "Id\tPart2b\tPart2t\tPart1\tScope\tLanguage_Type\tRef_Name\tComment\n\
+//!          qqv\t\t\t\tI\tL\tA Newly Coded Language\t\n",
+//!     )
+//!     .unwrap();
+//!
+//! let new = registry.language("qqv").unwrap();
+//! assert_eq!(new.name(), "A Newly Coded Language");
+//! assert_eq!(new.source(), Source::Loaded("update.tab"));
+//! assert_eq!(new.known(), None);
+//!
+//! // codes not within any loaded dataset are looked up within the code enumerations
+//! let english = registry.language("en").unwrap();
+//! assert_eq!(english.iso639_3(), "eng");
+//! assert_eq!(english.source(), Source::CompiledIn);
+//! assert_eq!(english.known(), Some(Iso639_3::Eng));
+//! ```
+
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    convert::{TryFrom, TryInto},
+    error, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    country::{CallingCodes, Country, Dependencies, Iso3166_1_alpha_2, Iso3166_1_alpha_3},
+    language::{Iso639_1, Iso639_2b, Iso639_2t, Iso639_3},
+    CodeEnumeration,
+};
+
+#[cfg(any(not(feature = "without-names"), feature = "cldr"))]
+use crate::language::Language;
+
+/// The columns the language table must begin with, compared ignoring case and anything other than
+/// letters and digits
+const LANGUAGE_TABLE_HEADER: [&str; 7] = [
+    "id",
+    "part2b",
+    "part2t",
+    "part1",
+    "scope",
+    "languagetype",
+    "refname",
+];
+
+/// A list of all possible errors encountered while loading a dataset into a [`Registry`]
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    /// An error returned when a dataset could not be read
+    Io(PathBuf, io::Error),
+
+    /// An error returned when the country codes are not valid JSON
+    Json(String, serde_json::Error),
+
+    /// An error returned when a dataset does not match the expected schema, holding the name of
+    /// the dataset and every problem found within it (each prefixed with where it was found)
+    Invalid(String, Vec<String>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(
+                formatter,
+                "unable to read {}: {}",
+                path.as_os_str().to_string_lossy(),
+                e
+            ),
+            Self::Json(name, e) => write!(formatter, "{}: {}", name, e),
+            Self::Invalid(name, problems) => {
+                for (index, problem) in problems.iter().enumerate() {
+                    if index > 0 {
+                        formatter.write_str("\n")?;
+                    }
+                    write!(formatter, "{}:{}", name, problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(_, e) => Some(e),
+            Self::Json(_, e) => Some(e),
+            Self::Invalid(..) => None,
+        }
+    }
+}
+
+/// Where an entry of a [`Registry`] came from
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum Source<'a> {
+    /// The code enumerations compiled into the crate
+    CompiledIn,
+
+    /// A dataset loaded at runtime, by its name (the path it was loaded from, for a file)
+    Loaded(&'a str),
+}
+
+/// A country loaded at runtime
+#[derive(Clone, Debug)]
+struct LoadedCountry {
+    name: String,
+    alpha_2: String,
+    alpha_3: String,
+    numeric: u16,
+    source: usize,
+}
+
+/// A language loaded at runtime
+#[derive(Clone, Debug)]
+struct LoadedLanguage {
+    iso639_3: String,
+    iso639_2b: Option<String>,
+    iso639_2t: Option<String>,
+    iso639_1: Option<String>,
+    name: String,
+    source: usize,
+}
+
+/// A registry of countries and languages, holding those loaded at runtime and falling back to the
+/// code enumerations for any others
+///
+/// Datasets loaded later take precedence over those loaded earlier, and every dataset loaded takes
+/// precedence over the code enumerations. An entry replaces any loaded earlier which shares one of
+/// its codes (or, for languages, its ISO 639-3 code) entirely, so none of the codes of the replaced
+/// entry are looked up any longer unless the entry replacing it gives them as well
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    sources: Vec<String>,
+    countries: Vec<LoadedCountry>,
+    alpha_2: HashMap<String, usize>,
+    alpha_3: HashMap<String, usize>,
+    numeric: HashMap<u16, usize>,
+    languages: Vec<LoadedLanguage>,
+    iso639_3: HashMap<String, usize>,
+    iso639_2b: HashMap<String, usize>,
+    iso639_2t: HashMap<String, usize>,
+    iso639_1: HashMap<String, usize>,
+}
+
+impl Registry {
+    /// Creates a registry holding no datasets, which looks every code up within the code
+    /// enumerations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the name of every dataset loaded, in the order they were loaded
+    pub fn sources(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.sources.iter().map(String::as_str)
+    }
+
+    /// Reads a file to be loaded
+    fn read(path: &Path) -> Result<(String, String), Error> {
+        let contents = fs::read_to_string(path).map_err(|e| Error::Io(path.to_path_buf(), e))?;
+        Ok((path.as_os_str().to_string_lossy().into_owned(), contents))
+    }
+
+    /// Loads ISO 3166-1 country codes from a file in the format of `country.json`, returning the
+    /// number of countries loaded
+    pub fn load_country_codes(&mut self, path: impl AsRef<Path>) -> Result<usize, Error> {
+        let (name, contents) = Self::read(path.as_ref())?;
+        self.add_country_codes(&name, &contents)
+    }
+
+    /// Loads ISO 639 language codes from a file in the format of `language.tab` (the code tables
+    /// published by the ISO 639-3 registration authority), returning the number of languages loaded
+    pub fn load_language_table(&mut self, path: impl AsRef<Path>) -> Result<usize, Error> {
+        let (name, contents) = Self::read(path.as_ref())?;
+        self.add_language_table(&name, &contents)
+    }
+
+    /// Loads ISO 3166-1 country codes in the format of `country.json`, under a name to report as
+    /// their source, returning the number of countries loaded
+    ///
+    /// Nothing is loaded if any problem is found within the dataset
+    ///
+    /// ```
+    /// # use iso::{country::Iso3166_1_alpha_2, registry::{Registry, Source}};
+    /// let mut registry = Registry::new();
+    /// let countries = r#"[
+    ///     {"name": "Germany (Updated)", "alpha-2": "DE", "alpha-3": "DEU", "country-code": "276"},
+    ///     {"name": "Newland", "alpha-2": "QN", "alpha-3": "QNL", "country-code": "901"}
+    /// ]"#;
+    /// assert_eq!(registry.add_country_codes("countries", countries).unwrap(), 2);
+    ///
+    /// let germany = registry.country("DEU").unwrap();
+    /// assert_eq!(germany.name(), "Germany (Updated)");
+    /// assert_eq!(germany.source(), Source::Loaded("countries"));
+    /// assert_eq!(germany.known(), Some(Iso3166_1_alpha_2::De));
+    /// assert_eq!(registry.country_by_numeric(901).unwrap().alpha_2(), "QN");
+    ///
+    /// assert!(registry.add_country_codes("broken", r#"[{"name": "Nowhere"}]"#).is_err());
+    /// ```
+    pub fn add_country_codes(&mut self, name: &str, contents: &str) -> Result<usize, Error> {
+        let entries = serde_json::from_str::<Vec<Value>>(contents)
+            .map_err(|e| Error::Json(name.to_string(), e))?;
+
+        let source = self.sources.len();
+        let mut problems = Vec::new();
+        let mut countries = Vec::new();
+        let mut seen = HashMap::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let field = |field: &str| entry.get(field).and_then(Value::as_str).unwrap_or_default();
+            let mut problem = |problem: String| {
+                problems.push(format!("entry {}: {}", index + 1, problem));
+            };
+
+            let (country_name, alpha_2, alpha_3) =
+                (field("name"), field("alpha-2"), field("alpha-3"));
+            if country_name.trim().is_empty() {
+                problem("the country has no name".to_string());
+            }
+            if !Iso3166_1_alpha_2::is_well_formed(alpha_2.as_bytes()) {
+                problem(format!(
+                    "`{}` is not an alpha-2 code, which must be two uppercase letters",
+                    alpha_2
+                ));
+            }
+            if !Iso3166_1_alpha_3::is_well_formed(alpha_3.as_bytes()) {
+                problem(format!(
+                    "`{}` is not an alpha-3 code, which must be three uppercase letters",
+                    alpha_3
+                ));
+            }
+            let numeric = field("country-code");
+            let numeric = if numeric.len() == 3 && numeric.bytes().all(|c| c.is_ascii_digit()) {
+                numeric.parse::<u16>().unwrap_or_default()
+            } else {
+                problem(format!(
+                    "`{}` is not a numeric code, which must be three digits",
+                    numeric
+                ));
+                continue;
+            };
+            for code in &[alpha_2, alpha_3, field("country-code")] {
+                if let Some(previous) = seen.insert(code.to_string(), index) {
+                    problem(format!(
+                        "the code `{}` was already given by entry {}",
+                        code,
+                        previous + 1
+                    ));
+                }
+            }
+
+            countries.push(LoadedCountry {
+                name: country_name.to_string(),
+                alpha_2: alpha_2.to_string(),
+                alpha_3: alpha_3.to_string(),
+                numeric,
+                source,
+            });
+        }
+        if !problems.is_empty() {
+            return Err(Error::Invalid(name.to_string(), problems));
+        }
+
+        let count = countries.len();
+        self.sources.push(name.to_string());
+        for country in countries {
+            let index = self.countries.len();
+            let replaced = [
+                self.alpha_2.get(&country.alpha_2),
+                self.alpha_3.get(&country.alpha_3),
+                self.numeric.get(&country.numeric),
+            ];
+            for replaced in replaced
+                .iter()
+                .flatten()
+                .map(|index| **index)
+                .collect::<Vec<usize>>()
+            {
+                self.unindex_country(replaced);
+            }
+            self.alpha_2.insert(country.alpha_2.clone(), index);
+            self.alpha_3.insert(country.alpha_3.clone(), index);
+            self.numeric.insert(country.numeric, index);
+            self.countries.push(country);
+        }
+        Ok(count)
+    }
+
+    /// Loads ISO 639 language codes in the format of `language.tab`, under a name to report as
+    /// their source, returning the number of languages loaded
+    ///
+    /// Nothing is loaded if any problem is found within the dataset
+    ///
+    /// ```
+    /// # use iso::registry::Registry;
+    /// let mut registry = Registry::new();
+    /// let table = "Id\tPart2b\tPart2t\tPart1\tScope\tLanguage_Type\tRef_Name\tComment\n\
+    ///              QQV\t\t\t\tI\tL\tA Newly Coded Language\t\n";
+    ///
+    /// let error = registry.add_language_table("update.tab", table).unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "update.tab:2:1: `QQV` is not an iso 639-3 code, which must be 3 lowercase letters"
+    /// );
+    /// ```
+    pub fn add_language_table(&mut self, name: &str, contents: &str) -> Result<usize, Error> {
+        let mut lines = contents.lines();
+        let header = lines
+            .next()
+            .unwrap_or_default()
+            .split('\t')
+            .map(|column| {
+                column
+                    .chars()
+                    .filter(char::is_ascii_alphanumeric)
+                    .collect::<String>()
+                    .to_lowercase()
+            })
+            .collect::<Vec<String>>();
+        if header.len() < LANGUAGE_TABLE_HEADER.len()
+            || header
+                .iter()
+                .zip(&LANGUAGE_TABLE_HEADER)
+                .any(|(a, b)| a != b)
+        {
+            return Err(Error::Invalid(
+                name.to_string(),
+                vec![format!(
+                    "1:1: the header does not match the expected schema, which begins with the \
+                     columns {}",
+                    LANGUAGE_TABLE_HEADER.join(", ")
+                )],
+            ));
+        }
+
+        let source = self.sources.len();
+        let mut problems = Vec::new();
+        let mut languages = Vec::new();
+        let mut seen = HashMap::new();
+        for (number, line) in lines.enumerate() {
+            let number = number + 2;
+            if line.is_empty() {
+                continue;
+            }
+            let cells = line.split('\t').collect::<Vec<&str>>();
+            if cells.len() < LANGUAGE_TABLE_HEADER.len() {
+                problems.push(format!(
+                    "{}:{}: expected at least 7 columns, found {}",
+                    number,
+                    line.chars().count() + 1,
+                    cells.len()
+                ));
+                continue;
+            }
+
+            let mut valid = true;
+            let codes = [
+                ("639-3", Iso639_3::is_well_formed as fn(&[u8]) -> bool, 3),
+                ("639-2b", Iso639_2b::is_well_formed, 3),
+                ("639-2t", Iso639_2t::is_well_formed, 3),
+                ("639-1", Iso639_1::is_well_formed, 2),
+            ];
+            for (index, (set, is_well_formed, length)) in codes.iter().enumerate() {
+                let code = cells[index];
+                let column = cells[..index].iter().map(|cell| cell.chars().count() + 1);
+                let column = column.sum::<usize>() + 1;
+                if index != 0 && code.is_empty() {
+                    continue;
+                }
+                if !is_well_formed(code.as_bytes()) {
+                    valid = false;
+                    problems.push(format!(
+                        "{}:{}: `{}` is not an iso {} code, which must be {} lowercase letters",
+                        number, column, code, set, length
+                    ));
+                } else if let Some(previous) = seen.insert((index, code), number) {
+                    problems.push(format!(
+                        "{}:{}: the {} code `{}` was already given on line {}",
+                        number, column, set, code, previous
+                    ));
+                }
+            }
+            if cells[6].trim().is_empty() {
+                valid = false;
+                let column = cells[..6].iter().map(|cell| cell.chars().count() + 1);
+                problems.push(format!(
+                    "{}:{}: the language has no name",
+                    number,
+                    column.sum::<usize>() + 1
+                ));
+            }
+
+            if valid {
+                let optional = |cell: &str| Some(cell.to_string()).filter(|cell| !cell.is_empty());
+                languages.push(LoadedLanguage {
+                    iso639_3: cells[0].to_string(),
+                    iso639_2b: optional(cells[1]),
+                    iso639_2t: optional(cells[2]),
+                    iso639_1: optional(cells[3]),
+                    name: cells[6].to_string(),
+                    source,
+                });
+            }
+        }
+        if !problems.is_empty() {
+            return Err(Error::Invalid(name.to_string(), problems));
+        }
+
+        let count = languages.len();
+        self.sources.push(name.to_string());
+        for language in languages {
+            let index = self.languages.len();
+            if let Some(replaced) = self.iso639_3.get(&language.iso639_3) {
+                self.unindex_language(*replaced);
+            }
+            self.iso639_3.insert(language.iso639_3.clone(), index);
+            for (codes, code) in [
+                (&mut self.iso639_2b, &language.iso639_2b),
+                (&mut self.iso639_2t, &language.iso639_2t),
+                (&mut self.iso639_1, &language.iso639_1),
+            ] {
+                if let Some(code) = code {
+                    codes.insert(code.clone(), index);
+                }
+            }
+            self.languages.push(language);
+        }
+        Ok(count)
+    }
+
+    /// Removes every code of a country loaded at runtime from the indices, as a country loaded
+    /// later shares one of its codes
+    fn unindex_country(&mut self, index: usize) {
+        let country = &self.countries[index];
+        for (codes, code) in [
+            (&mut self.alpha_2, &country.alpha_2),
+            (&mut self.alpha_3, &country.alpha_3),
+        ] {
+            if codes.get(code) == Some(&index) {
+                codes.remove(code);
+            }
+        }
+        if self.numeric.get(&country.numeric) == Some(&index) {
+            self.numeric.remove(&country.numeric);
+        }
+    }
+
+    /// Removes every code of a language loaded at runtime from the indices, as a language loaded
+    /// later shares its ISO 639-3 code
+    fn unindex_language(&mut self, index: usize) {
+        let language = &self.languages[index];
+        for (codes, code) in [
+            (&mut self.iso639_3, Some(&language.iso639_3)),
+            (&mut self.iso639_2b, language.iso639_2b.as_ref()),
+            (&mut self.iso639_2t, language.iso639_2t.as_ref()),
+            (&mut self.iso639_1, language.iso639_1.as_ref()),
+        ] {
+            if let Some(code) = code {
+                if codes.get(code) == Some(&index) {
+                    codes.remove(code);
+                }
+            }
+        }
+    }
+
+    /// Returns the country a code stands for, given as an ISO 3166-1 alpha-2, alpha-3 or numeric
+    /// code (in the same case as the code enumerations take)
+    ///
+    /// A code which is only known to the code enumerations is not looked up within them if the
+    /// country it stands for has been loaded at runtime, as the dataset it was loaded from no
+    /// longer gives the country that code
+    pub fn country(&self, code: &str) -> Option<RegisteredCountry<'_>> {
+        let loaded = self
+            .alpha_2
+            .get(code)
+            .or_else(|| self.alpha_3.get(code))
+            .or_else(|| {
+                Some(code)
+                    .filter(|code| code.len() == 3)
+                    .and_then(|code| code.parse::<u16>().ok())
+                    .and_then(|numeric| self.numeric.get(&numeric))
+            });
+        if let Some(index) = loaded {
+            return Some(self.loaded_country(*index));
+        }
+
+        let compiled = Iso3166_1_alpha_2::from_bytes(code.as_bytes())
+            .or_else(|| Iso3166_1_alpha_3::from_bytes(code.as_bytes()).map(Into::into))
+            .or_else(|| {
+                Some(code)
+                    .filter(|code| code.len() == 3)
+                    .and_then(|code| code.parse::<u16>().ok())
+                    .and_then(|numeric| Iso3166_1_alpha_2::try_from(numeric).ok())
+            })?;
+        self.compiled_country(compiled)
+    }
+
+    /// Returns the country an ISO 3166-1 numeric code stands for
+    pub fn country_by_numeric(&self, numeric: u16) -> Option<RegisteredCountry<'_>> {
+        match self.numeric.get(&numeric) {
+            Some(index) => Some(self.loaded_country(*index)),
+            None => Iso3166_1_alpha_2::try_from(numeric)
+                .ok()
+                .and_then(|country| self.compiled_country(country)),
+        }
+    }
+
+    /// Returns the language a code stands for, given as an ISO 639-3, 639-2/T, 639-2/B or 639-1
+    /// code (in the same case as the code enumerations take)
+    ///
+    /// A code which is only known to the code enumerations is not looked up within them if the
+    /// language it stands for has been loaded at runtime, as the dataset it was loaded from no
+    /// longer gives the language that code
+    pub fn language(&self, code: &str) -> Option<RegisteredLanguage<'_>> {
+        let loaded = self
+            .iso639_3
+            .get(code)
+            .or_else(|| self.iso639_2t.get(code))
+            .or_else(|| self.iso639_2b.get(code))
+            .or_else(|| self.iso639_1.get(code));
+        if let Some(index) = loaded {
+            return Some(self.loaded_language(*index));
+        }
+
+        let compiled = Iso639_3::from_bytes(code.as_bytes())
+            .or_else(|| Iso639_2t::from_bytes(code.as_bytes()).and_then(|l| l.try_into().ok()))
+            .or_else(|| Iso639_2b::from_bytes(code.as_bytes()).and_then(|l| l.try_into().ok()))
+            .or_else(|| Iso639_1::from_bytes(code.as_bytes()).and_then(|l| l.try_into().ok()))?;
+        self.compiled_language(compiled)
+    }
+
+    /// Returns every country, with those loaded at runtime (in the order they were loaded) before
+    /// those only the code enumerations know of
+    ///
+    /// ```
+    /// # use iso::registry::{Registry, Source};
+    /// let mut registry = Registry::new();
+    /// let codes = r#"[
+    ///     {"name": "Newland", "alpha-2": "QN", "alpha-3": "QNL", "country-code": "901"}
+    /// ]"#;
+    /// registry.add_country_codes("new.json", codes).unwrap();
+    ///
+    /// let loaded = registry
+    ///     .countries()
+    ///     .filter(|country| country.source() == Source::Loaded("new.json"))
+    ///     .count();
+    /// assert_eq!(loaded, 1);
+    /// assert_eq!(registry.countries().count(), iso::country::Iso3166_1_alpha_2::iter().len() + 1);
+    /// ```
+    pub fn countries(&self) -> impl Iterator<Item = RegisteredCountry<'_>> {
+        let loaded = (0..self.countries.len())
+            .filter(move |index| self.alpha_2.get(&self.countries[*index].alpha_2) == Some(index))
+            .map(move |index| self.loaded_country(index));
+        let compiled =
+            Iso3166_1_alpha_2::iter().filter_map(move |country| self.compiled_country(country));
+        loaded.chain(compiled)
+    }
+
+    /// Returns every language, with those loaded at runtime (in the order they were loaded) before
+    /// those only the code enumerations know of
+    pub fn languages(&self) -> impl Iterator<Item = RegisteredLanguage<'_>> {
+        let loaded = (0..self.languages.len())
+            .filter(move |index| self.iso639_3.get(&self.languages[*index].iso639_3) == Some(index))
+            .map(move |index| self.loaded_language(index));
+        let compiled =
+            Iso639_3::iter().filter_map(move |language| self.compiled_language(language));
+        loaded.chain(compiled)
+    }
+
+    fn loaded_country(&self, index: usize) -> RegisteredCountry<'_> {
+        let country = &self.countries[index];
+        RegisteredCountry(CountryEntry::Loaded(country, &self.sources[country.source]))
+    }
+
+    /// Returns a country known to the code enumerations, unless it was loaded at runtime as well
+    fn compiled_country(&self, country: Iso3166_1_alpha_2) -> Option<RegisteredCountry<'_>> {
+        Some(RegisteredCountry(CountryEntry::CompiledIn(country)))
+            .filter(|_| !self.alpha_2.contains_key(country.code()))
+    }
+
+    fn loaded_language(&self, index: usize) -> RegisteredLanguage<'_> {
+        let language = &self.languages[index];
+        RegisteredLanguage(LanguageEntry::Loaded(
+            language,
+            &self.sources[language.source],
+        ))
+    }
+
+    /// Returns a language known to the code enumerations, unless it was loaded at runtime as well
+    fn compiled_language(&self, language: Iso639_3) -> Option<RegisteredLanguage<'_>> {
+        Some(RegisteredLanguage(LanguageEntry::CompiledIn(language)))
+            .filter(|_| !self.iso639_3.contains_key(language.code()))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum CountryEntry<'a> {
+    Loaded(&'a LoadedCountry, &'a str),
+    CompiledIn(Iso3166_1_alpha_2),
+}
+
+/// A country within a [`Registry`], which was either loaded at runtime or is known to the code
+/// enumerations
+#[derive(Clone, Copy, Debug)]
+pub struct RegisteredCountry<'a>(CountryEntry<'a>);
+
+impl<'a> RegisteredCountry<'a> {
+    /// Returns the country's name (or, if names are left out of the code enumerations, its alpha-2
+    /// code for a country known to them)
+    pub fn name(&self) -> &'a str {
+        match self.0 {
+            CountryEntry::Loaded(country, _) => &country.name,
+            #[cfg(not(feature = "without-names"))]
+            CountryEntry::CompiledIn(country) => country.name(),
+            #[cfg(feature = "without-names")]
+            CountryEntry::CompiledIn(country) => country.code(),
+        }
+    }
+
+    /// Returns the country's ISO 3166-1 alpha-2 code
+    pub fn alpha_2(&self) -> &'a str {
+        match self.0 {
+            CountryEntry::Loaded(country, _) => &country.alpha_2,
+            CountryEntry::CompiledIn(country) => country.code(),
+        }
+    }
+
+    /// Returns the country's ISO 3166-1 alpha-3 code
+    pub fn alpha_3(&self) -> &'a str {
+        match self.0 {
+            CountryEntry::Loaded(country, _) => &country.alpha_3,
+            CountryEntry::CompiledIn(country) => Iso3166_1_alpha_3::from(country).code(),
+        }
+    }
+
+    /// Returns the country's ISO 3166-1 numeric code
+    pub fn numeric(&self) -> u16 {
+        match self.0 {
+            CountryEntry::Loaded(country, _) => country.numeric,
+            CountryEntry::CompiledIn(country) => country.numeric(),
+        }
+    }
+
+    /// Returns where the country came from
+    pub fn source(&self) -> Source<'a> {
+        match self.0 {
+            CountryEntry::Loaded(_, source) => Source::Loaded(source),
+            CountryEntry::CompiledIn(_) => Source::CompiledIn,
+        }
+    }
+
+    /// Returns the country as a variant of the code enumerations, if they know of it (by its
+    /// alpha-2 code), through which anything else the [`Country`] trait knows of it can be had
+    pub fn known(&self) -> Option<Iso3166_1_alpha_2> {
+        match self.0 {
+            CountryEntry::Loaded(country, _) => {
+                Iso3166_1_alpha_2::from_bytes(country.alpha_2.as_bytes())
+            }
+            CountryEntry::CompiledIn(country) => Some(country),
+        }
+    }
+
+    /// Returns the country's name in French, or `None` if only a dataset loaded at runtime knows
+    /// of the country
+    #[cfg(not(feature = "without-names"))]
+    pub fn name_fr(&self) -> Option<&'static str> {
+        self.known().map(|country| country.name_fr())
+    }
+
+    /// Returns the country's name within a locale, as given by the Unicode CLDR, or `None` if it
+    /// is not given one there or only a dataset loaded at runtime knows of the country
+    #[cfg(feature = "cldr")]
+    pub fn localized_name(&self, locale: &str) -> Option<&'static str> {
+        self.known()
+            .and_then(|country| country.localized_name(locale))
+    }
+
+    /// Returns the country's short name, or `None` if only a dataset loaded at runtime knows of
+    /// the country
+    #[cfg(not(feature = "without-names"))]
+    pub fn short_name(&self) -> Option<&'static str> {
+        self.known().map(|country| country.short_name())
+    }
+
+    /// Returns the country's full name, or `None` if it has none or only a dataset loaded at
+    /// runtime knows of the country
+    #[cfg(not(feature = "without-names"))]
+    pub fn full_name(&self) -> Option<&'static str> {
+        self.known().and_then(|country| country.full_name())
+    }
+
+    /// Returns the country's common name, or `None` if only a dataset loaded at runtime knows of
+    /// the country
+    #[cfg(not(feature = "without-names"))]
+    pub fn common_name(&self) -> Option<&'static str> {
+        self.known().map(|country| country.common_name())
+    }
+
+    /// Returns the name the country is sorted by, or `None` if only a dataset loaded at runtime
+    /// knows of the country
+    #[cfg(not(feature = "without-names"))]
+    pub fn sort_name(&self) -> Option<&'static str> {
+        self.known().map(|country| country.sort_name())
+    }
+
+    /// Returns the country's calling codes, or `None` if only a dataset loaded at runtime knows of
+    /// the country
+    pub fn calling_codes(&self) -> Option<CallingCodes> {
+        self.known().map(|country| country.calling_codes())
+    }
+
+    /// Returns the country's ccTLD, or `None` if it has none or only a dataset loaded at runtime
+    /// knows of the country
+    pub fn cctld(&self) -> Option<&'static str> {
+        self.known().and_then(|country| country.cctld())
+    }
+
+    /// Returns the countries the country borders on land, or `None` if only a dataset loaded at
+    /// runtime knows of the country
+    pub fn neighbors(&self) -> Option<&'static [Iso3166_1_alpha_2]> {
+        self.known().map(|country| country.neighbors())
+    }
+
+    /// Returns whether or not the country is independent, or `None` if only a dataset loaded at
+    /// runtime knows of the country
+    pub fn independent(&self) -> Option<bool> {
+        self.known().map(|country| country.independent())
+    }
+
+    /// Returns the country the country is a dependency of, or `None` if it is not a dependency or
+    /// only a dataset loaded at runtime knows of the country
+    pub fn sovereign(&self) -> Option<Iso3166_1_alpha_2> {
+        self.known().and_then(|country| country.sovereign())
+    }
+
+    /// Returns the dependencies of the country, or `None` if only a dataset loaded at runtime
+    /// knows of the country
+    pub fn dependencies(&self) -> Option<Dependencies> {
+        self.known().map(|country| country.dependencies())
+    }
+}
+
+impl fmt::Display for RegisteredCountry<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum LanguageEntry<'a> {
+    Loaded(&'a LoadedLanguage, &'a str),
+    CompiledIn(Iso639_3),
+}
+
+/// A language within a [`Registry`], which was either loaded at runtime or is known to the code
+/// enumerations
+#[derive(Clone, Copy, Debug)]
+pub struct RegisteredLanguage<'a>(LanguageEntry<'a>);
+
+impl<'a> RegisteredLanguage<'a> {
+    /// Returns the language's name (or, if names are left out of the code enumerations, its ISO
+    /// 639-3 code for a language known to them)
+    pub fn name(&self) -> &'a str {
+        match self.0 {
+            LanguageEntry::Loaded(language, _) => &language.name,
+            #[cfg(not(feature = "without-names"))]
+            LanguageEntry::CompiledIn(language) => language.name(),
+            #[cfg(feature = "without-names")]
+            LanguageEntry::CompiledIn(language) => language.code(),
+        }
+    }
+
+    /// Returns the language's ISO 639-3 code
+    pub fn iso639_3(&self) -> &'a str {
+        match self.0 {
+            LanguageEntry::Loaded(language, _) => &language.iso639_3,
+            LanguageEntry::CompiledIn(language) => language.code(),
+        }
+    }
+
+    /// Returns the language's ISO 639-2/B code, if it has one
+    pub fn iso639_2b(&self) -> Option<&'a str> {
+        match self.0 {
+            LanguageEntry::Loaded(language, _) => language.iso639_2b.as_deref(),
+            LanguageEntry::CompiledIn(language) => {
+                Iso639_2b::try_from(language).ok().map(Iso639_2b::code)
+            }
+        }
+    }
+
+    /// Returns the language's ISO 639-2/T code, if it has one
+    pub fn iso639_2t(&self) -> Option<&'a str> {
+        match self.0 {
+            LanguageEntry::Loaded(language, _) => language.iso639_2t.as_deref(),
+            LanguageEntry::CompiledIn(language) => {
+                Iso639_2t::try_from(language).ok().map(Iso639_2t::code)
+            }
+        }
+    }
+
+    /// Returns the language's ISO 639-1 code, if it has one
+    pub fn iso639_1(&self) -> Option<&'a str> {
+        match self.0 {
+            LanguageEntry::Loaded(language, _) => language.iso639_1.as_deref(),
+            LanguageEntry::CompiledIn(language) => {
+                Iso639_1::try_from(language).ok().map(Iso639_1::code)
+            }
+        }
+    }
+
+    /// Returns where the language came from
+    pub fn source(&self) -> Source<'a> {
+        match self.0 {
+            LanguageEntry::Loaded(_, source) => Source::Loaded(source),
+            LanguageEntry::CompiledIn(_) => Source::CompiledIn,
+        }
+    }
+
+    /// Returns the language as a variant of the code enumerations, if they know of it (by its ISO
+    /// 639-3 code), through which anything else the [`Language`] trait knows of it can be had
+    pub fn known(&self) -> Option<Iso639_3> {
+        match self.0 {
+            LanguageEntry::Loaded(language, _) => {
+                Iso639_3::from_bytes(language.iso639_3.as_bytes())
+            }
+            LanguageEntry::CompiledIn(language) => Some(language),
+        }
+    }
+
+    /// Returns the language's name in French, or `None` if it is not given one or only a dataset
+    /// loaded at runtime knows of the language
+    #[cfg(not(feature = "without-names"))]
+    pub fn name_fr(&self) -> Option<&'static str> {
+        self.known().and_then(|language| language.name_fr())
+    }
+
+    /// Returns the language's name within a locale, as given by the Unicode CLDR, or `None` if it
+    /// is not given one there or only a dataset loaded at runtime knows of the language
+    #[cfg(feature = "cldr")]
+    pub fn localized_name(&self, locale: &str) -> Option<&'static str> {
+        self.known()
+            .and_then(|language| language.localized_name(locale))
+    }
+
+    /// Returns the name the language is given by its own speakers (within a script, if one is
+    /// given), or `None` if it is not given one or only a dataset loaded at runtime knows of the
+    /// language
+    #[cfg(not(feature = "without-names"))]
+    pub fn native_name(&self, script: Option<&str>) -> Option<&'static str> {
+        self.known()
+            .and_then(|language| language.native_name(script))
+    }
+}
+
+impl fmt::Display for RegisteredLanguage<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
@@ -0,0 +1,33 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Checks the provenance the crate reports for the datasets it was built from
+//!
+//! Run with `cargo test -p iso`
+
+use iso::{country, language};
+
+#[test]
+fn bundled_datasets_report_their_provenance() {
+    for dataset in &[country::DATASET, language::DATASET] {
+        // a replacement only has a version if one was given alongside it
+        if !dataset.bundled {
+            continue;
+        }
+        assert!(dataset.source.is_some(), "{}", dataset.description);
+        assert!(dataset.licence.is_some(), "{}", dataset.description);
+        assert!(dataset.attribution.is_some(), "{}", dataset.description);
+    }
+    if country::DATASET.bundled {
+        assert!(country::DATASET_VERSION.is_some());
+    }
+    if language::DATASET.bundled {
+        assert!(language::DATASET_VERSION.is_some());
+    }
+}
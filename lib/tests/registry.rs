@@ -0,0 +1,244 @@
+//
+// iso - implementations of datatypes related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+//! Checks the precedence of the datasets loaded into a registry, and its fallback to the code
+//! enumerations
+//!
+//! Run with `cargo test -p iso --features registry`
+
+use iso::{
+    country::{Country, Iso3166_1_alpha_2},
+    language::Iso639_3,
+    registry::{Registry, Source},
+};
+use std::{env, fs, process};
+
+const LANGUAGE_TABLE_HEADER: &str =
+    "Id\tPart2b\tPart2t\tPart1\tScope\tLanguage_Type\tRef_Name\tComment\n";
+
+fn country(name: &str, alpha_2: &str, alpha_3: &str, numeric: &str) -> String {
+    format!(
+        r#"{{"name": "{}", "alpha-2": "{}", "alpha-3": "{}", "country-code": "{}"}}"#,
+        name, alpha_2, alpha_3, numeric
+    )
+}
+
+fn countries(countries: &[String]) -> String {
+    format!("[{}]", countries.join(","))
+}
+
+#[test]
+fn later_countries_replace_every_code_of_earlier_ones() {
+    let mut registry = Registry::new();
+    registry
+        .add_country_codes("a", &countries(&[country("Germany A", "DE", "DEU", "276")]))
+        .unwrap();
+    registry
+        .add_country_codes("b", &countries(&[country("Germany B", "DE", "DEX", "277")]))
+        .unwrap();
+
+    let germany = registry.country("DE").unwrap();
+    assert_eq!(germany.name(), "Germany B");
+    assert_eq!(germany.source(), Source::Loaded("b"));
+    assert_eq!(registry.country("DEX").unwrap().name(), "Germany B");
+    assert_eq!(
+        registry.country_by_numeric(277).unwrap().name(),
+        "Germany B"
+    );
+
+    // the codes only the replaced entry gave (and the code enumerations know of) are gone
+    assert!(registry.country("DEU").is_none());
+    assert!(registry.country("276").is_none());
+    assert!(registry.country_by_numeric(276).is_none());
+
+    let loaded = registry
+        .countries()
+        .filter(|country| country.source() != Source::CompiledIn)
+        .map(|country| country.name())
+        .collect::<Vec<&str>>();
+    assert_eq!(loaded, ["Germany B"]);
+    assert_eq!(
+        registry.countries().count(),
+        Iso3166_1_alpha_2::iter().len()
+    );
+    assert_eq!(registry.sources().collect::<Vec<&str>>(), ["a", "b"]);
+}
+
+#[test]
+fn countries_sharing_any_code_are_replaced() {
+    let mut registry = Registry::new();
+    registry
+        .add_country_codes(
+            "a",
+            &countries(&[
+                country("First", "QM", "QAA", "900"),
+                country("Second", "QP", "QBB", "901"),
+            ]),
+        )
+        .unwrap();
+    registry
+        .add_country_codes("b", &countries(&[country("Third", "QR", "QAA", "902")]))
+        .unwrap();
+
+    assert!(registry.country("QM").is_none());
+    assert!(registry.country_by_numeric(900).is_none());
+    assert_eq!(registry.country("QAA").unwrap().alpha_2(), "QR");
+    assert_eq!(
+        registry.country("QP").unwrap().source(),
+        Source::Loaded("a")
+    );
+    assert_eq!(
+        registry
+            .countries()
+            .take_while(|country| country.source() != Source::CompiledIn)
+            .map(|country| country.name())
+            .collect::<Vec<&str>>(),
+        ["Second", "Third"]
+    );
+}
+
+#[test]
+fn later_languages_replace_every_code_of_earlier_ones() {
+    let mut registry = Registry::new();
+    registry
+        .add_language_table(
+            "a",
+            &format!(
+                "{}qqa\tqqb\tqqt\tqq\tI\tL\tOld Name\t\n",
+                LANGUAGE_TABLE_HEADER
+            ),
+        )
+        .unwrap();
+    registry
+        .add_language_table(
+            "b",
+            &format!("{}qqa\t\t\t\tI\tL\tNew Name\t\n", LANGUAGE_TABLE_HEADER),
+        )
+        .unwrap();
+
+    let language = registry.language("qqa").unwrap();
+    assert_eq!(language.name(), "New Name");
+    assert_eq!(language.source(), Source::Loaded("b"));
+    assert_eq!(language.iso639_1(), None);
+    assert!(registry.language("qq").is_none());
+    assert!(registry.language("qqb").is_none());
+    assert!(registry.language("qqt").is_none());
+    assert_eq!(
+        registry
+            .languages()
+            .filter(|language| language.source() != Source::CompiledIn)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn codes_fall_back_to_the_code_enumerations() {
+    let mut registry = Registry::new();
+    registry
+        .add_country_codes("new", &countries(&[country("Newland", "QN", "QNL", "901")]))
+        .unwrap();
+    registry
+        .add_language_table(
+            "new",
+            &format!("{}qqv\t\t\t\tI\tL\tNewlandic\t\n", LANGUAGE_TABLE_HEADER),
+        )
+        .unwrap();
+
+    for code in &["FR", "FRA", "250"] {
+        let france = registry.country(code).unwrap();
+        assert_eq!(france.source(), Source::CompiledIn);
+        assert_eq!(france.known(), Some(Iso3166_1_alpha_2::Fr));
+        assert_eq!((france.alpha_2(), france.alpha_3()), ("FR", "FRA"));
+        assert_eq!(france.numeric(), 250);
+        assert_eq!(france.independent(), Some(true));
+        assert_eq!(
+            france.calling_codes().map(|codes| codes.codes()),
+            Some(Iso3166_1_alpha_2::Fr.calling_codes().codes())
+        );
+    }
+    for code in &["fr", "fre", "fra"] {
+        let french = registry.language(code).unwrap();
+        assert_eq!(french.source(), Source::CompiledIn);
+        assert_eq!(french.known(), Some(Iso639_3::Fra));
+        assert_eq!(french.iso639_2b(), Some("fre"));
+    }
+
+    // entries which only exist at runtime answer what their dataset holds, and nothing else
+    let newland = registry.country("QNL").unwrap();
+    assert_eq!(newland.source(), Source::Loaded("new"));
+    assert_eq!(newland.known(), None);
+    assert_eq!(newland.independent(), None);
+    assert_eq!(newland.neighbors(), None);
+    #[cfg(not(feature = "without-names"))]
+    assert_eq!(registry.language("qqv").unwrap().name_fr(), None);
+    assert!(registry.country("QZ").is_none());
+    assert!(registry.language("qqz").is_none());
+}
+
+#[test]
+fn countries_are_looked_up_by_numeric_code() {
+    let mut registry = Registry::new();
+    registry
+        .add_country_codes("new", &countries(&[country("Newland", "QN", "QNL", "009")]))
+        .unwrap();
+
+    assert_eq!(registry.country_by_numeric(9).unwrap().alpha_2(), "QN");
+    assert_eq!(registry.country("009").unwrap().alpha_2(), "QN");
+    assert_eq!(registry.country_by_numeric(4).unwrap().alpha_2(), "AF");
+    assert_eq!(registry.country("004").unwrap().alpha_2(), "AF");
+
+    // numeric codes are only given as strings of three digits
+    assert!(registry.country("4").is_none());
+    assert!(registry.country_by_numeric(999).is_none());
+}
+
+#[test]
+fn datasets_are_loaded_from_files() {
+    let directory = env::temp_dir().join(format!("iso-registry-{}", process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("language.tab");
+    fs::write(
+        &path,
+        format!("{}qqv\t\t\t\tI\tL\tNewlandic\t\n", LANGUAGE_TABLE_HEADER),
+    )
+    .unwrap();
+
+    let mut registry = Registry::new();
+    assert_eq!(registry.load_language_table(&path).unwrap(), 1);
+    let name = path.to_string_lossy();
+    assert_eq!(
+        registry.language("qqv").unwrap().source(),
+        Source::Loaded(&name)
+    );
+    assert!(registry
+        .load_country_codes(directory.join("missing.json"))
+        .is_err());
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn invalid_datasets_load_nothing() {
+    let mut registry = Registry::new();
+    let error = registry
+        .add_country_codes(
+            "broken",
+            &countries(&[
+                country("Newland", "QN", "QNL", "901"),
+                country("Newland Again", "QN", "QNX", "902"),
+            ]),
+        )
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "broken:entry 2: the code `QN` was already given by entry 1"
+    );
+    assert!(registry.country("QNL").is_none());
+    assert_eq!(registry.sources().count(), 0);
+}
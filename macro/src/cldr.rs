@@ -0,0 +1,394 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Code generation for the localized names vendored from the Unicode CLDR
+//!
+//! The `assets/cldr` directory mirrors the layout of the `cldr-json` packages. Each locale has a
+//! directory of its own, holding the `territories.json` and `languages.json` files of
+//! `cldr-localenames-full`, while `parentLocales.json` is taken from `cldr-core`. The files are
+//! vendored unmodified, and are pruned here instead: only the names given to identifiers which
+//! correspond to a code are kept (leaving out regions, scripts, dialects and alternative forms),
+//! and every locale is compiled in only when the `cldr-<locale>` feature (with the locale in
+//! lowercase) is enabled on the crate invoking the macro.
+
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::{read_dir, File},
+    io::BufReader,
+    path::Path,
+    result::Result as StdResult,
+};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    Error, Ident, Token, Visibility,
+};
+
+use crate::{
+    ascii_formatter, dataset::dependency_hook, packed, parse_country_codes, parse_language_table,
+    subset, Dataset, LanguageTableEntryKey, PackedNames,
+};
+
+/// The kind of names held by a locale's dataset
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum NameKind {
+    Territories,
+    Languages,
+}
+
+impl NameKind {
+    fn file_name(self) -> &'static str {
+        match self {
+            Self::Territories => "territories",
+            Self::Languages => "languages",
+        }
+    }
+}
+
+fn load_json(path: &Path) -> StdResult<Value, String> {
+    let reader = BufReader::new(File::open(path).map_err(|e| {
+        format!(
+            "unable to load {}: {}",
+            path.as_os_str().to_string_lossy(),
+            e
+        )
+    })?);
+    serde_json::from_reader(reader).map_err(|e| {
+        format!(
+            "unable to parse {}: {}",
+            path.as_os_str().to_string_lossy(),
+            e
+        )
+    })
+}
+
+/// Returns the locales present within the CLDR directory, sorted by name
+fn locales(cldr_directory: &Path) -> StdResult<Vec<String>, String> {
+    let mut locales = read_dir(cldr_directory)
+        .map_err(|e| {
+            format!(
+                "unable to read {}: {}",
+                cldr_directory.as_os_str().to_string_lossy(),
+                e
+            )
+        })?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect::<Vec<String>>();
+    locales.sort();
+    Ok(locales)
+}
+
+/// Returns the names of a kind given by a locale, keyed by their CLDR identifier
+fn names(
+    cldr_directory: &Path,
+    locale: &str,
+    kind: NameKind,
+) -> StdResult<BTreeMap<String, String>, String> {
+    let path = cldr_directory
+        .join(locale)
+        .join(format!("{}.json", kind.file_name()));
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let document = load_json(&path)?;
+    let names = document
+        .pointer(&format!(
+            "/main/{}/localeDisplayNames/{}",
+            locale,
+            kind.file_name()
+        ))
+        .and_then(Value::as_object)
+        .ok_or_else(|| {
+            format!(
+                "{} does not hold the {} of `{}`",
+                path.as_os_str().to_string_lossy(),
+                kind.file_name(),
+                locale
+            )
+        })?;
+    names
+        .iter()
+        .map(|(key, name)| {
+            name.as_str()
+                .map(|name| (key.clone(), name.to_string()))
+                .ok_or_else(|| {
+                    format!(
+                        "{}: the name given to `{}` is not a string",
+                        path.as_os_str().to_string_lossy(),
+                        key
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Returns the name of the feature controlling whether or not a locale is compiled in
+fn feature(locale: &str) -> String {
+    format!("cldr-{}", locale.to_lowercase())
+}
+
+/// Maps each CLDR identifier which corresponds to a code to the variant of the requested
+/// enumeration it corresponds to
+fn variants(
+    enumeration: &Ident,
+    manifest_directory: &Path,
+) -> StdResult<(NameKind, HashMap<String, Ident>), String> {
+    let mut variants = HashMap::new();
+    let formatted = |code: &str| {
+        let mut variant = code.to_string();
+        ascii_formatter(&mut variant);
+        Ident::new(&variant, Span::call_site())
+    };
+
+    if enumeration == "Iso3166_1_alpha_2" {
+        for entry in parse_country_codes(&Dataset::CountryCodes.path(manifest_directory))? {
+            variants.insert(entry.alpha_2.clone(), formatted(&entry.alpha_2));
+        }
+        return Ok((NameKind::Territories, variants));
+    }
+
+    let key = match enumeration.to_string().as_ref() {
+        "Iso639_1" => LanguageTableEntryKey::Iso639_1,
+        "Iso639_2b" => LanguageTableEntryKey::Iso639_2b,
+        "Iso639_2t" => LanguageTableEntryKey::Iso639_2t,
+        "Iso639_3" => LanguageTableEntryKey::Iso639_3,
+        _ => {
+            return Err(format!(
+                "`{}` is not a country or language code enumeration",
+                enumeration
+            ))
+        }
+    };
+    for entry in parse_language_table(&Dataset::LanguageTable.path(manifest_directory))? {
+        if let Some(code) = entry.get(&key) {
+            // cldr identifies languages by their iso 639-1 code where there is one, and by their
+            // iso 639-3 code otherwise
+            let identifier = entry
+                .get(&LanguageTableEntryKey::Iso639_1)
+                .unwrap_or(&entry[&LanguageTableEntryKey::Iso639_3]);
+            variants.insert(identifier.clone(), formatted(code));
+        }
+    }
+    Ok((NameKind::Languages, variants))
+}
+
+/// The shape of the code to generate from the CLDR datasets
+enum CldrForm {
+    /// A `match` over the provided locale and code, yielding the name given to the code within the
+    /// locale
+    Match {
+        locale: Ident,
+        match_against: TokenStream2,
+        enumeration: Ident,
+    },
+
+    /// A `static` slice of every locale, alongside whether or not it is compiled in
+    Locales(Visibility, Ident),
+
+    /// A `static` slice of every locale which does not fall back to the locale formed by removing
+    /// its last subtag, alongside the locale it falls back to instead
+    Parents(Visibility, Ident),
+}
+
+pub(crate) struct CldrInput {
+    form: CldrForm,
+}
+
+impl Parse for CldrInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let form = if input.peek(Token![match]) {
+            input.parse::<Token![match]>()?;
+            let locale = input.parse::<Ident>()?;
+            input.parse::<Token![,]>()?;
+            let mut match_against = TokenStream2::new();
+            if input.peek(Token![&]) {
+                input.parse::<Token![&]>()?.to_tokens(&mut match_against);
+            }
+            if input.peek(Token![self]) {
+                input.parse::<Token![self]>()?.to_tokens(&mut match_against);
+            } else {
+                input.parse::<Ident>()?.to_tokens(&mut match_against);
+            }
+            input.parse::<Token![:]>()?;
+            CldrForm::Match {
+                locale,
+                match_against,
+                enumeration: input.parse::<Ident>()?,
+            }
+        } else {
+            let visibility = input.parse::<Visibility>()?;
+            input.parse::<Token![static]>()?;
+            let name = input.parse::<Ident>()?;
+            input.parse::<Token![:]>()?;
+            let contents = input.parse::<Ident>()?;
+            if contents == "locales" {
+                CldrForm::Locales(visibility, name)
+            } else if contents == "parents" {
+                CldrForm::Parents(visibility, name)
+            } else {
+                return Err(Error::new(
+                    contents.span(),
+                    "expected either `locales` or `parents`",
+                ));
+            }
+        };
+
+        Ok(CldrInput { form })
+    }
+}
+
+pub(crate) fn generate(input: CldrInput, manifest_directory: &Path) -> Result<TokenStream2> {
+    let cldr_directory = manifest_directory.join("assets/cldr");
+    let error = |e: String| Error::new(Span::call_site(), e);
+
+    match input.form {
+        CldrForm::Match {
+            locale,
+            match_against,
+            enumeration,
+        } => {
+            let (kind, variants) = variants(&enumeration, manifest_directory).map_err(error)?;
+            let mut arms = Vec::new();
+            for name in locales(&cldr_directory).map_err(error)? {
+                let names = names(&cldr_directory, &name, kind).map_err(error)?;
+
+                // a code may be given several names (such as `GB` and `GB-alt-short`), and only
+                // identifiers which correspond to a variant are kept
+                let mut seen = HashSet::new();
+                let names = names
+                    .iter()
+                    .filter_map(|(identifier, name)| {
+                        let variant = variants.get(identifier)?;
+                        if !seen.insert(variant) {
+                            return None;
+                        }
+                        Some((variant, subset::optional_name(name)?))
+                    })
+                    .collect::<Vec<(&Ident, String)>>();
+                if names.is_empty() {
+                    continue;
+                }
+
+                let feature = feature(&name);
+                let name = Literal::string(&name);
+                if packed::is_enabled() {
+                    // the names of each locale are packed into a string of their own, so that
+                    // only those of the locales compiled in take up any space
+                    let packed_names =
+                        PackedNames::new(names.iter().map(|(_, name)| name.as_str()))
+                            .map_err(error)?;
+                    let text = packed_names.text();
+                    let absent = packed::absent();
+                    let rows = names.iter().map(|(variant, name)| {
+                        let name = packed_names.name(name);
+                        quote! { #enumeration::#variant => #name }
+                    });
+                    arms.push(quote! {
+                        #[cfg(feature = #feature)]
+                        #name => {
+                            static NAMES: &str = #text;
+                            crate::packed::unpack_optional(NAMES, match #match_against {
+                                #(#rows),*,
+                                #[allow(unreachable_patterns)]
+                                _ => #absent,
+                            })
+                        }
+                    });
+                } else {
+                    let rows = names.iter().map(|(variant, name)| {
+                        let name = Literal::string(name);
+                        quote! { #enumeration::#variant => Some(#name) }
+                    });
+                    arms.push(quote! {
+                        #[cfg(feature = #feature)]
+                        #name => match #match_against {
+                            #(#rows),*,
+                            #[allow(unreachable_patterns)]
+                            _ => None,
+                        }
+                    });
+                }
+            }
+
+            // the arms are only compiled in when their locale's feature is enabled, so the locale
+            // and code are used outside of them as well to avoid unused variable warnings when no
+            // locales are
+            Ok(quote! {
+                {
+                    let _ = (#locale, &#match_against);
+                    match #locale {
+                        #(#arms)*
+                        _ => None,
+                    }
+                }
+            })
+        }
+        CldrForm::Locales(visibility, name) => {
+            let locales = locales(&cldr_directory).map_err(error)?;
+
+            // every locale is listed here, which makes this the one place the crate is made to
+            // depend on the names of each, rather than every `match` over them
+            let hook = dependency_hook(
+                locales
+                    .iter()
+                    .flat_map(|locale| {
+                        let directory = cldr_directory.join(locale);
+                        [NameKind::Territories, NameKind::Languages]
+                            .iter()
+                            .map(move |kind| directory.join(format!("{}.json", kind.file_name())))
+                    })
+                    .filter(|path| path.exists()),
+            );
+            let rows = locales.into_iter().map(|locale| {
+                let feature = feature(&locale);
+                let locale = Literal::string(&locale);
+                quote! { (#locale, cfg!(feature = #feature)) }
+            });
+
+            Ok(quote! {
+                #hook
+                #visibility static #name: &[(&str, bool)] = &[
+                    #(#rows),*
+                ];
+            })
+        }
+        CldrForm::Parents(visibility, name) => {
+            let path = cldr_directory.join("parentLocales.json");
+            let document = load_json(&path).map_err(error)?;
+            let parents = document
+                .pointer("/supplemental/parentLocales/parentLocale")
+                .and_then(Value::as_object)
+                .ok_or_else(|| {
+                    error(format!(
+                        "{} does not hold any parent locales",
+                        path.as_os_str().to_string_lossy()
+                    ))
+                })?;
+            let rows = parents.iter().filter_map(|(locale, parent)| {
+                let locale = Literal::string(locale);
+                let parent = Literal::string(parent.as_str()?);
+                Some(quote! { (#locale, #parent) })
+            });
+
+            let hook = dependency_hook([&path]);
+            Ok(quote! {
+                #hook
+                #visibility static #name: &[(&str, &str)] = &[
+                    #(#rows),*
+                ];
+            })
+        }
+    }
+}
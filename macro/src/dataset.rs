@@ -0,0 +1,429 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Resolution of the primary datasets, which may be overridden through the environment
+//!
+//! Each primary dataset is read from the `assets` directory unless the environment variable
+//! naming it is set to the path of a replacement, in which case the replacement is read instead.
+//! Relative paths are resolved against the directory the compiler is run from, which cargo sets to
+//! the directory of the crate being built rather than that of the project depending on it, so the
+//! path should be absolute (a path given in the `[env]` table of a cargo configuration file may be
+//! marked with `relative = true` to have cargo make it so). The version of a replacement may be
+//! given through a second variable, and is otherwise unknown.
+//!
+//! As proc macros have no stable way to tell cargo what they read, the code generated from a
+//! dataset reads each variable with `option_env!` and includes every file it was generated from
+//! with `include_bytes!`, which records both within the crate's dependency information so that a
+//! change to either (such as a run of `cargo xtask update`) causes it to be rebuilt. The included
+//! bytes are bound to unnamed constants, so they never make it into the compiled crate.
+//!
+//! Where each bundled dataset was taken from is recorded within `assets/sources.tab`, a
+//! tab-separated file with a header and a row for each bundled dataset giving the name of its file,
+//! its source, its version (which is left empty where it is not known), its licence and the
+//! attribution its licence requires. None of these are known for a replacement other than its
+//! version.
+
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::quote;
+use std::{
+    env::{current_dir, var_os},
+    fs::{read, read_to_string},
+    path::{Path, PathBuf},
+    result::Result as StdResult,
+};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    Error, Ident,
+};
+
+use crate::{packed, parse_country_codes, subset};
+
+/// The primary datasets
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Dataset {
+    CountryCodes,
+    LanguageTable,
+}
+
+impl Dataset {
+    /// Returns the environment variable holding the path of a replacement for the dataset
+    fn variable(self) -> &'static str {
+        match self {
+            Self::CountryCodes => "ISO_COUNTRY_DATASET",
+            Self::LanguageTable => "ISO_LANGUAGE_TABLE",
+        }
+    }
+
+    /// Returns the environment variable holding the version of a replacement for the dataset
+    fn version_variable(self) -> &'static str {
+        match self {
+            Self::CountryCodes => "ISO_COUNTRY_DATASET_VERSION",
+            Self::LanguageTable => "ISO_LANGUAGE_TABLE_VERSION",
+        }
+    }
+
+    /// Returns the path of the dataset bundled with the crate, relative to its manifest directory
+    fn bundled(self) -> &'static str {
+        match self {
+            Self::CountryCodes => "assets/country.json",
+            Self::LanguageTable => "assets/language.tab",
+        }
+    }
+
+    /// Returns a description of the dataset
+    fn description(self) -> &'static str {
+        match self {
+            Self::CountryCodes => "ISO 3166-1 country codes",
+            Self::LanguageTable => "ISO 639-3 code tables",
+        }
+    }
+
+    /// Returns the path of the replacement for the dataset, if one has been given
+    pub(crate) fn replacement(self) -> Option<PathBuf> {
+        let path = PathBuf::from(var_os(self.variable())?);
+        Some(if path.is_relative() {
+            current_dir()
+                .map(|directory| directory.join(&path))
+                .unwrap_or(path)
+        } else {
+            path
+        })
+    }
+
+    /// Returns the path the dataset is read from
+    pub(crate) fn path(self, manifest_directory: &Path) -> PathBuf {
+        self.replacement()
+            .unwrap_or_else(|| manifest_directory.join(self.bundled()))
+    }
+
+    /// Returns the version a replacement for the dataset was given, if any
+    fn replacement_version(self) -> Option<String> {
+        var_os(self.version_variable()).map(|version| version.to_string_lossy().into_owned())
+    }
+
+    /// Returns the provenance recorded for the bundled dataset
+    fn provenance(self, manifest_directory: &Path) -> StdResult<Provenance, String> {
+        let path = manifest_directory.join(SOURCES);
+        let contents = read_to_string(&path).map_err(|e| {
+            format!(
+                "unable to load the dataset sources, {}: {}",
+                path.as_os_str().to_string_lossy(),
+                e
+            )
+        })?;
+        let file_name = Path::new(self.bundled())
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        contents
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .skip(1)
+            .map(|line| line.split('\t').collect::<Vec<&str>>())
+            .find(|cells| cells[0] == file_name)
+            .map(|cells| {
+                let cell = |index: usize| {
+                    cells
+                        .get(index)
+                        .filter(|cell| !cell.is_empty())
+                        .map(|cell| cell.to_string())
+                };
+                Provenance {
+                    source: cell(1),
+                    version: cell(2),
+                    licence: cell(3),
+                    attribution: cell(4),
+                }
+            })
+            .ok_or_else(|| {
+                format!(
+                    "{} has no row for `{}`",
+                    path.as_os_str().to_string_lossy(),
+                    file_name
+                )
+            })
+    }
+
+    /// Returns the number of entries within the dataset, given its contents
+    fn rows(self, path: &Path, contents: &str) -> StdResult<usize, String> {
+        Ok(match self {
+            Self::CountryCodes => parse_country_codes(path)?.len(),
+
+            // the language table is filtered by the enabled subsets as it is parsed, so its
+            // entries are counted from its lines instead
+            Self::LanguageTable => contents
+                .lines()
+                .skip(1)
+                .filter(|line| !line.is_empty())
+                .count(),
+        })
+    }
+
+    /// Returns items which make the crate depend on the environment variables read for the
+    /// dataset (including those selecting its subsets and how its names are stored), as well as on
+    /// the file it is read from
+    pub(crate) fn dependency_hook(self, manifest_directory: &Path) -> TokenStream2 {
+        let variables = [
+            self.variable(),
+            self.version_variable(),
+            subset::VARIABLE,
+            packed::VARIABLE,
+        ];
+        let files = dependency_hook([self.path(manifest_directory)]);
+        quote! {
+            #(const _: Option<&str> = option_env!(#variables);)*
+            #files
+        }
+    }
+}
+
+/// The path of the file recording the provenance of the bundled datasets, relative to the manifest
+/// directory of the crate
+const SOURCES: &str = "assets/sources.tab";
+
+/// Where a bundled dataset was taken from, and under what terms
+struct Provenance {
+    source: Option<String>,
+    version: Option<String>,
+    licence: Option<String>,
+    attribution: Option<String>,
+}
+
+/// Returns an optional string as an expression
+fn optional_string(string: Option<&str>) -> TokenStream2 {
+    if let Some(string) = string {
+        let string = Literal::string(string);
+        quote! { Some(#string) }
+    } else {
+        quote! { None }
+    }
+}
+
+/// Returns items which make the crate depend on the contents of the provided files, so that it is
+/// rebuilt whenever any of them change
+pub(crate) fn dependency_hook<P>(paths: impl IntoIterator<Item = P>) -> TokenStream2
+where
+    P: AsRef<Path>,
+{
+    let paths = paths
+        .into_iter()
+        .map(|path| Literal::string(&path.as_ref().to_string_lossy()));
+    quote! {
+        #(const _: &[u8] = include_bytes!(#paths);)*
+    }
+}
+
+pub(crate) struct DatasetInput {
+    dataset: Dataset,
+}
+
+impl Parse for DatasetInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let dataset = input.parse::<Ident>()?;
+        let dataset = if dataset == "country" {
+            Dataset::CountryCodes
+        } else if dataset == "language" {
+            Dataset::LanguageTable
+        } else {
+            return Err(Error::new(
+                dataset.span(),
+                "expected either `country` or `language`",
+            ));
+        };
+
+        Ok(DatasetInput { dataset })
+    }
+}
+
+pub(crate) fn generate(input: DatasetInput, manifest_directory: &Path) -> Result<TokenStream2> {
+    let dataset = input.dataset;
+    let hook = dataset.dependency_hook(manifest_directory);
+    let path = dataset.path(manifest_directory);
+    let error = |e: String| Error::new(Span::call_site(), e);
+    let contents = read(&path).map_err(|e| {
+        error(format!(
+            "unable to load the dataset, {}: {}",
+            path.as_os_str().to_string_lossy(),
+            e
+        ))
+    })?;
+    let rows = dataset
+        .rows(&path, &String::from_utf8_lossy(&contents))
+        .map_err(error)?;
+    let checksum = sha256(&contents);
+    let bundled = dataset.replacement().is_none();
+    let (provenance, version) = if bundled {
+        let provenance = dataset.provenance(manifest_directory).map_err(error)?;
+        let version = provenance.version.clone();
+        (Some(provenance), version)
+    } else {
+        (None, dataset.replacement_version())
+    };
+    let field = |field: fn(&Provenance) -> &Option<String>| {
+        optional_string(provenance.as_ref().and_then(|p| field(p).as_deref()))
+    };
+    let description = dataset.description();
+    let source = field(|p| &p.source);
+    let version = optional_string(version.as_deref());
+    let licence = field(|p| &p.licence);
+    let attribution = field(|p| &p.attribution);
+    let sources = dependency_hook([manifest_directory.join(SOURCES)]);
+
+    Ok(quote! {
+        {
+            #hook
+            #sources
+            crate::DatasetInfo {
+                description: #description,
+                bundled: #bundled,
+                source: #source,
+                version: #version,
+                rows: #rows,
+                checksum: #checksum,
+                licence: #licence,
+                attribution: #attribution,
+            }
+        }
+    })
+}
+
+/// Returns the SHA-256 digest of the provided bytes, as a lowercase hexadecimal string
+fn sha256(bytes: &[u8]) -> String {
+    const K: [u32; 64] = [
+        0x428a_2f98,
+        0x7137_4491,
+        0xb5c0_fbcf,
+        0xe9b5_dba5,
+        0x3956_c25b,
+        0x59f1_11f1,
+        0x923f_82a4,
+        0xab1c_5ed5,
+        0xd807_aa98,
+        0x1283_5b01,
+        0x2431_85be,
+        0x550c_7dc3,
+        0x72be_5d74,
+        0x80de_b1fe,
+        0x9bdc_06a7,
+        0xc19b_f174,
+        0xe49b_69c1,
+        0xefbe_4786,
+        0x0fc1_9dc6,
+        0x240c_a1cc,
+        0x2de9_2c6f,
+        0x4a74_84aa,
+        0x5cb0_a9dc,
+        0x76f9_88da,
+        0x983e_5152,
+        0xa831_c66d,
+        0xb003_27c8,
+        0xbf59_7fc7,
+        0xc6e0_0bf3,
+        0xd5a7_9147,
+        0x06ca_6351,
+        0x1429_2967,
+        0x27b7_0a85,
+        0x2e1b_2138,
+        0x4d2c_6dfc,
+        0x5338_0d13,
+        0x650a_7354,
+        0x766a_0abb,
+        0x81c2_c92e,
+        0x9272_2c85,
+        0xa2bf_e8a1,
+        0xa81a_664b,
+        0xc24b_8b70,
+        0xc76c_51a3,
+        0xd192_e819,
+        0xd699_0624,
+        0xf40e_3585,
+        0x106a_a070,
+        0x19a4_c116,
+        0x1e37_6c08,
+        0x2748_774c,
+        0x34b0_bcb5,
+        0x391c_0cb3,
+        0x4ed8_aa4a,
+        0x5b9c_ca4f,
+        0x682e_6ff3,
+        0x748f_82ee,
+        0x78a5_636f,
+        0x84c8_7814,
+        0x8cc7_0208,
+        0x90be_fffa,
+        0xa450_6ceb,
+        0xbef9_a3f7,
+        0xc671_78f2,
+    ];
+    let mut state: [u32; 8] = [
+        0x6a09_e667,
+        0xbb67_ae85,
+        0x3c6e_f372,
+        0xa54f_f53a,
+        0x510e_527f,
+        0x9b05_688c,
+        0x1f83_d9ab,
+        0x5be0_cd19,
+    ];
+
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((bytes.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut schedule = [0_u32; 64];
+        for (word, chunk) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        for i in 16..64 {
+            let s0 = schedule[i - 15].rotate_right(7)
+                ^ schedule[i - 15].rotate_right(18)
+                ^ (schedule[i - 15] >> 3);
+            let s1 = schedule[i - 2].rotate_right(17)
+                ^ schedule[i - 2].rotate_right(19)
+                ^ (schedule[i - 2] >> 10);
+            schedule[i] = schedule[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(schedule[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for (k, word) in K.iter().zip(&schedule) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(choice)
+                .wrapping_add(*k)
+                .wrapping_add(*word);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(majority);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    state.iter().map(|word| format!("{:08x}", word)).collect()
+}
@@ -21,7 +21,7 @@
 #![deny(clippy::cast_lossless)]
 #![deny(clippy::clone_on_ref_ptr)]
 #![deny(clippy::doc_markdown)]
-#![deny(clippy::empty_enum)]
+#![deny(clippy::empty_enums)]
 #![deny(clippy::enum_glob_use)]
 #![deny(clippy::exit)]
 #![deny(clippy::explicit_into_iter_loop)]
@@ -35,9 +35,8 @@
 #![deny(clippy::single_match_else)]
 #![deny(clippy::option_option)]
 #![deny(clippy::mut_mut)]
-#![feature(proc_macro_diagnostic)]
 
-use proc_macro::{Diagnostic, Level, TokenStream};
+use proc_macro::TokenStream;
 use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens};
 use serde::Deserialize;
@@ -45,20 +44,33 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     env::var,
-    fmt::Debug,
-    fs::File,
+    fmt::Display,
+    fs::{read_to_string, File},
     io::{BufRead, BufReader},
+    iter::once,
     path::{Path, PathBuf},
     result::Result as StdResult,
 };
 use syn::{
+    bracketed,
     parse::{Parse, ParseStream, Result},
-    parse_macro_input, Ident, LitStr, Token,
+    parse_macro_input, Error, Ident, LitStr, Token, Visibility,
 };
 
+use packed::PackedNames;
+
+mod cldr;
+mod dataset;
+mod literal;
+mod packed;
+mod subset;
+mod table;
+mod validate;
+
+use dataset::Dataset;
+
 //TODO(superwhiskers):
 //   - refactor the source code to not be so repetitive
-//   - give proper diagnostics and handle errors well (no `.unwrap()`)
 //   - add documentation comments
 
 /// A structure representing ISO country code entries
@@ -69,9 +81,11 @@ struct CountryEntry {
     alpha_2: String,
     alpha_3: String,
     country_code: String,
+    #[serde(skip)]
+    name_fr: String,
 }
 
-/// An enumeration over the supported ISO language code formats aas well as the name of the language
+/// An enumeration over the supported ISO language code formats and the names of the language
 #[derive(PartialEq, Eq, Hash, Clone)]
 enum LanguageTableEntryKey {
     Iso639_3,
@@ -79,9 +93,15 @@ enum LanguageTableEntryKey {
     Iso639_2t,
     Iso639_1,
     Name,
+    NameFr,
 }
 
 impl LanguageTableEntryKey {
+    /// Returns whether or not a language may lack a value for the key
+    fn may_be_absent(&self) -> bool {
+        !matches!(self, Self::Iso639_3 | Self::Name)
+    }
+
     fn as_standard_code(&self) -> Option<&'static str> {
         Some(match &self {
             Self::Iso639_3 => "639-3",
@@ -103,6 +123,7 @@ impl TryFrom<String> for LanguageTableEntryKey {
             "iso639_2t" => Self::Iso639_2t,
             "iso639_1" => Self::Iso639_1,
             "name" => Self::Name,
+            "name_fr" => Self::NameFr,
             _ => return Err("unable to find a matching variant"),
         })
     }
@@ -128,9 +149,19 @@ enum CountryIdentifierKey {
     Alpha3,
     Numeric,
     Name,
+    NameFr,
 }
 
 impl CountryIdentifierKey {
+    /// Returns the type of a cell of a generated table holding the key's value
+    fn value_type(&self, literal: bool, span: Span) -> Result<TokenStream2> {
+        Ok(match (self, literal) {
+            (Self::Numeric, true) => quote! { u16 },
+            (_, true) => quote! { &'static str },
+            (_, false) => enumeration_path(self, span)?.into_token_stream(),
+        })
+    }
+
     fn as_standard_code(&self) -> Option<&'static str> {
         Some(match &self {
             Self::Alpha2 => "3166-1 alpha-2",
@@ -150,11 +181,82 @@ impl TryFrom<String> for CountryIdentifierKey {
             "iso3166_1_alpha_3" => Self::Alpha3,
             "iso3166_1_numeric" => Self::Numeric,
             "name" => Self::Name,
+            "name_fr" => Self::NameFr,
             _ => return Err("unable to find a matching variant"),
         })
     }
 }
 
+impl CountryEntry {
+    /// Returns the value of a key for the entry as a literal
+    fn literal(&self, key: &CountryIdentifierKey) -> Result<Literal> {
+        Ok(match key {
+            CountryIdentifierKey::Alpha2 => Literal::string(&self.alpha_2),
+            CountryIdentifierKey::Alpha3 => Literal::string(&self.alpha_3),
+            CountryIdentifierKey::Numeric => {
+                Literal::u16_unsuffixed(self.country_code.parse().map_err(|e| {
+                    Error::new(
+                        Span::call_site(),
+                        format!(
+                            "`{}` is not a valid numeric code for {}: {}",
+                            self.country_code, self.alpha_2, e
+                        ),
+                    )
+                })?)
+            }
+            CountryIdentifierKey::Name => Literal::string(&self.name),
+            CountryIdentifierKey::NameFr => Literal::string(&self.name_fr),
+        })
+    }
+
+    /// Returns the value of a key for the entry, as it is written in the dataset
+    fn string(&self, key: &CountryIdentifierKey) -> &str {
+        match key {
+            CountryIdentifierKey::Alpha2 => &self.alpha_2,
+            CountryIdentifierKey::Alpha3 => &self.alpha_3,
+            CountryIdentifierKey::Numeric => &self.country_code,
+            CountryIdentifierKey::Name => &self.name,
+            CountryIdentifierKey::NameFr => &self.name_fr,
+        }
+    }
+
+    /// Returns the value of a key for the entry as a cell of a generated table
+    fn cell(&self, (key, literal): &(CountryIdentifierKey, bool), span: Span) -> Result<Cell> {
+        Ok(
+            if let (CountryIdentifierKey::Name | CountryIdentifierKey::NameFr, true) =
+                (key, literal)
+            {
+                Cell::Name(self.string(key).to_string())
+            } else if *literal {
+                Cell::Value(self.literal(key)?.into_token_stream())
+            } else {
+                let path = enumeration_path(key, span)?;
+                let variant = self.identifier(key, span)?;
+                Cell::Value(quote! { #path::#variant })
+            },
+        )
+    }
+
+    /// Returns the variant a key's value for the entry corresponds to, as an identifier
+    fn identifier(&self, key: &CountryIdentifierKey, span: Span) -> Result<Ident> {
+        let mut identifier = match key {
+            CountryIdentifierKey::Alpha2 => self.alpha_2.clone(),
+            CountryIdentifierKey::Alpha3 => self.alpha_3.clone(),
+            CountryIdentifierKey::Numeric => {
+                return Err(Error::new(
+                    span,
+                    "numeric identifiers cannot be used as an identifier",
+                ))
+            }
+            CountryIdentifierKey::Name | CountryIdentifierKey::NameFr => {
+                return Err(Error::new(span, "names cannot be used as an identifier"))
+            }
+        };
+        ascii_formatter(&mut identifier);
+        Ok(Ident::new(&identifier, Span::call_site()))
+    }
+}
+
 impl TryInto<&'static str> for CountryIdentifierKey {
     type Error = &'static str;
 
@@ -167,120 +269,260 @@ impl TryInto<&'static str> for CountryIdentifierKey {
     }
 }
 
-fn parse_country_codes(dataset: &Path) -> Option<Vec<CountryEntry>> {
-    let country_reader = BufReader::new(match File::open(dataset) {
-        Ok(file) => file,
-        Err(e) => {
-            Diagnostic::new(
-                Level::Error,
-                format!(
-                    "Unable to load the country code dataset, {}",
-                    dataset.as_os_str().to_string_lossy()
-                ),
-            )
-            .note(format!("{}", e))
-            .emit();
-            return None;
+fn parse_country_codes(dataset: &Path) -> StdResult<Vec<CountryEntry>, String> {
+    let contents = read_to_string(dataset).map_err(|e| {
+        format!(
+            "unable to load the country code dataset, {}: {}",
+            dataset.as_os_str().to_string_lossy(),
+            e
+        )
+    })?;
+
+    // note: errors from `serde_json` already give the line and column they were found at
+    let entries: Vec<CountryEntry> = serde_json::from_str(&contents).map_err(|e| {
+        format!(
+            "unable to parse the country code dataset, {}: {}",
+            dataset.as_os_str().to_string_lossy(),
+            e
+        )
+    })?;
+    validate::country_codes(dataset, &contents, &entries)?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| CountryEntry {
+            name: subset::name(&entry.name),
+            ..entry
+        })
+        .collect())
+}
+
+fn parse_language_table(
+    table: &Path,
+) -> StdResult<Vec<HashMap<LanguageTableEntryKey, String>>, String> {
+    let contents = read_to_string(table).map_err(|e| {
+        format!(
+            "unable to load the language table, {}: {}",
+            table.as_os_str().to_string_lossy(),
+            e
+        )
+    })?;
+    validate::language_table(table, &contents)?;
+
+    let mut entries = Vec::new();
+    for raw_line in contents.lines().skip(1) {
+        let line = raw_line.split('\t').collect::<Vec<&str>>();
+        if !subset::includes_language(!line[3].is_empty(), line[5]) {
+            continue;
         }
-    });
 
-    Some(match serde_json::from_reader(country_reader) {
-        Ok(parsed) => parsed,
-        Err(e) => {
-            Diagnostic::new(
-                Level::Error,
-                format!(
-                    "Unable to parse the country code dataset, {}",
-                    dataset.as_os_str().to_string_lossy()
-                ),
-            )
-            .note(format!("{}", e))
-            .emit();
-            return None;
+        let mut entry = HashMap::new();
+        entry.insert(LanguageTableEntryKey::Iso639_3, line[0].to_string());
+        if !line[1].is_empty() {
+            entry.insert(LanguageTableEntryKey::Iso639_2b, line[1].to_string());
         }
-    })
+        if !line[2].is_empty() {
+            entry.insert(LanguageTableEntryKey::Iso639_2t, line[2].to_string());
+        }
+        if !line[3].is_empty() {
+            entry.insert(LanguageTableEntryKey::Iso639_1, line[3].to_string());
+        }
+        entry.insert(LanguageTableEntryKey::Name, subset::name(line[6]));
+
+        entries.push(entry);
+    }
+    Ok(entries)
 }
 
-fn parse_language_table(table: &Path) -> Option<Vec<HashMap<LanguageTableEntryKey, String>>> {
-    let table_reader = BufReader::new(match File::open(table) {
-        Ok(file) => file,
-        Err(e) => {
-            Diagnostic::new(
-                Level::Error,
-                format!(
-                    "Unable to load the language table, {}",
-                    table.as_os_str().to_string_lossy()
-                ),
+// note: the table is the french short name column of the iso online browsing platform, which has a
+// row of the form `alpha-2\tname` for each code following a header, and may hold comments
+fn parse_french_country_names(dataset: &Path) -> StdResult<HashMap<String, String>, String> {
+    let contents = read_to_string(dataset).map_err(|e| {
+        format!(
+            "unable to load the french country name dataset, {}: {}",
+            dataset.as_os_str().to_string_lossy(),
+            e
+        )
+    })?;
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .skip(1)
+        .map(|(index, line)| match line.split_once('\t') {
+            Some((alpha_2, name)) => Ok((alpha_2.trim().to_string(), name.trim().to_string())),
+            None => Err(format!(
+                "{}:{}: the row is not of the form `alpha-2<tab>name`",
+                dataset.as_os_str().to_string_lossy(),
+                index + 1
+            )),
+        })
+        .collect()
+}
+
+// note: the table is the library of congress' iso 639-2 code list, which has a row of the form
+// `bibliographic|terminologic|alpha-2|english names|french names` for each code. names within the
+// last two columns are separated by `; `, and only the first (preferred) name is kept
+fn parse_french_language_names(table: &Path) -> StdResult<HashMap<String, String>, String> {
+    let table_reader = BufReader::new(File::open(table).map_err(|e| {
+        format!(
+            "unable to load the french language name table, {}: {}",
+            table.as_os_str().to_string_lossy(),
+            e
+        )
+    })?);
+
+    let mut names = HashMap::new();
+    for raw_line in table_reader.lines() {
+        let raw_line = raw_line.map_err(|e| {
+            format!(
+                "unable to read the french language name table, {}: {}",
+                table.as_os_str().to_string_lossy(),
+                e
             )
-            .note(format!("{}", e))
-            .emit();
-            return None;
+        })?;
+        let line = raw_line
+            .trim_start_matches('\u{feff}')
+            .trim_end()
+            .split('|')
+            .collect::<Vec<&str>>();
+
+        // ranges of codes (such as `qaa-qtz`) are skipped along with any blank lines
+        if line.len() != 5 || line[0].len() != 3 {
+            continue;
         }
-    });
 
-    Some(
-        table_reader
-            .lines()
-            .skip(1)
-            .filter_map(|raw_line| {
-                let line = match &raw_line {
-                    Ok(s) => s,
-                    Err(_) => return None,
-                }
-                .split('\t')
-                .collect::<Vec<&str>>();
+        if let Some(name) = line[4].split("; ").next() {
+            names.insert(line[0].to_string(), name.to_string());
+        }
+    }
+    Ok(names)
+}
 
-                let mut entry = HashMap::new();
-                entry.insert(LanguageTableEntryKey::Iso639_3, line[0].to_string());
-                if line[1].len() == 3 {
-                    entry.insert(LanguageTableEntryKey::Iso639_2b, line[1].to_string());
-                }
-                if line[2].len() == 3 {
-                    entry.insert(LanguageTableEntryKey::Iso639_2t, line[2].to_string());
-                }
-                if line[3].len() == 2 {
-                    entry.insert(LanguageTableEntryKey::Iso639_1, line[3].to_string());
-                }
-                entry.insert(LanguageTableEntryKey::Name, line[6].to_string());
+/// Returns the arm a `match` over every variant of one of the code enumerations needs when it is
+/// expanded outside of the crate defining them, where they are non-exhaustive, and which may
+/// be reached there if that crate was built from a dataset with codes the macro does not know of
+fn foreign_variants() -> Option<TokenStream2> {
+    if var("CARGO_CRATE_NAME").as_deref() == Ok("iso") {
+        None
+    } else {
+        Some(quote! {
+            _ => panic!("the code is not within the dataset the `match` was generated from"),
+        })
+    }
+}
 
-                Some(entry)
-            })
-            .collect(),
-    )
+fn manifest_directory() -> Result<PathBuf> {
+    var("CARGO_MANIFEST_DIR").map(PathBuf::from).map_err(|e| {
+        Error::new(
+            Span::call_site(),
+            format!("unable to locate the crate's manifest directory: {}", e),
+        )
+    })
 }
 
-fn parse_language_table_from_environment() -> Option<Vec<HashMap<LanguageTableEntryKey, String>>> {
-    let mut language_table_path = PathBuf::from(var("CARGO_MANIFEST_DIR").unwrap());
-    language_table_path.push("assets/language.tab");
-    parse_language_table(&language_table_path)
+fn parse_language_table_from_environment(
+    manifest_directory: &Path,
+) -> StdResult<Vec<HashMap<LanguageTableEntryKey, String>>, String> {
+    let mut table = parse_language_table(&Dataset::LanguageTable.path(manifest_directory))?;
+    let french_names =
+        parse_french_language_names(&manifest_directory.join("assets/language_fr.txt"))?;
+    for entry in &mut table {
+        if let Some(name) = entry
+            .get(&LanguageTableEntryKey::Iso639_2b)
+            .and_then(|code| french_names.get(code))
+        {
+            entry.insert(LanguageTableEntryKey::NameFr, subset::name(name));
+        }
+    }
+    Ok(table)
 }
 
-fn parse_country_codes_from_environment() -> Option<Vec<CountryEntry>> {
-    let mut country_codes_path = PathBuf::from(var("CARGO_MANIFEST_DIR").unwrap());
-    country_codes_path.push("assets/country.json");
-    parse_country_codes(&country_codes_path)
+fn parse_country_codes_from_environment(
+    manifest_directory: &Path,
+) -> StdResult<Vec<CountryEntry>, String> {
+    let mut country_codes = parse_country_codes(&Dataset::CountryCodes.path(manifest_directory))?;
+    let mut french_names =
+        parse_french_country_names(&manifest_directory.join("assets/country_fr.tab"))?;
+    for country in &mut country_codes {
+        country.name_fr = match french_names.remove(&country.alpha_2) {
+            Some(name) => subset::name(&name),
+
+            // a replacement for the country code dataset may well contain countries the french
+            // country name dataset has yet to catch up with, which fall back to their english name
+            None if Dataset::CountryCodes.replacement().is_some() => country.name.clone(),
+            None => {
+                return Err(format!(
+                    "the french country name dataset has no name for {}",
+                    country.alpha_2
+                ))
+            }
+        };
+    }
+    Ok(country_codes)
 }
 
 // note: the second parameter of each tuple is `true` if a string or integer is being worked with
 struct GenerationInput<K>
 where
     K: TryFrom<String>,
-    K::Error: Debug,
+    K::Error: Display,
 {
     enumeration: Option<String>,
     match_against: Option<TokenStream2>,
+    table: Option<(Visibility, Ident)>,
     lhs: (K, bool),
+    lhs_span: Span,
     rhs: Option<(K, bool)>,
+    rhs_span: Span,
+    columns: Vec<((K, bool), Span)>,
+}
+
+impl<K> GenerationInput<K>
+where
+    K: TryFrom<String>,
+    K::Error: Display,
+{
+    /// Parses a key, given either as an identifier or as a string literal
+    fn parse_key(input: ParseStream) -> Result<((K, bool), Span)> {
+        let key = input.lookahead1();
+        let (key, literal, span) = if key.peek(Ident) {
+            let key = input.parse::<Ident>()?;
+            (key.to_string(), false, key.span())
+        } else if key.peek(LitStr) {
+            let key = input.parse::<LitStr>()?;
+            (key.value(), true, key.span())
+        } else {
+            return Err(key.error());
+        };
+
+        match K::try_from(key.clone()) {
+            Ok(key) => Ok(((key, literal), span)),
+            Err(e) => Err(Error::new(
+                span,
+                format!("`{}` is not a valid key: {}", key, e),
+            )),
+        }
+    }
 }
 
 impl<K> Parse for GenerationInput<K>
 where
     K: TryFrom<String>,
-    K::Error: Debug,
+    K::Error: Display,
 {
     fn parse(input: ParseStream) -> Result<Self> {
+        let table = if input.peek(Token![static]) || input.peek(Token![pub]) {
+            let visibility = input.parse::<Visibility>()?;
+            input.parse::<Token![static]>()?;
+            let name = input.parse::<Ident>()?;
+            input.parse::<Token![:]>()?;
+            Some((visibility, name))
+        } else {
+            None
+        };
         let keyword = input.lookahead1();
-        let enumeration = if keyword.peek(Token![enum]) {
+        let enumeration = if table.is_none() && keyword.peek(Token![enum]) {
             input.parse::<Token![enum]>()?;
             let enumeration_name = input.parse::<Ident>()?.to_string();
             input.parse::<Token![:]>()?;
@@ -288,7 +530,7 @@ where
         } else {
             None
         };
-        let match_against = if keyword.peek(Token![match]) {
+        let match_against = if table.is_none() && keyword.peek(Token![match]) {
             input.parse::<Token![match]>()?;
             let match_against = input.lookahead1();
             let match_against = if match_against.peek(Token![&]) {
@@ -298,47 +540,52 @@ where
             } else if match_against.peek(Ident) {
                 Some(input.parse::<Ident>()?.to_token_stream())
             } else {
-                None
+                return Err(match_against.error());
             };
             input.parse::<Token![:]>()?;
             match_against
         } else {
             None
         };
-        let lhs = input.lookahead1();
-        let lhs = if lhs.peek(Ident) {
-            (
-                input.parse::<Ident>()?.to_string().try_into().unwrap(),
-                false,
-            )
-        } else if lhs.peek(LitStr) {
-            (input.parse::<LitStr>()?.value().try_into().unwrap(), true)
-        } else {
-            return Err(lhs.error());
-        };
-        let token = input.lookahead1();
-        let rhs = if token.peek(Token![=>]) {
+        if table.is_none() && enumeration.is_none() && match_against.is_none() {
+            return Err(keyword.error());
+        }
+        let (lhs, lhs_span) = Self::parse_key(input)?;
+        let mut columns = Vec::new();
+        if table.is_some() {
             input.parse::<Token![=>]>()?;
-            let rhs = input.lookahead1();
-            Some(if rhs.peek(Ident) {
-                (
-                    input.parse::<Ident>()?.to_string().try_into().unwrap(),
-                    false,
-                )
-            } else if rhs.peek(LitStr) {
-                (input.parse::<LitStr>()?.value().try_into().unwrap(), true)
-            } else {
-                return Err(rhs.error());
-            })
+            let column_input;
+            bracketed!(column_input in input);
+            while !column_input.is_empty() {
+                columns.push(Self::parse_key(&column_input)?);
+                if !column_input.is_empty() {
+                    column_input.parse::<Token![,]>()?;
+                }
+            }
+            if columns.is_empty() {
+                return Err(Error::new(lhs_span, "at least one column must be provided"));
+            }
+        }
+        let (rhs, rhs_span) = if table.is_none() && input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            let (rhs, rhs_span) = Self::parse_key(input)?;
+            (Some(rhs), rhs_span)
         } else {
-            None
+            (None, lhs_span)
         };
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after the keys"));
+        }
 
         Ok(GenerationInput {
             enumeration,
             match_against,
+            table,
             lhs,
+            lhs_span,
             rhs,
+            rhs_span,
+            columns,
         })
     }
 }
@@ -352,131 +599,230 @@ fn ascii_formatter(string: &mut str) {
     }
 }
 
+/// Returns the name of the enumeration a key corresponds to, as an identifier
+fn enumeration_path<K>(key: &K, span: Span) -> Result<Ident>
+where
+    K: TryInto<&'static str, Error = &'static str> + Clone,
+{
+    key.clone()
+        .try_into()
+        .map(|path| Ident::new(path, Span::call_site()))
+        .map_err(|_| Error::new(span, "only codes can be used as an identifier"))
+}
+
+/// The value a row of a generated table is sorted by
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    Numeric(u16),
+    String(String),
+}
+
+/// A cell of a generated `static` table
+enum Cell {
+    Value(TokenStream2),
+    Name(String),
+}
+
+/// Generates a `static` table from rows of cells, each of which may be absent, sorted by their
+/// keys. The first cell of each row is its key, which is never absent. Each column is given a type
+/// alongside whether or not its cells may be absent, and every column which may be (or which has an
+/// absent cell) is made into an `Option` of its type, so that the type of a column does not depend
+/// on which rows are compiled in
+///
+/// When names are packed, every column of names is instead made into a column of packed names,
+/// and the string they are packed into is generated alongside the table as a `&str` named after it
+/// with a `_NAMES` suffix
+fn generate_static_table(
+    visibility: Visibility,
+    name: Ident,
+    mut rows: Vec<(SortKey, Vec<Option<Cell>>)>,
+    value_types: Vec<(TokenStream2, bool)>,
+    span: Span,
+) -> Result<TokenStream2> {
+    rows.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let packed_names = if packed::is_enabled() {
+        let names = rows.iter().flat_map(|(_, cells)| {
+            cells.iter().filter_map(|cell| match cell {
+                Some(Cell::Name(name)) => Some(name.as_str()),
+                _ => None,
+            })
+        });
+        Some(PackedNames::new(names).map_err(|e| Error::new(span, e))?)
+    } else {
+        None
+    };
+    let packed = value_types
+        .iter()
+        .enumerate()
+        .map(|(column, _)| {
+            packed_names.is_some()
+                && rows
+                    .iter()
+                    .any(|(_, cells)| matches!(cells[column], Some(Cell::Name(_))))
+        })
+        .collect::<Vec<bool>>();
+    let (value_types, optional): (Vec<TokenStream2>, Vec<bool>) = value_types.into_iter().unzip();
+    let optional = optional
+        .into_iter()
+        .enumerate()
+        .map(|(column, optional)| {
+            !packed[column] && (optional || rows.iter().any(|(_, cells)| cells[column].is_none()))
+        })
+        .collect::<Vec<bool>>();
+    let value_types = value_types
+        .into_iter()
+        .zip(optional.iter().zip(&packed))
+        .map(|(value_type, (optional, packed))| {
+            if *packed {
+                quote! { u32 }
+            } else if *optional {
+                quote! { Option<#value_type> }
+            } else {
+                value_type
+            }
+        })
+        .collect::<Vec<TokenStream2>>();
+    let length = rows.len();
+    let rows = rows.into_iter().map(|(_, cells)| {
+        let cells = cells.into_iter().zip(optional.iter().zip(&packed)).map(
+            |(cell, (optional, packed))| {
+                let cell = match (cell, &packed_names) {
+                    (Some(Cell::Name(name)), Some(packed_names)) => {
+                        return packed_names.name(&name)
+                    }
+                    (Some(Cell::Name(name)), None) => Literal::string(&name).into_token_stream(),
+                    (Some(Cell::Value(value)), _) => value,
+                    (None, _) if *packed => return packed::absent(),
+                    (None, _) => return quote! { None },
+                };
+                if *optional {
+                    quote! { Some(#cell) }
+                } else {
+                    cell
+                }
+            },
+        );
+        quote! { (#(#cells),*) }
+    });
+    let names = packed_names.as_ref().map(|packed_names| {
+        let names_name = Ident::new(&format!("{}_NAMES", name), name.span());
+        let text = packed_names.text();
+        quote! {
+            #visibility static #names_name: &str = #text;
+        }
+    });
+
+    Ok(quote! {
+        #visibility static #name: [(#(#value_types),*); #length] = [
+            #(#rows),*
+        ];
+        #names
+    })
+}
+
 #[proc_macro]
 pub fn country_identifiers_from_table(tokens: TokenStream) -> TokenStream {
-    let country_codes = parse_country_codes_from_environment().unwrap();
+    let input = parse_macro_input!(tokens as GenerationInput<CountryIdentifierKey>);
+    TokenStream::from(generate_country_identifiers(input).unwrap_or_else(Error::into_compile_error))
+}
+
+fn generate_country_identifiers(
+    input: GenerationInput<CountryIdentifierKey>,
+) -> Result<TokenStream2> {
     let GenerationInput {
         enumeration,
         match_against,
+        table,
         lhs,
+        lhs_span,
         rhs,
-    } = parse_macro_input!(tokens as GenerationInput<CountryIdentifierKey>);
+        rhs_span,
+        columns,
+    } = input;
+    let manifest_directory = manifest_directory()?;
+    let mut country_codes = parse_country_codes_from_environment(&manifest_directory)
+        .map_err(|e| Error::new(lhs_span, e))?;
+
+    if let Some((visibility, name)) = table {
+        let mut value_types = vec![(lhs.0.value_type(lhs.1, lhs_span)?, false)];
+        for (column, span) in &columns {
+            value_types.push((column.0.value_type(column.1, *span)?, false));
+        }
+        let mut rows = Vec::new();
+        for codes in &country_codes {
+            let key = match lhs.0 {
+                CountryIdentifierKey::Numeric => {
+                    SortKey::Numeric(codes.country_code.parse().unwrap_or_default())
+                }
+                _ => SortKey::String(codes.string(&lhs.0).to_string()),
+            };
+            let mut cells = vec![Some(codes.cell(&lhs, lhs_span)?)];
+            for (column, span) in &columns {
+                cells.push(Some(codes.cell(column, *span)?));
+            }
+            rows.push((key, cells));
+        }
+        return generate_static_table(visibility, name, rows, value_types, lhs_span);
+    }
+
+    // the variants of an enumeration are ordered by their code, so that their discriminants index
+    // the tables generated alongside them
+    if enumeration.is_some() {
+        country_codes.sort_by(|a, b| a.string(&lhs.0).cmp(b.string(&lhs.0)));
+    }
 
     let mut rows: Vec<proc_macro2::TokenStream> = Vec::new();
     for codes in country_codes {
         match (&lhs, &rhs) {
             ((lhs_key, true), None) => {
-                let lhs = Literal::string(match &lhs_key {
-                    CountryIdentifierKey::Alpha2 => &codes.alpha_2,
-                    CountryIdentifierKey::Alpha3 => &codes.alpha_3,
-                    CountryIdentifierKey::Numeric => {
-                        panic!("numeric identifiers cannot be used alone")
-                    }
-                    CountryIdentifierKey::Name => panic!("names cannot be used alone"),
-                });
+                if let CountryIdentifierKey::Numeric
+                | CountryIdentifierKey::Name
+                | CountryIdentifierKey::NameFr = lhs_key
+                {
+                    return Err(Error::new(
+                        lhs_span,
+                        "only alpha-2 and alpha-3 codes can be used alone",
+                    ));
+                }
+                let lhs = codes.literal(lhs_key)?;
                 rows.push(quote! {
                     #lhs
                 });
             }
             ((lhs_key, false), None) => {
-                let mut lhs_string = match &lhs_key {
-                    CountryIdentifierKey::Alpha2 => codes.alpha_2,
-                    CountryIdentifierKey::Alpha3 => codes.alpha_3,
-                    CountryIdentifierKey::Numeric => {
-                        panic!("numeric identifiers cannot be used as an identifier")
-                    }
-                    CountryIdentifierKey::Name => panic!("names cannot be used as an identifier"),
-                };
-                ascii_formatter(&mut lhs_string);
-                let lhs = Ident::new(&lhs_string, Span::call_site());
+                let lhs = codes.identifier(lhs_key, lhs_span)?;
                 rows.push(quote! {
                     #lhs
                 });
             }
             ((lhs_key, true), Some((rhs_key, true))) => {
-                let lhs = match &lhs_key {
-                    CountryIdentifierKey::Alpha2 => Literal::string(&codes.alpha_2),
-                    CountryIdentifierKey::Alpha3 => Literal::string(&codes.alpha_3),
-                    CountryIdentifierKey::Numeric => Literal::u16_unsuffixed(codes.country_code.parse().unwrap()),
-                    CountryIdentifierKey::Name => Literal::string(&codes.name),
-                };
-                let rhs = match &rhs_key {
-                    CountryIdentifierKey::Alpha2 => Literal::string(&codes.alpha_2),
-                    CountryIdentifierKey::Alpha3 => Literal::string(&codes.alpha_3),
-                    CountryIdentifierKey::Numeric => Literal::u16_unsuffixed(codes.country_code.parse().unwrap()),
-                    CountryIdentifierKey::Name => Literal::string(&codes.name),
-                };
+                let lhs = codes.literal(lhs_key)?;
+                let rhs = codes.literal(rhs_key)?;
                 rows.push(quote! {
                     #lhs => #rhs
                 });
             }
             ((lhs_key, false), Some((rhs_key, true))) => {
-                let mut lhs_string = match &lhs_key {
-                    CountryIdentifierKey::Alpha2 => codes.alpha_2.clone(),
-                    CountryIdentifierKey::Alpha3 => codes.alpha_3.clone(),
-                    CountryIdentifierKey::Numeric => {
-                        panic!("numeric identifiers cannot be used as an identifier")
-                    }
-                    CountryIdentifierKey::Name => panic!("names cannot be used as an identifier"),
-                };
-                ascii_formatter(&mut lhs_string);
-                let lhs = Ident::new(&lhs_string, Span::call_site());
-                let lhs_path = Ident::new(lhs_key.clone().try_into().unwrap(), Span::call_site());
-                let rhs = match &rhs_key {
-                    CountryIdentifierKey::Alpha2 => Literal::string(&codes.alpha_2),
-                    CountryIdentifierKey::Alpha3 => Literal::string(&codes.alpha_3),
-                    CountryIdentifierKey::Numeric => Literal::u16_unsuffixed(codes.country_code.parse().unwrap()),
-                    CountryIdentifierKey::Name => Literal::string(&codes.name),
-                };
+                let lhs = codes.identifier(lhs_key, lhs_span)?;
+                let lhs_path = enumeration_path(lhs_key, lhs_span)?;
+                let rhs = codes.literal(rhs_key)?;
                 rows.push(quote! {
                     #lhs_path::#lhs => #rhs
                 });
             }
             ((lhs_key, true), Some((rhs_key, false))) => {
-                let lhs = match &lhs_key {
-                    CountryIdentifierKey::Alpha2 => Literal::string(&codes.alpha_2),
-                    CountryIdentifierKey::Alpha3 => Literal::string(&codes.alpha_3),
-                    CountryIdentifierKey::Numeric => Literal::u16_unsuffixed(codes.country_code.parse().unwrap()),
-                    CountryIdentifierKey::Name => Literal::string(&codes.name),
-                };
-                let mut rhs_string = match &rhs_key {
-                    CountryIdentifierKey::Alpha2 => codes.alpha_2.clone(),
-                    CountryIdentifierKey::Alpha3 => codes.alpha_3.clone(),
-                    CountryIdentifierKey::Numeric => {
-                        panic!("numeric identifiers cannot be used as an identifier")
-                    }
-                    CountryIdentifierKey::Name => panic!("names cannot be used as an identifier"),
-                };
-                ascii_formatter(&mut rhs_string);
-                let rhs = Ident::new(&rhs_string, Span::call_site());
-                let rhs_path = Ident::new(rhs_key.clone().try_into().unwrap(), Span::call_site());
+                let lhs = codes.literal(lhs_key)?;
+                let rhs = codes.identifier(rhs_key, rhs_span)?;
+                let rhs_path = enumeration_path(rhs_key, rhs_span)?;
                 rows.push(quote! {
                     #lhs => Some(#rhs_path::#rhs)
                 });
             }
             ((lhs_key, false), Some((rhs_key, false))) => {
-                let mut lhs_string = match &lhs_key {
-                    CountryIdentifierKey::Alpha2 => codes.alpha_2.clone(),
-                    CountryIdentifierKey::Alpha3 => codes.alpha_3.clone(),
-                    CountryIdentifierKey::Numeric => {
-                        panic!("numeric identifiers cannot be used as an identifier")
-                    }
-                    CountryIdentifierKey::Name => panic!("names cannot be used as an identifier"),
-                };
-                ascii_formatter(&mut lhs_string);
-                let lhs = Ident::new(&lhs_string, Span::call_site());
-                let lhs_path = Ident::new(lhs_key.clone().try_into().unwrap(), Span::call_site());
-                let mut rhs_string = match &rhs_key {
-                    CountryIdentifierKey::Alpha2 => codes.alpha_2.clone(),
-                    CountryIdentifierKey::Alpha3 => codes.alpha_3.clone(),
-                    CountryIdentifierKey::Numeric => {
-                        panic!("numeric identifiers cannot be used as an identifier")
-                    }
-                    CountryIdentifierKey::Name => panic!("names cannot be used as an identifier"),
-                };
-                ascii_formatter(&mut rhs_string);
-                let rhs = Ident::new(&rhs_string, Span::call_site());
-                let rhs_path = Ident::new(rhs_key.clone().try_into().unwrap(), Span::call_site());
+                let lhs = codes.identifier(lhs_key, lhs_span)?;
+                let lhs_path = enumeration_path(lhs_key, lhs_span)?;
+                let rhs = codes.identifier(rhs_key, rhs_span)?;
+                let rhs_path = enumeration_path(rhs_key, rhs_span)?;
 
                 // we don't need optionals here because there's always an alpha3 and numeric code for every alpha2 and friends
                 rows.push(quote! {
@@ -486,28 +832,39 @@ pub fn country_identifiers_from_table(tokens: TokenStream) -> TokenStream {
         }
     }
 
-    return TokenStream::from(if let Some(enumeration_name) = enumeration {
+    if let Some(enumeration_name) = enumeration {
         let enumeration_name = Ident::new(&enumeration_name, Span::call_site());
-        let iso_code = lhs.0.as_standard_code();
-        if let Some(iso_code) = iso_code {
-            quote! {
-                /// Enumeration over all possible ISO
-                #[doc = #iso_code]
-                /// country codes
-                #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-                #[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
-                #[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
-                pub enum #enumeration_name {
-                    #(#rows),*
-                }
-            }
-        } else {
-            quote! {
-                compile_error!("the selected key to generate an enumeration from does not have a corresponding iso standard")
+        let iso_code = lhs.0.as_standard_code().ok_or_else(|| {
+            Error::new(
+                lhs_span,
+                "the selected key to generate an enumeration from does not have a corresponding iso standard",
+            )
+        })?;
+        let hook = [
+            Dataset::CountryCodes.dependency_hook(&manifest_directory),
+            dataset::dependency_hook([manifest_directory.join("assets/country_fr.tab")]),
+        ];
+        Ok(quote! {
+            #(#hook)*
+
+            /// Enumeration over all possible ISO
+            #[doc = #iso_code]
+            /// country codes
+            ///
+            /// Codes are added to (and removed from) the standard over time, so the enumeration is
+            /// non-exhaustive. Wrap it within a [`Lossless`](crate::Lossless) to keep well-formed
+            /// codes it does not know of rather than reject them
+            #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+            #[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
+            #[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
+            #[non_exhaustive]
+            #[repr(u16)]
+            pub enum #enumeration_name {
+                #(#rows),*
             }
-        }
+        })
     } else if let Some(match_against) = match_against {
-        if lhs.1 {
+        Ok(if lhs.1 {
             quote! {
                 match #match_against {
                     #(#rows),*,
@@ -515,32 +872,115 @@ pub fn country_identifiers_from_table(tokens: TokenStream) -> TokenStream {
                 }
             }
         } else {
+            let foreign_variants = foreign_variants();
             quote! {
                 match #match_against {
-                    #(#rows),*
+                    #(#rows),*,
+                    #foreign_variants
                 }
             }
-        }
+        })
     } else {
-        quote! {
-            compile_error!("not enough information was provided");
-        }
-    });
+        Err(Error::new(lhs_span, "not enough information was provided"))
+    }
 }
 
 #[proc_macro]
 pub fn language_identifiers_from_table(tokens: TokenStream) -> TokenStream {
-    let table = parse_language_table_from_environment().unwrap();
+    let input = parse_macro_input!(tokens as GenerationInput<LanguageTableEntryKey>);
+    TokenStream::from(
+        generate_language_identifiers(input).unwrap_or_else(Error::into_compile_error),
+    )
+}
+
+fn generate_language_identifiers(
+    input: GenerationInput<LanguageTableEntryKey>,
+) -> Result<TokenStream2> {
     let GenerationInput {
         enumeration,
         match_against,
+        table: static_table,
         lhs,
+        lhs_span,
         rhs,
-    } = parse_macro_input!(tokens as GenerationInput<LanguageTableEntryKey>);
+        rhs_span,
+        columns,
+    } = input;
+    let manifest_directory = manifest_directory()?;
+    let mut table = parse_language_table_from_environment(&manifest_directory)
+        .map_err(|e| Error::new(lhs_span, e))?;
+
+    // only codes can be turned into a variant, so names must always be given as a literal
+    let keys = once(((&lhs.0, lhs.1), lhs_span))
+        .chain(rhs.iter().map(|(key, literal)| ((key, *literal), rhs_span)))
+        .chain(
+            columns
+                .iter()
+                .map(|((key, literal), span)| ((key, *literal), *span)),
+        );
+    for ((key, literal), span) in keys {
+        if !literal && key.as_standard_code().is_none() {
+            return Err(Error::new(span, "names cannot be used as an identifier"));
+        }
+    }
+
+    let variant = |key: &LanguageTableEntryKey, code: &str, span: Span| {
+        let mut variant = code.to_string();
+        ascii_formatter(&mut variant);
+        let variant = Ident::new(&variant, Span::call_site());
+        let path = enumeration_path(key, span)?;
+        Ok::<_, Error>(quote! { #path::#variant })
+    };
+    if let Some((visibility, name)) = static_table {
+        let value_types = once((&lhs, lhs_span))
+            .chain(columns.iter().map(|(column, span)| (column, *span)))
+            .map(|((key, literal), span)| {
+                let value_type = if *literal {
+                    quote! { &'static str }
+                } else {
+                    enumeration_path(key, span)?.into_token_stream()
+                };
+                // every row holds a value for the key it is keyed by
+                Ok((value_type, key.may_be_absent() && *key != lhs.0))
+            })
+            .collect::<Result<Vec<(TokenStream2, bool)>>>()?;
+        let mut rows = Vec::new();
+        for table_entry in &table {
+            let code = match table_entry.get(&lhs.0) {
+                Some(code) => code,
+                None => continue,
+            };
+            let mut cells = Vec::new();
+            for ((key, literal), span) in
+                once((&lhs, lhs_span)).chain(columns.iter().map(|(column, span)| (column, *span)))
+            {
+                cells.push(match (key, table_entry.get(key)) {
+                    (LanguageTableEntryKey::Name | LanguageTableEntryKey::NameFr, Some(value))
+                        if *literal =>
+                    {
+                        Some(Cell::Name(value.clone()))
+                    }
+                    (_, Some(value)) if *literal => {
+                        Some(Cell::Value(Literal::string(value).into_token_stream()))
+                    }
+                    (_, Some(value)) => Some(Cell::Value(variant(key, value, span)?)),
+                    (_, None) => None,
+                });
+            }
+            rows.push((SortKey::String(code.clone()), cells));
+        }
+        return generate_static_table(visibility, name, rows, value_types, lhs_span);
+    }
+
+    // the variants of an enumeration are ordered by their code, so that their discriminants index
+    // the tables generated alongside them
+    if enumeration.is_some() {
+        table.sort_by(|a, b| a.get(&lhs.0).cmp(&b.get(&lhs.0)));
+    }
 
     let mut rows: Vec<proc_macro2::TokenStream> = Vec::new();
     for table_entry in table {
-        if table_entry.get(&lhs.0).is_none() {
+        if !table_entry.contains_key(&lhs.0) {
             continue;
         }
         match (&lhs, &rhs) {
@@ -560,7 +1000,15 @@ pub fn language_identifiers_from_table(tokens: TokenStream) -> TokenStream {
             }
             ((lhs_table, true), Some((rhs_table, true))) => {
                 let lhs = Literal::string(&table_entry[lhs_table]);
-                let rhs = Literal::string(&table_entry[rhs_table]);
+                let rhs = Literal::string(table_entry.get(rhs_table).ok_or_else(|| {
+                    Error::new(
+                        rhs_span,
+                        format!(
+                            "`{}` has no corresponding value for this key",
+                            table_entry[lhs_table]
+                        ),
+                    )
+                })?);
                 rows.push(quote! {
                     #lhs => #rhs
                 });
@@ -569,11 +1017,26 @@ pub fn language_identifiers_from_table(tokens: TokenStream) -> TokenStream {
                 let mut lhs_string = table_entry[lhs_table].clone();
                 ascii_formatter(&mut lhs_string);
                 let lhs = Ident::new(&lhs_string, Span::call_site());
+                let lhs_path = enumeration_path(lhs_table, lhs_span)?;
 
-                // while this technically isn't safe, trying to generate a literal for a name is impossible
-                let lhs_path = Ident::new(lhs_table.clone().try_into().unwrap(), Span::call_site());
-
-                let rhs = Literal::string(&table_entry[rhs_table]);
+                // the french name is the only column that isn't known for every language
+                let rhs = match (rhs_table, table_entry.get(rhs_table)) {
+                    (LanguageTableEntryKey::NameFr, Some(rhs)) => {
+                        let rhs = Literal::string(rhs);
+                        quote! { Some(#rhs) }
+                    }
+                    (LanguageTableEntryKey::NameFr, None) => quote! { None },
+                    (_, Some(rhs)) => Literal::string(rhs).to_token_stream(),
+                    (_, None) => {
+                        return Err(Error::new(
+                            rhs_span,
+                            format!(
+                                "`{}` has no corresponding value for this key",
+                                table_entry[lhs_table]
+                            ),
+                        ))
+                    }
+                };
                 rows.push(quote! {
                     #lhs_path::#lhs => #rhs
                 })
@@ -584,10 +1047,7 @@ pub fn language_identifiers_from_table(tokens: TokenStream) -> TokenStream {
                     let mut rhs_string = rhs.clone();
                     ascii_formatter(&mut rhs_string);
                     let rhs = Ident::new(&rhs_string, Span::call_site());
-
-                    // while this technically isn't safe, trying to generate a literal for a name is impossible
-                    let rhs_path =
-                        Ident::new(rhs_table.clone().try_into().unwrap(), Span::call_site());
+                    let rhs_path = enumeration_path(rhs_table, rhs_span)?;
                     rows.push(quote! {
                         #lhs => Some(#rhs_path::#rhs)
                     })
@@ -601,17 +1061,12 @@ pub fn language_identifiers_from_table(tokens: TokenStream) -> TokenStream {
                 let mut lhs_string = table_entry[lhs_table].clone();
                 ascii_formatter(&mut lhs_string);
                 let lhs = Ident::new(&lhs_string, Span::call_site());
-
-                // while this technically isn't safe, trying to generate a literal for a name is impossible
-                let lhs_path = Ident::new(lhs_table.clone().try_into().unwrap(), Span::call_site());
+                let lhs_path = enumeration_path(lhs_table, lhs_span)?;
                 if let Some(rhs) = table_entry.get(rhs_table) {
                     let mut rhs_string = rhs.clone();
                     ascii_formatter(&mut rhs_string);
                     let rhs = Ident::new(&rhs_string, Span::call_site());
-
-                    // while this technically isn't safe, trying to generate a literal for a name is impossible
-                    let rhs_path =
-                        Ident::new(rhs_table.clone().try_into().unwrap(), Span::call_site());
+                    let rhs_path = enumeration_path(rhs_table, rhs_span)?;
                     rows.push(quote! {
                         #lhs_path::#lhs => Some(#rhs_path::#rhs)
                     })
@@ -624,28 +1079,39 @@ pub fn language_identifiers_from_table(tokens: TokenStream) -> TokenStream {
         }
     }
 
-    return TokenStream::from(if let Some(enumeration_name) = enumeration {
+    if let Some(enumeration_name) = enumeration {
         let enumeration_name = Ident::new(&enumeration_name, Span::call_site());
-        let iso_code = lhs.0.as_standard_code();
-        if let Some(iso_code) = iso_code {
-            quote! {
-                /// Enumeration over all possible ISO
-                #[doc = #iso_code]
-                /// language codes
-                #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-                #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
-                #[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
-                pub enum #enumeration_name {
-                    #(#rows),*
-                }
-            }
-        } else {
-            quote! {
-                compile_error!("the selected table column to generate an enumeration from does not have a corresponding iso standard")
+        let iso_code = lhs.0.as_standard_code().ok_or_else(|| {
+            Error::new(
+                lhs_span,
+                "the selected table column to generate an enumeration from does not have a corresponding iso standard",
+            )
+        })?;
+        let hook = [
+            Dataset::LanguageTable.dependency_hook(&manifest_directory),
+            dataset::dependency_hook([manifest_directory.join("assets/language_fr.txt")]),
+        ];
+        Ok(quote! {
+            #(#hook)*
+
+            /// Enumeration over all possible ISO
+            #[doc = #iso_code]
+            /// language codes
+            ///
+            /// Codes are added to (and removed from) the standard over time, so the enumeration is
+            /// non-exhaustive. Wrap it within a [`Lossless`](crate::Lossless) to keep well-formed
+            /// codes it does not know of rather than reject them
+            #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+            #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+            #[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
+            #[non_exhaustive]
+            #[repr(u16)]
+            pub enum #enumeration_name {
+                #(#rows),*
             }
-        }
+        })
     } else if let Some(match_against) = match_against {
-        if lhs.1 {
+        Ok(if lhs.1 {
             quote! {
                 match #match_against {
                     #(#rows),*,
@@ -653,15 +1119,79 @@ pub fn language_identifiers_from_table(tokens: TokenStream) -> TokenStream {
                 }
             }
         } else {
+            let foreign_variants = foreign_variants();
             quote! {
                 match #match_against {
-                    #(#rows),*
+                    #(#rows),*,
+                    #foreign_variants
                 }
             }
-        }
+        })
     } else {
-        quote! {
-            compile_error!("not enough information was provided");
-        }
-    });
+        Err(Error::new(lhs_span, "not enough information was provided"))
+    }
+}
+
+/// Generates either a `match` over or a `static` table of one of the supplementary datasets, which
+/// are described in the documentation of the `table` module
+#[proc_macro]
+pub fn data_from_table(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as table::TableInput);
+    TokenStream::from(
+        manifest_directory()
+            .and_then(|manifest_directory| table::generate(input, &manifest_directory))
+            .unwrap_or_else(Error::into_compile_error),
+    )
+}
+
+/// Generates either a `match` over or a `static` table of the localized names vendored from the
+/// Unicode CLDR, which are described in the documentation of the `cldr` module
+#[proc_macro]
+pub fn cldr_from_table(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as cldr::CldrInput);
+    TokenStream::from(
+        manifest_directory()
+            .and_then(|manifest_directory| cldr::generate(input, &manifest_directory))
+            .unwrap_or_else(Error::into_compile_error),
+    )
+}
+
+/// Generates the `DatasetInfo` of one of the primary datasets, which may be overridden as described
+/// in the documentation of the `dataset` module
+#[proc_macro]
+pub fn dataset_from_environment(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as dataset::DatasetInput);
+    TokenStream::from(
+        manifest_directory()
+            .and_then(|manifest_directory| dataset::generate(input, &manifest_directory))
+            .unwrap_or_else(Error::into_compile_error),
+    )
+}
+
+/// Generates a `macro_rules!` macro turning a code given as a literal into the variant it
+/// corresponds to, as described in the documentation of the `literal` module
+#[proc_macro]
+pub fn literal_macro_from_table(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as literal::LiteralInput);
+    TokenStream::from(
+        manifest_directory()
+            .and_then(|manifest_directory| literal::generate(input, &manifest_directory))
+            .unwrap_or_else(Error::into_compile_error),
+    )
+}
+
+/// Generates a `match` folding the character given as an expression into ASCII, as the
+/// `ascii-names` data subset folds names, which is described in the documentation of the `subset`
+/// module
+#[proc_macro]
+pub fn fold_from_subset(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as syn::Expr);
+    TokenStream::from(subset::generate_fold(input))
+}
+
+/// Reports a literal given to a macro generated by `literal_macro_from_table!` as an invalid code
+#[proc_macro]
+pub fn invalid_code_literal(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as literal::InvalidCodeInput);
+    TokenStream::from(literal::invalid_code(input).into_compile_error())
 }
@@ -0,0 +1,160 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Generation of the macros turning a code given as a literal into the variant it corresponds to
+//!
+//! A proc macro runs within the crate invoking it, which has no way of locating the datasets
+//! bundled with this crate, so these macros are instead generated as `macro_rules!` macros with an
+//! arm for every code. Any literal matching none of the arms is handed to `invalid_code_literal!`,
+//! which reports it as an error spanning the literal itself.
+
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::quote;
+use std::{convert::TryFrom, path::Path};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    Attribute, Error, Ident, Lit, LitStr, Token,
+};
+
+use crate::{
+    ascii_formatter, enumeration_path, parse_country_codes, parse_language_table,
+    CountryIdentifierKey, Dataset, LanguageTableEntryKey,
+};
+
+pub(crate) struct LiteralInput {
+    attributes: Vec<Attribute>,
+    name: Ident,
+    dataset: Dataset,
+    keys: Vec<Ident>,
+}
+
+impl Parse for LiteralInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
+        let name = input.parse::<Ident>()?;
+        let dataset = if name == "country" {
+            Dataset::CountryCodes
+        } else if name == "language" {
+            Dataset::LanguageTable
+        } else {
+            return Err(Error::new(
+                name.span(),
+                "expected either `country` or `language`",
+            ));
+        };
+        input.parse::<Token![:]>()?;
+        let keys = input.parse_terminated(Ident::parse, Token![,])?;
+        if keys.is_empty() {
+            return Err(Error::new(name.span(), "at least one key must be provided"));
+        }
+
+        Ok(LiteralInput {
+            attributes,
+            name,
+            dataset,
+            keys: keys.into_iter().collect(),
+        })
+    }
+}
+
+/// Parses a key given to the macro
+fn parse_key<K>(key: &Ident) -> Result<K>
+where
+    K: TryFrom<String, Error = &'static str>,
+{
+    K::try_from(key.to_string())
+        .map_err(|e| Error::new(key.span(), format!("`{}` is not a valid key: {}", key, e)))
+}
+
+pub(crate) fn generate(input: LiteralInput, manifest_directory: &Path) -> Result<TokenStream2> {
+    let LiteralInput {
+        attributes,
+        name,
+        dataset,
+        keys,
+    } = input;
+    let path = dataset.path(manifest_directory);
+
+    // each arm maps a code to its variant, given as a path relative to the crate's root
+    let mut arms = Vec::new();
+    let mut standards = Vec::new();
+    match dataset {
+        Dataset::CountryCodes => {
+            let country_codes =
+                parse_country_codes(&path).map_err(|e| Error::new(name.span(), e))?;
+            for key in &keys {
+                let parsed = parse_key::<CountryIdentifierKey>(key)?;
+                let enumeration = enumeration_path(&parsed, key.span())?;
+                standards.extend(parsed.as_standard_code());
+                for codes in &country_codes {
+                    let code = Literal::string(codes.string(&parsed));
+                    let variant = codes.identifier(&parsed, key.span())?;
+                    arms.push(quote! {
+                        (#code) => { $crate::country::#enumeration::#variant };
+                    });
+                }
+            }
+        }
+        Dataset::LanguageTable => {
+            let table = parse_language_table(&path).map_err(|e| Error::new(name.span(), e))?;
+            for key in &keys {
+                let parsed = parse_key::<LanguageTableEntryKey>(key)?;
+                let enumeration = enumeration_path(&parsed, key.span())?;
+                standards.extend(parsed.as_standard_code());
+                for code in table.iter().filter_map(|entry| entry.get(&parsed)) {
+                    let mut variant = code.clone();
+                    ascii_formatter(&mut variant);
+                    let variant = Ident::new(&variant, Span::call_site());
+                    let code = Literal::string(code);
+                    arms.push(quote! {
+                        (#code) => { $crate::language::#enumeration::#variant };
+                    });
+                }
+            }
+        }
+    }
+    let expected = format!("an ISO {} code", standards.join(" or ISO "));
+    let hook = dataset.dependency_hook(manifest_directory);
+
+    Ok(quote! {
+        #hook
+
+        #(#attributes)*
+        macro_rules! #name {
+            #(#arms)*
+            ($code:literal) => { $crate::__invalid_code_literal!(#expected, $code) };
+        }
+    })
+}
+
+pub(crate) struct InvalidCodeInput {
+    expected: LitStr,
+    code: Lit,
+}
+
+impl Parse for InvalidCodeInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let expected = input.parse::<LitStr>()?;
+        input.parse::<Token![,]>()?;
+        let code = input.parse::<Lit>()?;
+        Ok(InvalidCodeInput { expected, code })
+    }
+}
+
+pub(crate) fn invalid_code(input: InvalidCodeInput) -> Error {
+    let code = match &input.code {
+        Lit::Str(code) => code.value(),
+        code => quote!(#code).to_string(),
+    };
+    Error::new(
+        input.code.span(),
+        format!("`{}` is not {}", code, input.expected.value()),
+    )
+}
@@ -0,0 +1,195 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Packing of the names compiled into a table into a single string
+//!
+//! By default, every name is compiled in as a `&'static str` of its own, which costs a pointer and
+//! a length (and, within position-independent binaries, a relocation) for every name a table holds.
+//! When the `packed-names` feature is enabled on the crate invoking the macros (which is forwarded
+//! through the `ISO_NAME_STORAGE` environment variable by its build script), the names of each
+//! table are instead packed into one string, and each name is held as a `u32` giving its offset
+//! within the string and its length. Names which appear more than once are stored once, as are
+//! names which appear within another name (such as `Guinea` within `Equatorial Guinea`), and a name
+//! beginning with the end of the name before it shares the overlap with it. The names themselves
+//! only shrink by a few percent this way, as the saving lies in the tables no longer holding a
+//! pointer for every name (see the `packed-names` feature of the `iso` crate for measurements).
+//!
+//! A packed name holds the offset in its upper 22 bits and the length in its lower 10, and
+//! `u32::MAX` stands in for an absent name. These must be kept in step with the `packed` module of
+//! the `iso` crate, which unpacks them.
+
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use std::{cmp::Reverse, collections::HashMap, env::var, result::Result as StdResult};
+
+/// The environment variable the name storage is read from
+pub(crate) const VARIABLE: &str = "ISO_NAME_STORAGE";
+
+/// The number of bits of a packed name holding its length
+const LENGTH_BITS: u32 = 10;
+
+/// The packed name standing in for an absent name
+const ABSENT: u32 = u32::MAX;
+
+/// Returns whether or not names are to be packed
+pub(crate) fn is_enabled() -> bool {
+    var(VARIABLE).is_ok_and(|storage| storage == "packed")
+}
+
+/// The names of a table, packed into a single string
+pub(crate) struct PackedNames {
+    text: String,
+    offsets: HashMap<String, usize>,
+}
+
+impl PackedNames {
+    /// Packs the provided names
+    ///
+    /// Names found within another name are left to it, and the remainder are chained together
+    /// greedily, each followed by the name sharing the longest prefix with its suffix so that the
+    /// overlap is only stored once. Every lookup is made through a hash map of substrings or
+    /// prefixes, so packing takes time in proportion to the total length of the names multiplied
+    /// by the length of the longest one, rather than to the length of the text for every name.
+    pub(crate) fn new<'a>(names: impl IntoIterator<Item = &'a str>) -> StdResult<Self, String> {
+        let mut names = names.into_iter().collect::<Vec<&str>>();
+        names.sort_unstable();
+        names.dedup();
+        if let Some(name) = names.iter().find(|name| name.len() >= 1 << LENGTH_BITS) {
+            return Err(format!(
+                "`{}` is too long to be packed, as names may be at most {} bytes long",
+                name,
+                (1 << LENGTH_BITS) - 1
+            ));
+        }
+
+        // every name is held by the longest name it appears within, which is found by looking up
+        // each substring of the longer names (longest first, so that containers are themselves
+        // resolved to the outermost name before those within them)
+        names.sort_by_key(|name| Reverse(name.len()));
+        let index = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (*name, i))
+            .collect::<HashMap<&str, usize>>();
+        let mut container = (0..names.len()).collect::<Vec<usize>>();
+        for (i, name) in names.iter().enumerate() {
+            if container[i] != i {
+                continue;
+            }
+            for (start, _) in name.char_indices() {
+                for (end, _) in name[start..]
+                    .char_indices()
+                    .skip(1)
+                    .map(|(end, c)| (start + end, c))
+                    .chain(Some((name.len(), ' ')))
+                {
+                    if let Some(&j) = index.get(&name[start..end]) {
+                        if j != i && container[j] == j {
+                            container[j] = i;
+                        }
+                    }
+                }
+            }
+        }
+        let outermost = (0..names.len())
+            .filter(|i| container[*i] == *i)
+            .collect::<Vec<usize>>();
+
+        // each proper prefix of the outermost names, leading to the names beginning with it
+        let mut prefixes = HashMap::<&str, Vec<usize>>::new();
+        for &i in &outermost {
+            for (end, _) in names[i].char_indices().skip(1) {
+                prefixes.entry(&names[i][..end]).or_default().push(i);
+            }
+        }
+        let mut placed = vec![false; names.len()];
+        let mut offsets = vec![0; names.len()];
+        let mut text = String::new();
+        let mut next = outermost.first().copied();
+        let mut remaining = outermost.iter().copied();
+        while let Some(i) = next.take() {
+            let name = names[i];
+            placed[i] = true;
+
+            // the name overlaps with as much of the end of the text as it begins with
+            let overlap = name
+                .char_indices()
+                .skip(1)
+                .map(|(end, _)| end)
+                .filter(|end| text.ends_with(&name[..*end]))
+                .last()
+                .unwrap_or(0);
+            offsets[i] = text.len() - overlap;
+            text.push_str(&name[overlap..]);
+
+            for (start, _) in name.char_indices().skip(1) {
+                // names placed already are dropped from the candidates once they are found, so
+                // that each is only ever passed over once
+                if let Some(candidates) = prefixes.get_mut(&name[start..]) {
+                    while candidates.last().is_some_and(|j| placed[*j]) {
+                        candidates.pop();
+                    }
+                    if let Some(&j) = candidates.last() {
+                        next = Some(j);
+                        break;
+                    }
+                }
+            }
+            if next.is_none() {
+                next = remaining.find(|j| !placed[*j]);
+            }
+        }
+        if text.len() >= 1 << (32 - LENGTH_BITS) {
+            return Err(format!(
+                "the names of the table take up {} bytes, which is too many to be packed",
+                text.len()
+            ));
+        }
+
+        let offsets = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let outer = container[i];
+                let offset = offsets[outer]
+                    + names[outer]
+                        .find(name)
+                        .expect("a name is held by a name it is not within");
+                (name.to_string(), offset)
+            })
+            .collect();
+        Ok(PackedNames { text, offsets })
+    }
+
+    /// Returns the string the names are packed into, as a literal
+    pub(crate) fn text(&self) -> Literal {
+        Literal::string(&self.text)
+    }
+
+    /// Returns a name as it is held by the table
+    pub(crate) fn name(&self, name: &str) -> TokenStream2 {
+        let offset = self.offsets[name] as u32;
+        Literal::u32_suffixed((offset << LENGTH_BITS) | name.len() as u32).into_token_stream()
+    }
+
+    /// Returns an optional name as it is held by the table
+    pub(crate) fn optional_name(&self, name: Option<&str>) -> TokenStream2 {
+        match name {
+            Some(name) => self.name(name),
+            None => absent(),
+        }
+    }
+}
+
+/// Returns an absent name as it is held by a table
+pub(crate) fn absent() -> TokenStream2 {
+    let absent = Literal::u32_suffixed(ABSENT);
+    quote! { #absent }
+}
@@ -0,0 +1,211 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Selection of the subsets of the datasets which are compiled in
+//!
+//! Cargo features are not visible to proc macros, so the build script of the crate invoking them
+//! forwards the data subset features enabled on it through the `ISO_DATA_SUBSETS` environment
+//! variable, as a comma-separated list of their names. When it is unset or empty, every dataset is
+//! compiled in as it is.
+//!
+//! Each language subset keeps the languages which are a part of it, and enabling several keeps
+//! the languages which are a part of any of them. As the language table is filtered as it is
+//! parsed, every enumeration, table and macro generated from it is restricted alike. Enabling
+//! `ascii-names` folds every name (and any column of a supplementary dataset holding names) into
+//! ASCII, replacing letters bearing diacritics with their base letter. Characters which cannot be
+//! folded are replaced with `?` within names which must be present, while names which are
+//! optional are dropped instead. The same folding is generated into the crate invoking the macros
+//! by `fold_from_subset!`, so that names can be compared against those it holds.
+
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::quote;
+use std::env::var;
+use syn::Expr;
+
+/// The environment variable the enabled data subsets are read from
+pub(crate) const VARIABLE: &str = "ISO_DATA_SUBSETS";
+
+/// The subsets of the datasets which may be selected
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Subset {
+    Iso639_1Languages,
+    LivingLanguages,
+    AsciiNames,
+}
+
+impl Subset {
+    /// Returns the name of the feature selecting the subset
+    fn feature(self) -> &'static str {
+        match self {
+            Self::Iso639_1Languages => "iso639-1-languages",
+            Self::LivingLanguages => "living-languages",
+            Self::AsciiNames => "ascii-names",
+        }
+    }
+
+    fn is_enabled(self) -> bool {
+        var(VARIABLE).is_ok_and(|subsets| {
+            subsets
+                .split(',')
+                .any(|subset| subset.trim() == self.feature())
+        })
+    }
+}
+
+/// Returns whether or not any language subset is enabled, in which case the language table is
+/// restricted to the languages within the enabled subsets
+pub(crate) fn restricts_languages() -> bool {
+    Subset::Iso639_1Languages.is_enabled() || Subset::LivingLanguages.is_enabled()
+}
+
+/// Returns whether or not a language within the language table is compiled in, given whether it
+/// has an ISO 639-1 code and its language type (`L` for living languages)
+pub(crate) fn includes_language(has_iso639_1: bool, language_type: &str) -> bool {
+    !restricts_languages()
+        || (Subset::Iso639_1Languages.is_enabled() && has_iso639_1)
+        || (Subset::LivingLanguages.is_enabled() && language_type == "L")
+}
+
+/// Returns a name which must be present as it is to be compiled in
+pub(crate) fn name(name: &str) -> String {
+    if !Subset::AsciiNames.is_enabled() {
+        return name.to_string();
+    }
+    let mut folded = String::new();
+    for character in name.chars() {
+        if !fold(character, &mut folded) {
+            folded.push('?');
+        }
+    }
+    folded
+}
+
+/// Returns an optional name as it is to be compiled in, if it is to be compiled in at all
+pub(crate) fn optional_name(name: &str) -> Option<String> {
+    if !Subset::AsciiNames.is_enabled() {
+        return Some(name.to_string());
+    }
+    let mut folded = String::new();
+    for character in name.chars() {
+        if !fold(character, &mut folded) {
+            return None;
+        }
+    }
+    Some(folded)
+}
+
+/// Letters bearing diacritics (and other characters) alongside their ASCII replacements
+const FOLDS: &[(&str, &str)] = &[
+    ("ÀÁÂÃÄÅĀĂĄǍ", "A"),
+    ("àáâãäåāăąǎ", "a"),
+    ("ÇĆĈĊČ", "C"),
+    ("çćĉċč", "c"),
+    ("ĎĐ", "D"),
+    ("ďđ", "d"),
+    ("ÈÉÊËĒĔĖĘĚƏƐ", "E"),
+    ("èéêëēĕėęěəɛ", "e"),
+    ("ĜĞĠĢ", "G"),
+    ("ĝğġģ", "g"),
+    ("ĤĦḤ", "H"),
+    ("ĥħḥ", "h"),
+    ("ÌÍÎÏĨĪĬĮİƗ", "I"),
+    ("ìíîïĩīĭįıɨ", "i"),
+    ("Ĵ", "J"),
+    ("ĵ", "j"),
+    ("Ķ", "K"),
+    ("ķ", "k"),
+    ("ĹĻĽĿŁ", "L"),
+    ("ĺļľŀł", "l"),
+    ("ÑŃŅŇ", "N"),
+    ("ñńņň", "n"),
+    ("ÒÓÔÕÖØŌŎŐ", "O"),
+    ("òóôõöøōŏő", "o"),
+    ("ŔŖŘ", "R"),
+    ("ŕŗř", "r"),
+    ("ŚŜŞŠȘṢ", "S"),
+    ("śŝşšșṣ", "s"),
+    ("ŢŤŦȚṬ", "T"),
+    ("ţťŧțṭ", "t"),
+    ("ÙÚÛÜŨŪŬŮŰŲ", "U"),
+    ("ùúûüũūŭůűų", "u"),
+    ("Ŵ", "W"),
+    ("ŵ", "w"),
+    ("ÝŶŸ", "Y"),
+    ("ýÿŷ", "y"),
+    ("ŹŻŽ", "Z"),
+    ("źżž", "z"),
+    ("Ɓ", "B"),
+    ("ɓ", "b"),
+    ("Æ", "AE"),
+    ("æ", "ae"),
+    ("Œ", "OE"),
+    ("œ", "oe"),
+    ("Ŋ", "NG"),
+    ("ŋ", "ng"),
+    ("ß", "ss"),
+    ("Þ", "TH"),
+    ("þ", "th"),
+    ("Ð", "D"),
+    ("ð", "d"),
+    ("‘’ʻʼ´", "'"),
+    ("“”«»", "\""),
+    ("‐‑‒–—", "-"),
+    ("\u{a0}", " "),
+    ("ǀ", "|"),
+    ("ǁ", "||"),
+    ("ǂ", "="),
+    ("ǃ", "!"),
+];
+
+/// Pushes the ASCII replacement for a character onto a name, returning whether or not it has one
+fn fold(character: char, folded: &mut String) -> bool {
+    if character.is_ascii() {
+        folded.push(character);
+        return true;
+    }
+
+    // combining diacritical marks are dropped, leaving the letter they were applied to
+    if ('\u{300}'..='\u{36f}').contains(&character) {
+        return true;
+    }
+    match FOLDS
+        .iter()
+        .find(|(characters, _)| characters.contains(character))
+    {
+        Some((_, replacement)) => {
+            folded.push_str(replacement);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Generates a `match` over a character giving its ASCII replacement as a `&'static str`, exactly
+/// as it is folded into a name, or `None` if it has none
+pub(crate) fn generate_fold(character: Expr) -> TokenStream2 {
+    let ascii = (0..=0x7f_u8).map(|byte| {
+        let character = Literal::character(char::from(byte));
+        let replacement = Literal::string(&char::from(byte).to_string());
+        quote! { #character => Some(#replacement) }
+    });
+    let folds = FOLDS.iter().map(|(characters, replacement)| {
+        let characters = characters.chars().map(Literal::character);
+        let replacement = Literal::string(replacement);
+        quote! { #(#characters)|* => Some(#replacement) }
+    });
+    quote! {
+        match #character {
+            #(#ascii,)*
+            '\u{300}'..='\u{36f}' => Some(""),
+            #(#folds,)*
+            _ => None,
+        }
+    }
+}
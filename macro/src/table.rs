@@ -0,0 +1,830 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Code generation for the supplementary datasets keyed by a country or language code
+//!
+//! Each supplementary dataset is a tab-separated file in the `assets` directory. Lines beginning
+//! with `#` and empty lines are ignored, and the first remaining line is a header naming each
+//! column. The first column holds the key, and is named after the code set it is keyed by
+//! (`alpha-2` or `639-3`), while every other column is named in the form `name:kind`, where the
+//! kind is one of the following:
+//!
+//! - `bool`, either `yes` or `no`
+//! - `str`, a string present for every key
+//! - `str?`, an optional string, absent if the cell is empty
+//! - `[str]`, a comma-separated list of strings
+//! - `name` and `name?`, like `str` and `str?` but holding a name, which is folded into ASCII when
+//!   the `ascii-names` data subset is enabled (as described in the documentation of the `subset`
+//!   module), and which a `match` packs together with the other names of the column when the
+//!   `packed-names` feature is (as described in the documentation of the `packed` module)
+//! - `alpha-2?`, an optional ISO 3166-1 alpha-2 code
+//! - `[alpha-2]`, a comma-separated list of ISO 3166-1 alpha-2 codes
+//! - `{alpha-2}`, like `[alpha-2]` but describing a symmetric relation between the codes the table
+//!   is keyed by (such as sharing a border), so every code listed by a row must list its key in
+//!   turn
+//!
+//! When a primary dataset has been replaced (as described in the documentation of the `dataset`
+//! module), rows keyed by codes the replacement lacks are dropped, as are references to them.
+//! Codes the replacement adds are given the default value of optional and list columns, while
+//! `bool` columns fall back to `no` and `str` columns to the name the replacement gives the code.
+//! Rows keyed by languages left out of the enabled language subsets are dropped in the same way.
+
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::read_to_string,
+    path::Path,
+    result::Result as StdResult,
+};
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream, Result},
+    punctuated::Punctuated,
+    Error, Ident, LitStr, Token, Visibility,
+};
+
+use crate::{
+    ascii_formatter, dataset::dependency_hook, packed, parse_country_codes, parse_language_table,
+    subset, Dataset, LanguageTableEntryKey, PackedNames,
+};
+
+/// The code sets a supplementary dataset may be keyed by or refer to
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum CodeSet {
+    Alpha2,
+    Iso639_3,
+}
+
+impl CodeSet {
+    fn from_header(header: &str) -> Option<Self> {
+        Some(match header {
+            "alpha-2" => Self::Alpha2,
+            "639-3" => Self::Iso639_3,
+            _ => return None,
+        })
+    }
+
+    fn enumeration(self) -> Ident {
+        Ident::new(
+            match self {
+                Self::Alpha2 => "Iso3166_1_alpha_2",
+                Self::Iso639_3 => "Iso639_3",
+            },
+            Span::call_site(),
+        )
+    }
+
+    /// Returns every code within the code set, as it is written in the primary dataset
+    fn codes(self, manifest_directory: &Path) -> StdResult<HashSet<String>, String> {
+        Ok(match self {
+            Self::Alpha2 => parse_country_codes(&Dataset::CountryCodes.path(manifest_directory))?
+                .into_iter()
+                .map(|entry| entry.alpha_2)
+                .collect(),
+            Self::Iso639_3 => {
+                parse_language_table(&Dataset::LanguageTable.path(manifest_directory))?
+                    .into_iter()
+                    .filter_map(|mut entry| entry.remove(&LanguageTableEntryKey::Iso639_3))
+                    .collect()
+            }
+        })
+    }
+
+    /// Returns the name given to every code within the code set by the primary dataset
+    fn names(self, manifest_directory: &Path) -> StdResult<HashMap<String, String>, String> {
+        Ok(match self {
+            Self::Alpha2 => parse_country_codes(&Dataset::CountryCodes.path(manifest_directory))?
+                .into_iter()
+                .map(|entry| (entry.alpha_2, entry.name))
+                .collect(),
+            Self::Iso639_3 => {
+                parse_language_table(&Dataset::LanguageTable.path(manifest_directory))?
+                    .into_iter()
+                    .filter_map(|mut entry| {
+                        Some((
+                            entry.remove(&LanguageTableEntryKey::Iso639_3)?,
+                            entry.remove(&LanguageTableEntryKey::Name)?,
+                        ))
+                    })
+                    .collect()
+            }
+        })
+    }
+
+    /// Returns whether or not the codes within the code set may differ from those within the
+    /// primary dataset bundled with the crate, either as it has been replaced or as only a subset
+    /// of it is compiled in
+    fn is_pruned(self) -> bool {
+        self.is_replaced() || (self == Self::Iso639_3 && subset::restricts_languages())
+    }
+
+    /// Returns whether or not the primary dataset holding the code set has been replaced
+    fn is_replaced(self) -> bool {
+        match self {
+            Self::Alpha2 => Dataset::CountryCodes,
+            Self::Iso639_3 => Dataset::LanguageTable,
+        }
+        .replacement()
+        .is_some()
+    }
+
+    /// Returns the variant a code within the code set corresponds to
+    fn variant(self, code: &str) -> TokenStream2 {
+        let enumeration = self.enumeration();
+        let mut variant = code.to_string();
+        ascii_formatter(&mut variant);
+        let variant = Ident::new(&variant, Span::call_site());
+        quote! { #enumeration::#variant }
+    }
+}
+
+/// The kinds of value a column may hold
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum ColumnKind {
+    Bool,
+    Str,
+    OptionalStr,
+    StrList,
+    Name,
+    OptionalName,
+    OptionalCode(CodeSet),
+    CodeList(CodeSet),
+    SymmetricCodeList(CodeSet),
+}
+
+impl ColumnKind {
+    fn from_header(header: &str) -> Option<Self> {
+        Some(match header {
+            "bool" => Self::Bool,
+            "str" => Self::Str,
+            "str?" => Self::OptionalStr,
+            "[str]" => Self::StrList,
+            "name" => Self::Name,
+            "name?" => Self::OptionalName,
+            _ => {
+                if let Some(code_set) = header.strip_suffix('?') {
+                    Self::OptionalCode(CodeSet::from_header(code_set)?)
+                } else if let Some(code_set) = header.strip_prefix('{') {
+                    Self::SymmetricCodeList(CodeSet::from_header(code_set.strip_suffix('}')?)?)
+                } else {
+                    let code_set = header.strip_prefix('[')?.strip_suffix(']')?;
+                    Self::CodeList(CodeSet::from_header(code_set)?)
+                }
+            }
+        })
+    }
+
+    fn code_set(self) -> Option<CodeSet> {
+        match self {
+            Self::OptionalCode(code_set)
+            | Self::CodeList(code_set)
+            | Self::SymmetricCodeList(code_set) => Some(code_set),
+            _ => None,
+        }
+    }
+
+    fn is_name(self) -> bool {
+        matches!(self, Self::Name | Self::OptionalName)
+    }
+
+    /// Returns the name held by a cell of a kind holding names, if it holds one
+    fn name(self, cell: &str) -> Option<String> {
+        match self {
+            Self::Name => Some(subset::name(cell)),
+            Self::OptionalName => subset::optional_name(cell).filter(|name| !name.is_empty()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value used for keys without a row, if there is one
+    fn default_value(self) -> Option<TokenStream2> {
+        match self {
+            Self::Bool | Self::Str | Self::Name => None,
+            Self::OptionalStr | Self::OptionalName | Self::OptionalCode(_) => Some(quote! { None }),
+            Self::StrList | Self::CodeList(_) | Self::SymmetricCodeList(_) => Some(quote! { &[] }),
+        }
+    }
+
+    /// Returns the value used for codes a replacement for a primary dataset adds, which have no
+    /// row, for kinds without a default value. strings fall back to the name the code is given by
+    /// the primary dataset
+    fn fallback_value(self, name: &str) -> TokenStream2 {
+        match self {
+            Self::Bool => quote! { false },
+            Self::Str | Self::Name => Literal::string(name).into_token_stream(),
+            _ => self.default_value().unwrap_or_else(|| quote! { None }),
+        }
+    }
+
+    /// Returns the value held by a cell of this kind
+    fn value(self, cell: &str) -> TokenStream2 {
+        match self {
+            Self::Bool => {
+                if cell == "yes" {
+                    quote! { true }
+                } else {
+                    quote! { false }
+                }
+            }
+            Self::Str => Literal::string(cell).into_token_stream(),
+            Self::OptionalStr => {
+                if cell.is_empty() {
+                    quote! { None }
+                } else {
+                    let cell = Literal::string(cell);
+                    quote! { Some(#cell) }
+                }
+            }
+            Self::StrList => {
+                let cells = split_list(cell).map(Literal::string);
+                quote! { &[#(#cells),*] }
+            }
+            Self::Name => Literal::string(&subset::name(cell)).into_token_stream(),
+            Self::OptionalName => {
+                if let Some(name) = self.name(cell) {
+                    let name = Literal::string(&name);
+                    quote! { Some(#name) }
+                } else {
+                    quote! { None }
+                }
+            }
+            Self::OptionalCode(code_set) => {
+                if cell.is_empty() {
+                    quote! { None }
+                } else {
+                    let variant = code_set.variant(cell);
+                    quote! { Some(#variant) }
+                }
+            }
+            Self::CodeList(code_set) | Self::SymmetricCodeList(code_set) => {
+                let variants = split_list(cell).map(|code| code_set.variant(code));
+                quote! { &[#(#variants),*] }
+            }
+        }
+    }
+
+    fn value_type(self) -> TokenStream2 {
+        match self {
+            Self::Bool => quote! { bool },
+            Self::Str | Self::Name => quote! { &'static str },
+            Self::OptionalStr | Self::OptionalName => quote! { Option<&'static str> },
+            Self::StrList => quote! { &'static [&'static str] },
+            Self::OptionalCode(code_set) => {
+                let enumeration = code_set.enumeration();
+                quote! { Option<#enumeration> }
+            }
+            Self::CodeList(code_set) | Self::SymmetricCodeList(code_set) => {
+                let enumeration = code_set.enumeration();
+                quote! { &'static [#enumeration] }
+            }
+        }
+    }
+}
+
+/// A named column within a supplementary dataset
+struct Column {
+    name: String,
+    kind: ColumnKind,
+}
+
+/// A parsed supplementary dataset
+struct Table {
+    key: CodeSet,
+    columns: Vec<Column>,
+
+    /// The line number each row was found on, alongside its cells
+    rows: Vec<(usize, Vec<String>)>,
+}
+
+impl Table {
+    fn load(path: &Path) -> StdResult<Self, String> {
+        let contents = read_to_string(path).map_err(|e| {
+            format!(
+                "unable to load the dataset, {}: {}",
+                path.as_os_str().to_string_lossy(),
+                e
+            )
+        })?;
+        let mut lines = contents
+            .lines()
+            .enumerate()
+            .map(|(number, line)| (number + 1, line))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let (header_number, header) = lines
+            .next()
+            .ok_or_else(|| format!("{} has no header", path.as_os_str().to_string_lossy()))?;
+        let mut header = header.split('\t');
+        let key = header
+            .next()
+            .and_then(CodeSet::from_header)
+            .ok_or_else(|| {
+                format!(
+                    "{}:{}: the first column must be named `alpha-2` or `639-3`",
+                    path.as_os_str().to_string_lossy(),
+                    header_number
+                )
+            })?;
+        let columns = header
+            .map(|column| {
+                let (name, kind) = column.split_once(':').ok_or_else(|| {
+                    format!(
+                        "{}:{}: the column `{}` has no kind",
+                        path.as_os_str().to_string_lossy(),
+                        header_number,
+                        column
+                    )
+                })?;
+                Ok(Column {
+                    name: name.to_string(),
+                    kind: ColumnKind::from_header(kind).ok_or_else(|| {
+                        format!(
+                            "{}:{}: `{}` is not a valid column kind",
+                            path.as_os_str().to_string_lossy(),
+                            header_number,
+                            kind
+                        )
+                    })?,
+                })
+            })
+            .collect::<StdResult<Vec<Column>, String>>()?;
+        if let Some(column) = columns.iter().find(|column| {
+            matches!(column.kind, ColumnKind::SymmetricCodeList(code_set) if code_set != key)
+        }) {
+            return Err(format!(
+                "{}:{}: the column `{}` must list codes of the code set the table is keyed by, as it \
+                 describes a symmetric relation between them",
+                path.as_os_str().to_string_lossy(),
+                header_number,
+                column.name
+            ));
+        }
+
+        let rows = lines
+            .map(|(number, line)| {
+                let cells = line
+                    .split('\t')
+                    .map(|cell| cell.trim().to_string())
+                    .collect::<Vec<String>>();
+                if cells.len() == columns.len() + 1 {
+                    Ok((number, cells))
+                } else {
+                    Err(format!(
+                        "{}:{}: expected {} columns, found {}",
+                        path.as_os_str().to_string_lossy(),
+                        number,
+                        columns.len() + 1,
+                        cells.len()
+                    ))
+                }
+            })
+            .collect::<StdResult<Vec<(usize, Vec<String>)>, String>>()?;
+
+        Ok(Self { key, columns, rows })
+    }
+
+    fn column(&self, name: &LitStr) -> Result<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.name == name.value())
+            .map(|index| index + 1)
+            .ok_or_else(|| {
+                Error::new(
+                    name.span(),
+                    format!("the dataset has no column named `{}`", name.value()),
+                )
+            })
+    }
+
+    /// Drops the rows keyed by, and any references to, codes which are missing from a replacement
+    /// for a primary dataset or left out of its enabled subsets, as those codes have no variant to
+    /// refer to
+    fn prune(&mut self, manifest_directory: &Path) -> StdResult<(), String> {
+        for code_set in self.code_sets() {
+            if !code_set.is_pruned() {
+                continue;
+            }
+
+            let codes = code_set.codes(manifest_directory)?;
+            if self.key == code_set {
+                self.rows.retain(|(_, cells)| codes.contains(&cells[0]));
+            }
+            for (_, cells) in &mut self.rows {
+                for (column, cell) in self.columns.iter().zip(&mut cells[1..]) {
+                    if column.kind.code_set() == Some(code_set) {
+                        *cell = split_list(cell)
+                            .filter(|code| codes.contains(*code))
+                            .collect::<Vec<&str>>()
+                            .join(",");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the code sets the table is keyed by or refers to
+    fn code_sets(&self) -> Vec<CodeSet> {
+        let mut code_sets = vec![self.key];
+        code_sets.extend(
+            self.columns
+                .iter()
+                .filter_map(|column| column.kind.code_set()),
+        );
+        code_sets
+    }
+
+    /// Checks that no key is repeated, that every boolean is either `yes` or `no`, that every code
+    /// within the table is a part of the code set it refers to and that every symmetric relation is
+    /// listed by both of the codes it relates
+    fn validate(&self, path: &Path, manifest_directory: &Path) -> StdResult<(), String> {
+        let mut keys = HashSet::new();
+        for (number, cells) in &self.rows {
+            if !keys.insert(&cells[0]) {
+                return Err(format!(
+                    "{}:{}: `{}` has already been given a row",
+                    path.as_os_str().to_string_lossy(),
+                    number,
+                    cells[0]
+                ));
+            }
+        }
+
+        for (number, cells) in &self.rows {
+            for (column, cell) in self.columns.iter().zip(&cells[1..]) {
+                if column.kind == ColumnKind::Bool && cell != "yes" && cell != "no" {
+                    return Err(format!(
+                        "{}:{}: `{}` must be either `yes` or `no`",
+                        path.as_os_str().to_string_lossy(),
+                        number,
+                        cell
+                    ));
+                }
+            }
+        }
+
+        for code_set in self.code_sets() {
+            let codes = code_set.codes(manifest_directory)?;
+            for (number, cells) in &self.rows {
+                let mut referenced = Vec::new();
+                if self.key == code_set {
+                    referenced.push(cells[0].as_str());
+                }
+                for (column, cell) in self.columns.iter().zip(&cells[1..]) {
+                    if column.kind.code_set() == Some(code_set) {
+                        referenced.extend(split_list(cell));
+                    }
+                }
+                if let Some(unknown) = referenced.into_iter().find(|code| !codes.contains(*code)) {
+                    return Err(format!(
+                        "{}:{}: `{}` is not a known code",
+                        path.as_os_str().to_string_lossy(),
+                        number,
+                        unknown
+                    ));
+                }
+            }
+        }
+
+        for (index, column) in self.columns.iter().enumerate() {
+            if !matches!(column.kind, ColumnKind::SymmetricCodeList(_)) {
+                continue;
+            }
+
+            let lists = self
+                .rows
+                .iter()
+                .map(|(_, cells)| (cells[0].as_str(), cells[index + 1].as_str()))
+                .collect::<HashMap<&str, &str>>();
+            for (number, cells) in &self.rows {
+                let key = cells[0].as_str();
+                if let Some(code) = split_list(&cells[index + 1]).find(|code| {
+                    !lists
+                        .get(code)
+                        .is_some_and(|list| split_list(list).any(|code| code == key))
+                }) {
+                    return Err(format!(
+                        "{}:{}: `{}` lists `{}` within the column `{}`, but `{}` does not list \
+                         `{}` in turn",
+                        path.as_os_str().to_string_lossy(),
+                        number,
+                        key,
+                        code,
+                        column.name,
+                        code,
+                        key
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn split_list(cell: &str) -> impl Iterator<Item = &str> {
+    cell.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+/// The shape of the code to generate from a supplementary dataset
+enum TableForm {
+    /// A `match` over the provided expression, yielding the value of a single column
+    Match(TokenStream2),
+
+    /// A `match` over the provided expression, yielding the value of the column named by the
+    /// second expression (compared ignoring ASCII case), or the default value of the columns if
+    /// there is no such column. Every column must be of the same kind, which must have a default
+    /// value
+    ColumnMatch(TokenStream2, Ident),
+
+    /// A `static` array of tuples, each containing the key followed by the requested columns
+    Static(Visibility, Ident),
+}
+
+pub(crate) struct TableInput {
+    form: TableForm,
+    dataset: LitStr,
+    columns: Vec<LitStr>,
+}
+
+impl Parse for TableInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let form = if input.peek(Token![match]) {
+            input.parse::<Token![match]>()?;
+            let mut match_against = TokenStream2::new();
+            if input.peek(Token![&]) {
+                input.parse::<Token![&]>()?.to_tokens(&mut match_against);
+            }
+            if input.peek(Token![self]) {
+                input.parse::<Token![self]>()?.to_tokens(&mut match_against);
+            } else {
+                input.parse::<Ident>()?.to_tokens(&mut match_against);
+            }
+            if input.peek(Token![in]) {
+                input.parse::<Token![in]>()?;
+                TableForm::ColumnMatch(match_against, input.parse::<Ident>()?)
+            } else {
+                TableForm::Match(match_against)
+            }
+        } else {
+            let visibility = input.parse::<Visibility>()?;
+            input.parse::<Token![static]>()?;
+            TableForm::Static(visibility, input.parse::<Ident>()?)
+        };
+        input.parse::<Token![:]>()?;
+        let dataset = input.parse::<LitStr>()?;
+        let columns = match &form {
+            TableForm::Match(_) => {
+                input.parse::<Token![=>]>()?;
+                vec![input.parse::<LitStr>()?]
+            }
+            TableForm::ColumnMatch(..) => Vec::new(),
+            TableForm::Static(..) => {
+                input.parse::<Token![=>]>()?;
+                let columns;
+                bracketed!(columns in input);
+                Punctuated::<LitStr, Token![,]>::parse_terminated(&columns)?
+                    .into_iter()
+                    .collect()
+            }
+        };
+
+        Ok(TableInput {
+            form,
+            dataset,
+            columns,
+        })
+    }
+}
+
+/// Generates a `match` over the provided expression, yielding the value of a single column of the
+/// table. Errors are reported at the provided span
+fn generate_match(
+    table: &Table,
+    column: usize,
+    match_against: &TokenStream2,
+    span: Span,
+    manifest_directory: &Path,
+) -> Result<TokenStream2> {
+    let kind = table.columns[column - 1].kind;
+    let fallback_names = if table.key.is_replaced() {
+        table
+            .key
+            .names(manifest_directory)
+            .map_err(|e| Error::new(span, e))?
+    } else {
+        HashMap::new()
+    };
+
+    // when names are packed, the `match` yields packed names instead, which are unpacked from a
+    // string generated alongside it
+    let packed_names = if packed::is_enabled() && kind.is_name() {
+        let names = table
+            .rows
+            .iter()
+            .filter_map(|(_, cells)| kind.name(&cells[column]))
+            .collect::<Vec<String>>();
+        let names = names
+            .iter()
+            .map(String::as_str)
+            .chain(fallback_names.values().map(String::as_str));
+        Some(PackedNames::new(names).map_err(|e| Error::new(span, e))?)
+    } else {
+        None
+    };
+    let value = |cell: &str| match &packed_names {
+        Some(packed_names) => packed_names.optional_name(kind.name(cell).as_deref()),
+        None => kind.value(cell),
+    };
+    let fallback_value = |name: &str| match &packed_names {
+        Some(packed_names) if kind == ColumnKind::Name => packed_names.name(name),
+        _ => kind.fallback_value(name),
+    };
+    let default = match &packed_names {
+        Some(_) => kind.default_value().map(|_| packed::absent()),
+        None => kind.default_value(),
+    };
+
+    let rows = table.rows.iter().map(|(_, cells)| {
+        let key = table.key.variant(&cells[0]);
+        let value = value(&cells[column]);
+        quote! { #key => #value }
+    });
+
+    let covered = table.rows.len();
+    let total = table
+        .key
+        .codes(manifest_directory)
+        .map_err(|e| Error::new(span, e))?
+        .len();
+    let generated = if covered >= total {
+        Ok(quote! {
+            match #match_against {
+                #(#rows),*
+            }
+        })
+    } else if let Some(default) = default {
+        Ok(quote! {
+            match #match_against {
+                #(#rows),*,
+                _ => #default,
+            }
+        })
+    } else if table.key.is_replaced() {
+        let covered = table
+            .rows
+            .iter()
+            .map(|(_, cells)| cells[0].as_str())
+            .collect::<HashSet<&str>>();
+        let fallbacks = fallback_names
+            .iter()
+            .filter(|(code, _)| !covered.contains(code.as_str()))
+            .map(|(code, name)| {
+                let key = table.key.variant(code);
+                let value = fallback_value(name);
+                quote! { #key => #value }
+            });
+        Ok(quote! {
+            match #match_against {
+                #(#rows),*,
+                #(#fallbacks),*
+            }
+        })
+    } else {
+        Err(Error::new(
+            span,
+            format!(
+                "the column `{}` must have a value for every code, but {} of {} are missing",
+                table.columns[column - 1].name,
+                total - covered,
+                total
+            ),
+        ))
+    }?;
+
+    Ok(match packed_names {
+        Some(packed_names) => {
+            let text = packed_names.text();
+            let unpack = if kind == ColumnKind::Name {
+                quote! { unpack }
+            } else {
+                quote! { unpack_optional }
+            };
+            quote! {
+                {
+                    static NAMES: &str = #text;
+                    crate::packed::#unpack(NAMES, #generated)
+                }
+            }
+        }
+        None => generated,
+    })
+}
+
+pub(crate) fn generate(input: TableInput, manifest_directory: &Path) -> Result<TokenStream2> {
+    let path = manifest_directory
+        .join("assets")
+        .join(input.dataset.value());
+    let table = Table::load(&path)
+        .and_then(|mut table| {
+            table.prune(manifest_directory)?;
+            table.validate(&path, manifest_directory)?;
+            Ok(table)
+        })
+        .map_err(|e| Error::new(input.dataset.span(), e))?;
+    let columns = input
+        .columns
+        .iter()
+        .map(|column| table.column(column))
+        .collect::<Result<Vec<usize>>>()?;
+
+    let hook = dependency_hook([&path]);
+    let generated = match &input.form {
+        TableForm::Match(match_against) => generate_match(
+            &table,
+            columns[0],
+            match_against,
+            input.columns[0].span(),
+            manifest_directory,
+        ),
+        TableForm::ColumnMatch(match_against, column_name) => {
+            let span = input.dataset.span();
+            let kind = table.columns[0].kind;
+            let default = kind
+                .default_value()
+                .filter(|_| table.columns.iter().all(|column| column.kind == kind))
+                .ok_or_else(|| {
+                    Error::new(
+                        span,
+                        "every column must be of the same kind, which must have a default value, \
+                         to be looked up by name",
+                    )
+                })?;
+            let names = table
+                .columns
+                .iter()
+                .map(|column| Literal::string(&column.name));
+            let count = table.columns.len();
+            let arms = (1..=count)
+                .map(|column| {
+                    let index = column - 1;
+                    let generated =
+                        generate_match(&table, column, match_against, span, manifest_directory)?;
+                    Ok(quote! { Some(#index) => #generated })
+                })
+                .collect::<Result<Vec<TokenStream2>>>()?;
+
+            Ok(quote! {
+                {
+                    static COLUMNS: [&str; #count] = [#(#names),*];
+                    match COLUMNS.iter().position(|name| name.eq_ignore_ascii_case(#column_name)) {
+                        #(#arms,)*
+                        _ => #default,
+                    }
+                }
+            })
+        }
+        TableForm::Static(visibility, name) => {
+            let key_type = table.key.enumeration();
+            let value_types = columns
+                .iter()
+                .map(|column| table.columns[column - 1].kind.value_type());
+            let rows = table.rows.iter().map(|(_, cells)| {
+                let key = table.key.variant(&cells[0]);
+                let values = columns
+                    .iter()
+                    .map(|column| table.columns[column - 1].kind.value(&cells[*column]));
+                quote! { (#key, #(#values),*) }
+            });
+            let length = table.rows.len();
+
+            Ok(quote! {
+                #visibility static #name: [(#key_type, #(#value_types),*); #length] = [
+                    #(#rows),*
+                ];
+            })
+        }
+    }?;
+
+    Ok(match &input.form {
+        TableForm::Match(_) | TableForm::ColumnMatch(..) => quote! {
+            {
+                #hook
+                #generated
+            }
+        },
+        TableForm::Static(..) => quote! {
+            #hook
+            #generated
+        },
+    })
+}
@@ -0,0 +1,254 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Validation of the primary datasets, run before any code is generated from them
+//!
+//! Every problem found within a dataset is collected and reported at once, each prefixed with the
+//! file, line and column it was found at. Codes which are malformed, repeated or which would become
+//! the same variant once passed through `ascii_formatter` are all rejected, as generating an
+//! enumeration from them would either fail with an unrelated error or silently produce the wrong
+//! one.
+//!
+//! This module is also compiled into the `xtask` crate, which validates updated datasets before
+//! writing them, so it must depend on nothing from this crate beyond `ascii_formatter` and the
+//! fields of `CountryEntry` that the `xtask` crate mirrors.
+
+use std::{collections::HashMap, path::Path, result::Result as StdResult};
+
+use crate::{ascii_formatter, CountryEntry};
+
+/// A location within a dataset, as a line and column (both starting from one)
+type Location = (usize, usize);
+
+/// A collection of the problems found within a dataset
+struct Report<'a> {
+    path: &'a Path,
+    problems: Vec<(Location, String)>,
+}
+
+impl<'a> Report<'a> {
+    fn new(path: &'a Path) -> Self {
+        Self {
+            path,
+            problems: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, location: Location, problem: String) {
+        self.problems.push((location, problem));
+    }
+
+    fn finish(mut self) -> StdResult<(), String> {
+        if self.problems.is_empty() {
+            return Ok(());
+        }
+
+        self.problems.sort_by_key(|(location, _)| *location);
+        let path = self.path.as_os_str().to_string_lossy();
+        Err(self
+            .problems
+            .into_iter()
+            .map(|((line, column), problem)| format!("{}:{}:{}: {}", path, line, column, problem))
+            .collect::<Vec<String>>()
+            .join("\n"))
+    }
+}
+
+/// Tracks the codes seen within a code set, reporting any which are repeated or which would
+/// collide with another once turned into a variant
+struct Codes<'a> {
+    kind: &'a str,
+    seen: HashMap<String, (String, Location)>,
+}
+
+impl<'a> Codes<'a> {
+    fn new(kind: &'a str) -> Self {
+        Self {
+            kind,
+            seen: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, code: &str, location: Location, report: &mut Report) {
+        let mut variant = code.to_string();
+        ascii_formatter(&mut variant);
+        match self.seen.get(&variant) {
+            Some((previous, (line, column))) if previous == code => report.push(
+                location,
+                format!(
+                    "the {} code `{}` was already given at {}:{}",
+                    self.kind, code, line, column
+                ),
+            ),
+            Some((previous, (line, column))) => report.push(
+                location,
+                format!(
+                    "the {} code `{}` would collide with `{}` (given at {}:{}) as the variant `{}`",
+                    self.kind, code, previous, line, column, variant
+                ),
+            ),
+            None => {
+                self.seen.insert(variant, (code.to_string(), location));
+            }
+        }
+    }
+}
+
+fn is_code(code: &str, length: usize, letter: fn(&u8) -> bool) -> bool {
+    code.len() == length && code.bytes().all(|byte| letter(&byte))
+}
+
+/// Returns the location of the value given to a field within each object of a JSON document, in
+/// the order they appear
+///
+/// This relies on the field's name (in quotes) only appearing where it is used as a key, which
+/// holds for every field of the country code dataset
+fn field_locations(contents: &str, field: &str) -> Vec<Location> {
+    let needle = format!("\"{}\"", field);
+    let mut locations = Vec::new();
+    let (mut line, mut line_start, mut scanned) = (1, 0, 0);
+    for (start, _) in contents.match_indices(&needle) {
+        let after_key = &contents[start + needle.len()..];
+        let value = start + needle.len() + after_key.len()
+            - after_key
+                .trim_start()
+                .trim_start_matches(':')
+                .trim_start()
+                .len();
+        for (offset, _) in contents[scanned..value].match_indices('\n') {
+            line += 1;
+            line_start = scanned + offset + 1;
+        }
+        scanned = value;
+        locations.push((line, contents[line_start..value].chars().count() + 1));
+    }
+    locations
+}
+
+/// Validates the entries parsed from the country code dataset against its contents
+pub(crate) fn country_codes(
+    path: &Path,
+    contents: &str,
+    entries: &[CountryEntry],
+) -> StdResult<(), String> {
+    let mut report = Report::new(path);
+    let locations = |field| {
+        let locations = field_locations(contents, field);
+        move |index: usize| locations.get(index).copied().unwrap_or((1, 1))
+    };
+    let (name_at, alpha_2_at, alpha_3_at, numeric_at) = (
+        locations("name"),
+        locations("alpha-2"),
+        locations("alpha-3"),
+        locations("country-code"),
+    );
+
+    let mut alpha_2_codes = Codes::new("alpha-2");
+    let mut alpha_3_codes = Codes::new("alpha-3");
+    let mut numeric_codes = Codes::new("numeric");
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.name.trim().is_empty() {
+            report.push(name_at(index), "the country has no name".to_string());
+        }
+
+        if is_code(&entry.alpha_2, 2, u8::is_ascii_uppercase) {
+            alpha_2_codes.insert(&entry.alpha_2, alpha_2_at(index), &mut report);
+        } else {
+            report.push(
+                alpha_2_at(index),
+                format!(
+                    "`{}` is not an alpha-2 code, which must be two uppercase letters",
+                    entry.alpha_2
+                ),
+            );
+        }
+
+        if is_code(&entry.alpha_3, 3, u8::is_ascii_uppercase) {
+            alpha_3_codes.insert(&entry.alpha_3, alpha_3_at(index), &mut report);
+        } else {
+            report.push(
+                alpha_3_at(index),
+                format!(
+                    "`{}` is not an alpha-3 code, which must be three uppercase letters",
+                    entry.alpha_3
+                ),
+            );
+        }
+
+        if is_code(&entry.country_code, 3, u8::is_ascii_digit) {
+            numeric_codes.insert(&entry.country_code, numeric_at(index), &mut report);
+        } else {
+            report.push(
+                numeric_at(index),
+                format!(
+                    "`{}` is not a numeric code, which must be three digits",
+                    entry.country_code
+                ),
+            );
+        }
+    }
+
+    report.finish()
+}
+
+/// Validates the contents of the language table
+pub(crate) fn language_table(path: &Path, contents: &str) -> StdResult<(), String> {
+    let mut report = Report::new(path);
+
+    // the columns holding codes, alongside the number of letters each code must have. codes other
+    // than those in the first column may be absent
+    let mut code_columns = [
+        (0, 3, Codes::new("639-3")),
+        (1, 3, Codes::new("639-2b")),
+        (2, 3, Codes::new("639-2t")),
+        (3, 2, Codes::new("639-1")),
+    ];
+    for (number, line) in contents.lines().enumerate().skip(1) {
+        let number = number + 1;
+        let mut cells = Vec::new();
+        let mut start = 0;
+        for cell in line.split('\t') {
+            cells.push((line[..start].chars().count() + 1, cell));
+            start += cell.len() + 1;
+        }
+        if cells.len() < 7 {
+            report.push(
+                (number, line.chars().count() + 1),
+                format!("expected at least 7 columns, found {}", cells.len()),
+            );
+            continue;
+        }
+
+        for (index, length, codes) in &mut code_columns {
+            let (column, code) = cells[*index];
+            if *index != 0 && code.is_empty() {
+                continue;
+            }
+            if is_code(code, *length, u8::is_ascii_lowercase) {
+                codes.insert(code, (number, column), &mut report);
+            } else {
+                report.push(
+                    (number, column),
+                    format!(
+                        "`{}` is not an iso {} code, which must be {} lowercase letters",
+                        code, codes.kind, length
+                    ),
+                );
+            }
+        }
+
+        let (column, name) = cells[6];
+        if name.trim().is_empty() {
+            report.push((number, column), "the language has no name".to_string());
+        }
+    }
+
+    report.finish()
+}
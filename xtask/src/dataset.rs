@@ -0,0 +1,617 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! The datasets bundled within `lib/assets`, and how each is parsed and validated
+//!
+//! The primary datasets are parsed exactly as `iso-macro` parses them, and are then checked by the
+//! same validation it runs before generating any code from them (which is shared with it), so that
+//! a dataset accepted here is one the crate can be built from.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::Path,
+    result::Result as StdResult,
+};
+
+use crate::{source, validate};
+
+/// The datasets which may be updated
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub(crate) enum Dataset {
+    CountryCodes,
+    LanguageTable,
+    FrenchCountryNames,
+    FrenchLanguageNames,
+    Cldr,
+}
+
+impl Dataset {
+    pub(crate) const ALL: [Self; 5] = [
+        Self::CountryCodes,
+        Self::LanguageTable,
+        Self::FrenchCountryNames,
+        Self::FrenchLanguageNames,
+        Self::Cldr,
+    ];
+
+    /// Returns the name the dataset is given on the command line
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::CountryCodes => "country",
+            Self::LanguageTable => "language",
+            Self::FrenchCountryNames => "country-fr",
+            Self::FrenchLanguageNames => "language-fr",
+            Self::Cldr => "cldr",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|dataset| dataset.name() == name)
+    }
+
+    /// Returns a description of the dataset
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Self::CountryCodes => "ISO 3166-1 country codes",
+            Self::LanguageTable => "ISO 639-3 code tables",
+            Self::FrenchCountryNames => "French country names",
+            Self::FrenchLanguageNames => "French language names",
+            Self::Cldr => "Unicode CLDR localized names",
+        }
+    }
+
+    /// Returns the path of the dataset (a directory, for the CLDR), relative to the asset directory
+    pub(crate) fn file(self) -> &'static str {
+        match self {
+            Self::CountryCodes => "country.json",
+            Self::LanguageTable => "language.tab",
+            Self::FrenchCountryNames => "country_fr.tab",
+            Self::FrenchLanguageNames => "language_fr.txt",
+            Self::Cldr => "cldr",
+        }
+    }
+
+    /// Returns whether or not the dataset may be downloaded from its upstream source, which the
+    /// french country names (only published through the online browsing platform) may not be
+    pub(crate) fn downloadable(self) -> bool {
+        self != Self::FrenchCountryNames
+    }
+
+    /// Returns the upstream source of the dataset. The country codes are taken from the commit the
+    /// repository is at, and the CLDR source is the root of the `cldr-json` repository
+    pub(crate) fn upstream(self) -> &'static str {
+        match self {
+            Self::CountryCodes => COUNTRY_REPOSITORY,
+            Self::LanguageTable => {
+                "https://iso639-3.sil.org/sites/iso639-3/files/downloads/iso-639-3.tab"
+            }
+            Self::FrenchCountryNames => "https://www.iso.org/obp/ui/#search/code/",
+            Self::FrenchLanguageNames => {
+                "https://www.loc.gov/standards/iso639-2/ISO-639-2_utf-8.txt"
+            }
+            Self::Cldr => "https://raw.githubusercontent.com/unicode-org/cldr-json/main",
+        }
+    }
+
+    /// Returns the licence the dataset is distributed under, as recorded within `sources.tab`
+    pub(crate) fn licence(self) -> &'static str {
+        match self {
+            Self::CountryCodes => "CC-BY-SA-4.0",
+            Self::LanguageTable => "SIL International ISO 639-3 terms of use (https://iso639-3.sil.org/code_tables/download_tables#termsofuse)",
+            Self::FrenchCountryNames => "ISO copyright, under which the codes and names of ISO 3166-1 may be used free of charge (https://www.iso.org/iso-3166-country-codes.html)",
+            Self::FrenchLanguageNames => "public domain (a work of the Library of Congress)",
+            Self::Cldr => "Unicode-3.0",
+        }
+    }
+
+    /// Returns the attribution the licence of the dataset requires, as recorded within
+    /// `sources.tab`
+    pub(crate) fn attribution(self) -> &'static str {
+        match self {
+            Self::CountryCodes => "ISO-3166-Countries-with-Regional-Codes by Luke Duncalfe",
+            Self::LanguageTable => "ISO 639-3 code tables by SIL International, the ISO 639-3 Registration Authority",
+            Self::FrenchCountryNames => "ISO 3166-1 French short names by the ISO 3166 Maintenance Agency",
+            Self::FrenchLanguageNames => "ISO 639-2 code list by the Library of Congress, the ISO 639-2 Registration Authority",
+            Self::Cldr => "Unicode Common Locale Data Repository (CLDR), copyright © 1991-2023 Unicode, Inc.",
+        }
+    }
+}
+
+/// The repository the country codes are taken from
+pub(crate) const COUNTRY_REPOSITORY: &str =
+    "https://github.com/lukes/ISO-3166-Countries-with-Regional-Codes";
+
+/// Returns the URL of the country codes at a commit of their repository
+pub(crate) fn country_codes_at(commit: &str) -> String {
+    format!(
+        "https://raw.githubusercontent.com/lukes/ISO-3166-Countries-with-Regional-Codes/{}/all/all.json",
+        commit
+    )
+}
+
+/// A structure representing ISO country code entries, as `iso-macro` parses them
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct CountryEntry {
+    pub(crate) name: String,
+    pub(crate) alpha_2: String,
+    pub(crate) alpha_3: String,
+    pub(crate) country_code: String,
+}
+
+/// An entry of the language table
+pub(crate) struct LanguageEntry {
+    pub(crate) iso639_3: String,
+    pub(crate) iso639_2b: Option<String>,
+    pub(crate) iso639_2t: Option<String>,
+    pub(crate) iso639_1: Option<String>,
+    pub(crate) scope: String,
+    pub(crate) language_type: String,
+    pub(crate) name: String,
+}
+
+/// Decodes a dataset as UTF-8
+fn text<'a>(path: &Path, contents: &'a [u8]) -> StdResult<&'a str, String> {
+    std::str::from_utf8(contents).map_err(|e| {
+        format!(
+            "{}: the dataset is not valid UTF-8: {}",
+            path.as_os_str().to_string_lossy(),
+            e
+        )
+    })
+}
+
+/// Parses and validates the country codes
+pub(crate) fn country_codes(path: &Path, contents: &[u8]) -> StdResult<Vec<CountryEntry>, String> {
+    let contents = text(path, contents)?;
+    let entries: Vec<CountryEntry> = serde_json::from_str(contents).map_err(|e| {
+        format!(
+            "{}: the dataset does not match the expected schema: {}",
+            path.as_os_str().to_string_lossy(),
+            e
+        )
+    })?;
+    validate::country_codes(path, contents, &entries)?;
+    Ok(entries)
+}
+
+/// The columns the language table must begin with, compared ignoring case and anything other than
+/// letters and digits
+const LANGUAGE_TABLE_HEADER: [&str; 7] = [
+    "id",
+    "part2b",
+    "part2t",
+    "part1",
+    "scope",
+    "languagetype",
+    "refname",
+];
+
+/// Parses and validates the language table
+pub(crate) fn language_table(
+    path: &Path,
+    contents: &[u8],
+) -> StdResult<Vec<LanguageEntry>, String> {
+    let contents = text(path, contents)?;
+    let header = contents
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split('\t')
+        .map(|column| {
+            column
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .collect::<Vec<String>>();
+    if header.len() < LANGUAGE_TABLE_HEADER.len()
+        || header
+            .iter()
+            .zip(&LANGUAGE_TABLE_HEADER)
+            .any(|(a, b)| a != b)
+    {
+        return Err(format!(
+            "{}:1:1: the header does not match the expected schema, which begins with the columns {}",
+            path.as_os_str().to_string_lossy(),
+            LANGUAGE_TABLE_HEADER.join(", ")
+        ));
+    }
+    validate::language_table(path, contents)?;
+
+    let mut entries = Vec::new();
+    let mut problems = Vec::new();
+    for (number, line) in contents.lines().enumerate().skip(1) {
+        let line = line.split('\t').collect::<Vec<&str>>();
+        let optional = |cell: &str| Some(cell.to_string()).filter(|cell| !cell.is_empty());
+
+        // the data subsets rely on these to select languages, so any value they do not know of is
+        // rejected
+        if !["I", "M", "S"].contains(&line[4]) {
+            problems.push(format!(
+                "{}:{}: `{}` is not a scope, which must be one of I, M or S",
+                path.as_os_str().to_string_lossy(),
+                number + 1,
+                line[4]
+            ));
+        }
+        if !["A", "C", "E", "H", "L", "S"].contains(&line[5]) {
+            problems.push(format!(
+                "{}:{}: `{}` is not a language type, which must be one of A, C, E, H, L or S",
+                path.as_os_str().to_string_lossy(),
+                number + 1,
+                line[5]
+            ));
+        }
+
+        entries.push(LanguageEntry {
+            iso639_3: line[0].to_string(),
+            iso639_2b: optional(line[1]),
+            iso639_2t: optional(line[2]),
+            iso639_1: optional(line[3]),
+            scope: line[4].to_string(),
+            language_type: line[5].to_string(),
+            name: line[6].to_string(),
+        });
+    }
+    if !problems.is_empty() {
+        return Err(problems.join("\n"));
+    }
+    Ok(entries)
+}
+
+/// Parses the french country names, keyed by alpha-2 code, exactly as `iso-macro` does
+pub(crate) fn french_country_names(
+    path: &Path,
+    contents: &[u8],
+) -> StdResult<BTreeMap<String, String>, String> {
+    text(path, contents)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .skip(1)
+        .map(|(index, line)| match line.split_once('\t') {
+            Some((alpha_2, name)) => Ok((alpha_2.trim().to_string(), name.trim().to_string())),
+            None => Err(format!(
+                "{}:{}: the row is not of the form `alpha-2<tab>name`",
+                path.as_os_str().to_string_lossy(),
+                index + 1
+            )),
+        })
+        .collect()
+}
+
+/// Parses the french language names, keyed by ISO 639-2b code, exactly as `iso-macro` does
+pub(crate) fn french_language_names(
+    path: &Path,
+    contents: &[u8],
+) -> StdResult<BTreeMap<String, String>, String> {
+    let names = text(path, contents)?
+        .lines()
+        .map(|line| {
+            line.trim_start_matches('\u{feff}')
+                .trim_end()
+                .split('|')
+                .collect::<Vec<&str>>()
+        })
+        .filter(|line| line.len() == 5 && line[0].len() == 3)
+        .filter_map(|line| Some((line[0].to_string(), line[4].split("; ").next()?.to_string())))
+        .collect::<BTreeMap<String, String>>();
+    if names.is_empty() {
+        return Err(format!(
+            "{}: the dataset holds no rows of the form \
+             `bibliographic|terminologic|alpha-2|english names|french names`",
+            path.as_os_str().to_string_lossy()
+        ));
+    }
+    Ok(names)
+}
+
+/// The kinds of names given by each CLDR locale
+pub(crate) const CLDR_NAMES: [&str; 2] = ["territories", "languages"];
+
+/// The path of the parent locales within the `cldr-json` repository
+pub(crate) const CLDR_PARENTS: &str = "cldr-json/cldr-core/supplemental/parentLocales.json";
+
+/// The path of the locales available within each coverage level of the `cldr-json` repository
+pub(crate) const CLDR_LOCALES: &str = "cldr-json/cldr-core/availableLocales.json";
+
+/// The path of the package manifest of the localized names within the `cldr-json` repository,
+/// which is versioned by the tag of the release it belongs to
+pub(crate) const CLDR_PACKAGE: &str = "cldr-json/cldr-localenames-full/package.json";
+
+/// Returns the path of a kind of names given by a locale within the `cldr-json` repository
+pub(crate) fn cldr_names_path(locale: &str, kind: &str) -> String {
+    format!(
+        "cldr-json/cldr-localenames-full/main/{}/{}.json",
+        locale, kind
+    )
+}
+
+/// Parses a kind of names given by a CLDR locale, keyed by their CLDR identifier, exactly as
+/// `iso-macro` does
+pub(crate) fn cldr_names(
+    path: &Path,
+    contents: &[u8],
+    locale: &str,
+    kind: &str,
+) -> StdResult<BTreeMap<String, String>, String> {
+    let document: Value = serde_json::from_slice(contents).map_err(|e| {
+        format!(
+            "{}: unable to parse the dataset: {}",
+            path.as_os_str().to_string_lossy(),
+            e
+        )
+    })?;
+    document
+        .pointer(&format!("/main/{}/localeDisplayNames/{}", locale, kind))
+        .and_then(Value::as_object)
+        .ok_or_else(|| {
+            format!(
+                "{}: the dataset does not hold the {} of `{}`",
+                path.as_os_str().to_string_lossy(),
+                kind,
+                locale
+            )
+        })?
+        .iter()
+        .map(|(key, name)| {
+            name.as_str()
+                .map(|name| (key.clone(), name.to_string()))
+                .ok_or_else(|| {
+                    format!(
+                        "{}: the name given to `{}` is not a string",
+                        path.as_os_str().to_string_lossy(),
+                        key
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Parses the CLDR parent locales, keyed by locale
+pub(crate) fn cldr_parents(
+    path: &Path,
+    contents: &[u8],
+) -> StdResult<BTreeMap<String, String>, String> {
+    let document: Value = serde_json::from_slice(contents).map_err(|e| {
+        format!(
+            "{}: unable to parse the dataset: {}",
+            path.as_os_str().to_string_lossy(),
+            e
+        )
+    })?;
+    Ok(document
+        .pointer("/supplemental/parentLocales/parentLocale")
+        .and_then(Value::as_object)
+        .ok_or_else(|| {
+            format!(
+                "{}: the dataset does not hold any parent locales",
+                path.as_os_str().to_string_lossy()
+            )
+        })?
+        .iter()
+        .filter_map(|(locale, parent)| Some((locale.clone(), parent.as_str()?.to_string())))
+        .collect())
+}
+
+/// Parses the locales of the full coverage level, which are those `cldr-localenames-full` holds
+pub(crate) fn cldr_locales(path: &str, contents: &[u8]) -> StdResult<Vec<String>, String> {
+    let document: Value = serde_json::from_slice(contents)
+        .map_err(|e| format!("{}: unable to parse the dataset: {}", path, e))?;
+    let mut locales = document
+        .pointer("/availableLocales/full")
+        .and_then(Value::as_array)
+        .ok_or_else(|| format!("{}: the dataset does not list the full locales", path))?
+        .iter()
+        .map(|locale| {
+            locale
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| format!("{}: a locale is not a string", path))
+        })
+        .collect::<StdResult<Vec<String>, String>>()?;
+    locales.sort();
+    Ok(locales)
+}
+
+/// Returns the tag of the `cldr-json` release a package manifest belongs to
+pub(crate) fn cldr_release(contents: &[u8]) -> Option<String> {
+    serde_json::from_slice::<Value>(contents)
+        .ok()?
+        .get("version")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Returns the release of the CLDR the parent locales were taken from
+pub(crate) fn cldr_version(contents: &[u8]) -> Option<String> {
+    serde_json::from_slice::<Value>(contents)
+        .ok()?
+        .pointer("/supplemental/version/_cldrVersion")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Returns the `cldr-<locale>` features of the crate for the vendored locales, each enabling the
+/// feature of its parent (or `cldr` itself, for a locale inheriting from the root locale) as the
+/// names of a locale are looked up within its parents
+pub(crate) fn cldr_features(
+    locales: &[String],
+    parents: &BTreeMap<String, String>,
+) -> Vec<(String, String)> {
+    let feature = |locale: &str| format!("cldr-{}", locale.to_lowercase());
+    locales
+        .iter()
+        .map(|locale| {
+            // a locale without an explicit parent inherits from itself without its last subtag,
+            // and any parent which is not vendored is skipped over in favour of its own
+            let mut parent = locale.clone();
+            let parent = loop {
+                parent = match parents.get(&parent) {
+                    Some(explicit) => explicit.clone(),
+                    None => match parent.rsplit_once('-') {
+                        Some((truncated, _)) => truncated.to_string(),
+                        None => break "cldr".to_string(),
+                    },
+                };
+                if parent == "root" || parent == "und" {
+                    break "cldr".to_string();
+                }
+                if locales.contains(&parent) {
+                    break feature(&parent);
+                }
+            };
+            (feature(locale), parent)
+        })
+        .collect()
+}
+
+/// The coverage of the codes by one of the supplementary datasets
+pub(crate) struct Coverage {
+    pub(crate) file: String,
+
+    /// The keys which do not correspond to a code
+    pub(crate) unknown: Vec<String>,
+
+    /// The codes referred to by a cell which do not correspond to a code, alongside the key of the
+    /// row they are referred to by
+    pub(crate) unknown_references: Vec<(String, String)>,
+
+    /// The codes which there is no row for
+    pub(crate) lacking: Vec<String>,
+
+    /// The columns which must hold a value for every code (those of the `bool`, `str` and `name`
+    /// kinds, which `iso-macro` has no default value for)
+    pub(crate) required: Vec<String>,
+}
+
+/// Returns the code set a column of a supplementary dataset refers to, given its kind
+fn referenced_code_set(kind: &str) -> Option<&str> {
+    let code_set = kind
+        .strip_suffix('?')
+        .or_else(|| kind.strip_prefix('[')?.strip_suffix(']'))
+        .or_else(|| kind.strip_prefix('{')?.strip_suffix('}'))?;
+    Some(code_set).filter(|code_set| ["alpha-2", "639-3"].contains(code_set))
+}
+
+/// Returns the keys of each supplementary dataset (the `.tab` files other than the language table)
+/// and the codes within its cells which no longer correspond to a code, alongside the codes which
+/// it lacks a row for and the columns which must hold a value for every code
+///
+/// A dataset with a column that must hold a value for every code cannot be built from while it
+/// lacks a row for any, and neither can one keyed by or referring to codes which no longer exist,
+/// while codes lacking a row within any other dataset are only worth a look.
+pub(crate) fn supplementary_coverage(
+    assets: &Path,
+    country_codes: &HashSet<String>,
+    languages: &HashSet<String>,
+) -> StdResult<Vec<Coverage>, String> {
+    let mut coverage = Vec::new();
+    let mut files = std::fs::read_dir(assets)
+        .map_err(|e| format!("unable to read {}: {}", assets.display(), e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|file| file.ends_with(".tab") && file != Dataset::LanguageTable.file())
+        .filter(|file| file != Dataset::FrenchCountryNames.file())
+        .filter(|file| file != "sources.tab")
+        .collect::<Vec<String>>();
+    files.sort();
+
+    let code_set = |name: &str| match name {
+        "alpha-2" => Some(country_codes),
+        "639-3" => Some(languages),
+        _ => None,
+    };
+    for file in files {
+        let path = assets.join(&file);
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("unable to read {}: {}", path.display(), e))?;
+        let mut lines = contents
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        let mut header = lines.next().unwrap_or_default().split('\t');
+        let codes = match header.next().and_then(code_set) {
+            Some(codes) => codes,
+            None => continue,
+        };
+        let columns = header
+            .map(|column| column.split_once(':').unwrap_or((column, "")))
+            .collect::<Vec<(&str, &str)>>();
+        let rows = lines
+            .map(|line| line.split('\t').map(str::trim).collect::<Vec<&str>>())
+            .collect::<Vec<Vec<&str>>>();
+        let keys = rows.iter().map(|cells| cells[0]).collect::<HashSet<&str>>();
+
+        let mut unknown = keys
+            .iter()
+            .filter(|key| !codes.contains(**key))
+            .map(|key| key.to_string())
+            .collect::<Vec<String>>();
+        let mut unknown_references = Vec::new();
+        for cells in &rows {
+            for ((_, kind), cell) in columns.iter().zip(&cells[1..]) {
+                if let Some(referenced) = referenced_code_set(kind).and_then(code_set) {
+                    unknown_references.extend(
+                        cell.split(',')
+                            .map(str::trim)
+                            .filter(|code| !code.is_empty() && !referenced.contains(*code))
+                            .map(|code| (cells[0].to_string(), code.to_string())),
+                    );
+                }
+            }
+        }
+        let mut lacking = codes
+            .iter()
+            .filter(|code| !keys.contains(code.as_str()))
+            .cloned()
+            .collect::<Vec<String>>();
+        let required = columns
+            .iter()
+            .filter(|(_, kind)| ["bool", "str", "name"].contains(kind))
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<String>>();
+        unknown.sort();
+        unknown_references.sort();
+        lacking.sort();
+        coverage.push(Coverage {
+            file,
+            unknown,
+            unknown_references,
+            lacking,
+            required,
+        });
+    }
+    Ok(coverage)
+}
+
+/// Returns the sources of the CLDR files of each locale, alongside their path relative to the
+/// asset directory
+pub(crate) fn cldr_files(source: &str, locales: &[String]) -> Vec<(String, String)> {
+    let mut files = vec![(
+        source::join(source, CLDR_PARENTS),
+        "cldr/parentLocales.json".to_string(),
+    )];
+    for locale in locales {
+        for kind in &CLDR_NAMES {
+            files.push((
+                source::join(source, &cldr_names_path(locale, kind)),
+                format!("cldr/{}/{}.json", locale, kind),
+            ));
+        }
+    }
+    files
+}
@@ -0,0 +1,383 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Summaries of the changes between two copies of a dataset, written as Markdown
+//!
+//! The changes to each primary dataset are sorted into the following sections, each of which is
+//! left out when it would be empty:
+//!
+//! - the variants added to and removed from each code enumeration, as removing a variant breaks
+//!   any code naming it
+//! - the codes added and removed, with a removed code and an added code taken to be the same entry
+//!   under a new code (and listed as renamed instead) when they share a numeric code (for
+//!   countries) or a name (for languages)
+//! - the names changed
+//! - the mappings changed, which are the other codes of an entry (such as the alpha-3 code of a
+//!   country) and, for languages, their scope and type
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    ascii_formatter,
+    dataset::{CountryEntry, LanguageEntry},
+};
+
+/// A section of a summary, holding a title and a list of items
+struct Section {
+    title: &'static str,
+    items: Vec<String>,
+}
+
+/// Writes sections as Markdown, returning `No changes.` when every section is empty
+fn write(sections: Vec<Section>) -> String {
+    let sections = sections
+        .into_iter()
+        .filter(|section| !section.items.is_empty())
+        .map(|section| {
+            let items = section
+                .items
+                .iter()
+                .map(|item| format!("- {}\n", item))
+                .collect::<String>();
+            format!("### {}\n\n{}", section.title, items)
+        })
+        .collect::<Vec<String>>();
+    if sections.is_empty() {
+        "No changes.\n".to_string()
+    } else {
+        sections.join("\n")
+    }
+}
+
+/// Returns the variant a code is given within an enumeration
+fn variant(code: &str) -> String {
+    let mut variant = code.to_string();
+    ascii_formatter(&mut variant);
+    variant
+}
+
+/// Returns an item describing the variants added to and removed from an enumeration, if any were
+fn enumeration_changes<'a>(
+    enumeration: &str,
+    old: impl Iterator<Item = &'a str>,
+    new: impl Iterator<Item = &'a str>,
+) -> Option<String> {
+    let old = old.map(variant).collect::<BTreeSet<String>>();
+    let new = new.map(variant).collect::<BTreeSet<String>>();
+    let list = |variants: Vec<&String>| {
+        variants
+            .iter()
+            .map(|variant| format!("`{}`", variant))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    let added = new.difference(&old).collect::<Vec<&String>>();
+    let removed = old.difference(&new).collect::<Vec<&String>>();
+    let mut changes = Vec::new();
+    if !removed.is_empty() {
+        changes.push(format!("**removed** {} (breaking)", list(removed)));
+    }
+    if !added.is_empty() {
+        changes.push(format!("added {}", list(added)));
+    }
+    if changes.is_empty() {
+        None
+    } else {
+        Some(format!("`{}`: {}", enumeration, changes.join("; ")))
+    }
+}
+
+/// Returns a description of a change to a value, if it was changed
+fn changed<T>(what: &str, old: &Option<T>, new: &Option<T>) -> Option<String>
+where
+    T: AsRef<str> + PartialEq,
+{
+    if old == new {
+        return None;
+    }
+    let value = |value: &Option<T>| match value {
+        Some(value) => format!("`{}`", value.as_ref()),
+        None => "none".to_string(),
+    };
+    Some(format!("{} {} → {}", what, value(old), value(new)))
+}
+
+/// Summarizes the changes made to the country codes
+pub(crate) fn country_codes(old: &[CountryEntry], new: &[CountryEntry]) -> String {
+    let old_entries = old
+        .iter()
+        .map(|entry| (entry.alpha_2.as_str(), entry))
+        .collect::<BTreeMap<&str, &CountryEntry>>();
+    let new_entries = new
+        .iter()
+        .map(|entry| (entry.alpha_2.as_str(), entry))
+        .collect::<BTreeMap<&str, &CountryEntry>>();
+    let describe = |entry: &CountryEntry| {
+        format!(
+            "`{}` (`{}`, {}): {}",
+            entry.alpha_2, entry.alpha_3, entry.country_code, entry.name
+        )
+    };
+
+    let mut added = new_entries
+        .iter()
+        .filter(|(code, _)| !old_entries.contains_key(*code))
+        .map(|(_, entry)| *entry)
+        .collect::<Vec<&CountryEntry>>();
+    let mut removed = Vec::new();
+    let mut renamed = Vec::new();
+    for (code, old_entry) in &old_entries {
+        if new_entries.contains_key(code) {
+            continue;
+        }
+        match added
+            .iter()
+            .position(|entry| entry.country_code == old_entry.country_code)
+        {
+            Some(index) => {
+                let new_entry = added.remove(index);
+                renamed.push(format!(
+                    "`{}` → `{}` ({}): {}",
+                    old_entry.alpha_2, new_entry.alpha_2, new_entry.country_code, new_entry.name
+                ));
+            }
+            None => removed.push(describe(old_entry)),
+        }
+    }
+
+    let mut names = Vec::new();
+    let mut mappings = Vec::new();
+    for (code, new_entry) in &new_entries {
+        let old_entry = match old_entries.get(code) {
+            Some(old_entry) => old_entry,
+            None => continue,
+        };
+        if old_entry.name != new_entry.name {
+            names.push(format!(
+                "`{}`: {} → {}",
+                code, old_entry.name, new_entry.name
+            ));
+        }
+        let changes = [
+            changed(
+                "alpha-3",
+                &Some(&old_entry.alpha_3),
+                &Some(&new_entry.alpha_3),
+            ),
+            changed(
+                "numeric",
+                &Some(&old_entry.country_code),
+                &Some(&new_entry.country_code),
+            ),
+        ]
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<String>>();
+        if !changes.is_empty() {
+            mappings.push(format!("`{}`: {}", code, changes.join("; ")));
+        }
+    }
+
+    let enumerations = [
+        enumeration_changes(
+            "Iso3166_1_alpha_2",
+            old.iter().map(|entry| entry.alpha_2.as_str()),
+            new.iter().map(|entry| entry.alpha_2.as_str()),
+        ),
+        enumeration_changes(
+            "Iso3166_1_alpha_3",
+            old.iter().map(|entry| entry.alpha_3.as_str()),
+            new.iter().map(|entry| entry.alpha_3.as_str()),
+        ),
+    ];
+    write(vec![
+        Section {
+            title: "Enumeration changes",
+            items: enumerations.iter().flatten().cloned().collect(),
+        },
+        Section {
+            title: "Codes added",
+            items: added.into_iter().map(describe).collect(),
+        },
+        Section {
+            title: "Codes removed",
+            items: removed,
+        },
+        Section {
+            title: "Codes renamed",
+            items: renamed,
+        },
+        Section {
+            title: "Names changed",
+            items: names,
+        },
+        Section {
+            title: "Mappings changed",
+            items: mappings,
+        },
+    ])
+}
+
+/// Summarizes the changes made to the language table
+pub(crate) fn language_table(old: &[LanguageEntry], new: &[LanguageEntry]) -> String {
+    let old_entries = old
+        .iter()
+        .map(|entry| (entry.iso639_3.as_str(), entry))
+        .collect::<BTreeMap<&str, &LanguageEntry>>();
+    let new_entries = new
+        .iter()
+        .map(|entry| (entry.iso639_3.as_str(), entry))
+        .collect::<BTreeMap<&str, &LanguageEntry>>();
+    let describe = |entry: &LanguageEntry| format!("`{}`: {}", entry.iso639_3, entry.name);
+
+    let mut added = new_entries
+        .iter()
+        .filter(|(code, _)| !old_entries.contains_key(*code))
+        .map(|(_, entry)| *entry)
+        .collect::<Vec<&LanguageEntry>>();
+    let mut removed = Vec::new();
+    let mut renamed = Vec::new();
+    for (code, old_entry) in &old_entries {
+        if new_entries.contains_key(code) {
+            continue;
+        }
+        match added.iter().position(|entry| entry.name == old_entry.name) {
+            Some(index) => {
+                let new_entry = added.remove(index);
+                renamed.push(format!(
+                    "`{}` → `{}`: {}",
+                    old_entry.iso639_3, new_entry.iso639_3, new_entry.name
+                ));
+            }
+            None => removed.push(describe(old_entry)),
+        }
+    }
+
+    let mut names = Vec::new();
+    let mut mappings = Vec::new();
+    for (code, new_entry) in &new_entries {
+        let old_entry = match old_entries.get(code) {
+            Some(old_entry) => old_entry,
+            None => continue,
+        };
+        if old_entry.name != new_entry.name {
+            names.push(format!(
+                "`{}`: {} → {}",
+                code, old_entry.name, new_entry.name
+            ));
+        }
+        let changes = [
+            changed("639-2b", &old_entry.iso639_2b, &new_entry.iso639_2b),
+            changed("639-2t", &old_entry.iso639_2t, &new_entry.iso639_2t),
+            changed("639-1", &old_entry.iso639_1, &new_entry.iso639_1),
+            changed("scope", &Some(&old_entry.scope), &Some(&new_entry.scope)),
+            changed(
+                "type",
+                &Some(&old_entry.language_type),
+                &Some(&new_entry.language_type),
+            ),
+        ]
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<String>>();
+        if !changes.is_empty() {
+            mappings.push(format!("`{}`: {}", code, changes.join("; ")));
+        }
+    }
+
+    let codes = |entries: &[LanguageEntry], code: fn(&LanguageEntry) -> Option<&String>| {
+        entries
+            .iter()
+            .filter_map(code)
+            .cloned()
+            .collect::<Vec<String>>()
+    };
+    let enumerations = [
+        (
+            "Iso639_3",
+            (|entry| Some(&entry.iso639_3)) as fn(&LanguageEntry) -> Option<&String>,
+        ),
+        ("Iso639_2b", |entry| entry.iso639_2b.as_ref()),
+        ("Iso639_2t", |entry| entry.iso639_2t.as_ref()),
+        ("Iso639_1", |entry| entry.iso639_1.as_ref()),
+    ]
+    .iter()
+    .filter_map(|(enumeration, code)| {
+        let (old, new) = (codes(old, *code), codes(new, *code));
+        enumeration_changes(
+            enumeration,
+            old.iter().map(String::as_str),
+            new.iter().map(String::as_str),
+        )
+    })
+    .collect();
+    write(vec![
+        Section {
+            title: "Enumeration changes",
+            items: enumerations,
+        },
+        Section {
+            title: "Codes added",
+            items: added.into_iter().map(describe).collect(),
+        },
+        Section {
+            title: "Codes removed",
+            items: removed,
+        },
+        Section {
+            title: "Codes renamed",
+            items: renamed,
+        },
+        Section {
+            title: "Names changed",
+            items: names,
+        },
+        Section {
+            title: "Mappings changed",
+            items: mappings,
+        },
+    ])
+}
+
+/// Summarizes the changes made to a set of names (or any other strings) keyed by a code, with each
+/// key prefixed by the provided string
+pub(crate) fn names(
+    prefix: &str,
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    for (key, old_name) in old {
+        match new.get(key) {
+            Some(new_name) if new_name != old_name => {
+                changes.push(format!("{}`{}`: {} → {}", prefix, key, old_name, new_name))
+            }
+            Some(_) => (),
+            None => changes.push(format!("{}`{}`: removed {}", prefix, key, old_name)),
+        }
+    }
+    for (key, new_name) in new {
+        if !old.contains_key(key) {
+            changes.push(format!("{}`{}`: added {}", prefix, key, new_name));
+        }
+    }
+    changes
+}
+
+/// Summarizes the changes made to one of the datasets of names
+pub(crate) fn name_changes(changes: Vec<String>) -> String {
+    write(vec![Section {
+        title: "Names changed",
+        items: changes,
+    }])
+}
@@ -0,0 +1,690 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Maintenance tasks for the iso crate, run with `cargo xtask`
+//!
+//! # Updating the datasets
+//!
+//! ```text
+//! cargo xtask update [--check] [--offline] [--changelog <path>] [--version <dataset>=<version>]...
+//!                    [<dataset>[=<source>]]...
+//! ```
+//!
+//! Each dataset named (`country`, `language`, `country-fr`, `language-fr` or `cldr`) is taken from
+//! its source, which is either a path or a URL and defaults to its upstream source, while naming
+//! none updates every dataset which can be downloaded from upstream. The source of the CLDR is the
+//! root of a checkout of a tagged release of the `cldr-json` repository (or a URL serving one),
+//! from which the names of every locale of its full coverage level are taken. Any locale no longer
+//! a part of it is removed, and the `cldr-<locale>` features of the crate are generated from those
+//! which are. The french country names are only published through the ISO online browsing platform,
+//! so their source is always a path to a table exported from it.
+//!
+//! Every dataset is parsed and validated exactly as `iso-macro` would before anything is written,
+//! and is then checked against the others (such as every country having a french name, every row
+//! of the supplementary datasets being keyed by and referring to codes which still exist, or every
+//! code having a row within the supplementary datasets with a column that must hold a value for
+//! each). If any problem is found, each is reported and nothing is written.
+//!
+//! A summary of the changes is then written as Markdown to the standard output (and to the path
+//! given by `--changelog`, if any), listing the codes added, removed and renamed, the names and
+//! mappings changed, and the variants added to and removed from each enumeration, with removals
+//! marked as breaking. `--check` stops there, without writing any dataset.
+//!
+//! The version recorded in `sources.tab` for the country codes and language table is the commit or
+//! the date they were retrieved from upstream on, and that of the CLDR is the tag of the release
+//! its files were taken from, unless another is given by `--version` (which is required to record
+//! one for any other dataset taken from a path). A row is added to `sources.tab` for any dataset it
+//! lacks one for. `--offline` rejects any URL, so that datasets taken from local files may be
+//! checked and written without a network connection.
+
+#![allow(clippy::cognitive_complexity)]
+#![warn(clippy::cargo_common_metadata)]
+#![warn(clippy::dbg_macro)]
+#![warn(clippy::explicit_deref_methods)]
+#![warn(clippy::filetype_is_file)]
+#![warn(clippy::imprecise_flops)]
+#![warn(clippy::large_stack_arrays)]
+#![warn(clippy::todo)]
+#![warn(clippy::unimplemented)]
+#![deny(clippy::await_holding_lock)]
+#![deny(clippy::cast_lossless)]
+#![deny(clippy::clone_on_ref_ptr)]
+#![deny(clippy::doc_markdown)]
+#![deny(clippy::empty_enums)]
+#![deny(clippy::enum_glob_use)]
+#![deny(clippy::exit)]
+#![deny(clippy::explicit_into_iter_loop)]
+#![deny(clippy::explicit_iter_loop)]
+#![deny(clippy::fallible_impl_from)]
+#![deny(clippy::inefficient_to_string)]
+#![deny(clippy::large_digit_groups)]
+#![deny(clippy::wildcard_dependencies)]
+#![deny(clippy::wildcard_imports)]
+#![deny(clippy::unused_self)]
+#![deny(clippy::single_match_else)]
+#![deny(clippy::option_option)]
+#![deny(clippy::mut_mut)]
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    env::args,
+    fs::{create_dir_all, read, read_dir, read_to_string, remove_dir_all, write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    result::Result as StdResult,
+};
+
+use dataset::{CountryEntry, Dataset};
+
+mod dataset;
+mod diff;
+mod source;
+#[path = "../../macro/src/validate.rs"]
+mod validate;
+
+/// The usage of the tool
+const USAGE: &str = "usage: cargo xtask update [--check] [--offline] [--changelog <path>] \
+                     [--version <dataset>=<version>]... [<dataset>[=<source>]]...
+
+datasets: country, language, country-fr, language-fr, cldr";
+
+/// The comment heading `sources.tab`
+const SOURCES_HEADER: &str = "\
+# provenance of the datasets bundled with the crate, kept up to date by `cargo xtask update`. the
+# version is the upstream release (or commit) each was taken from, which describes as much as is
+# known of it where it was not recorded, and each is distributed under the licence given alongside
+# the attribution it requires. the supplementary datasets are curated within this repository from
+# the sources named, and are versioned by the release of the crate they were last changed in
+";
+
+/// The options an update is run with
+#[derive(Default)]
+struct Options {
+    check: bool,
+    offline: bool,
+    changelog: Option<PathBuf>,
+    versions: BTreeMap<Dataset, String>,
+    sources: BTreeMap<Dataset, Option<String>>,
+}
+
+impl Options {
+    /// Parses the options from the arguments following `update`
+    fn parse(mut arguments: impl Iterator<Item = String>) -> StdResult<Self, String> {
+        let mut options = Self::default();
+        let dataset = |name: &str| {
+            Dataset::from_name(name).ok_or_else(|| format!("`{}` is not a dataset", name))
+        };
+
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "--check" => options.check = true,
+                "--offline" => options.offline = true,
+                "--changelog" => {
+                    let path = arguments
+                        .next()
+                        .ok_or("--changelog must be followed by a path")?;
+                    options.changelog = Some(PathBuf::from(path));
+                }
+                "--version" => {
+                    let version = arguments
+                        .next()
+                        .ok_or("--version must be followed by <dataset>=<version>")?;
+                    let (name, version) = version
+                        .split_once('=')
+                        .ok_or("--version must be followed by <dataset>=<version>")?;
+                    options.versions.insert(dataset(name)?, version.to_string());
+                }
+                argument if argument.starts_with('-') => {
+                    return Err(format!("`{}` is not an option", argument))
+                }
+                argument => {
+                    let (name, source) = match argument.split_once('=') {
+                        Some((name, source)) => (name, Some(source.to_string())),
+                        None => (argument, None),
+                    };
+                    options.sources.insert(dataset(name)?, source);
+                }
+            }
+        }
+
+        if options.sources.is_empty() {
+            if options.offline {
+                return Err(
+                    "updating every dataset from upstream is not possible while working offline, \
+                     name each dataset alongside a path to take it from instead"
+                        .to_string(),
+                );
+            }
+            options.sources = Dataset::ALL
+                .iter()
+                .filter(|dataset| dataset.downloadable())
+                .map(|dataset| (*dataset, None))
+                .collect();
+        }
+        Ok(options)
+    }
+}
+
+/// A dataset retrieved from its source, as the files to write into the asset directory
+struct Update {
+    dataset: Dataset,
+    source: String,
+    version: Option<String>,
+    files: Vec<(String, Vec<u8>)>,
+}
+
+/// Retrieves a dataset from its source
+fn retrieve(
+    options: &Options,
+    dataset: Dataset,
+    source: Option<&str>,
+) -> StdResult<Update, String> {
+    let mut version = options.versions.get(&dataset).cloned();
+    let source = match source {
+        Some(source) => source.to_string(),
+
+        // the country codes are taken from the latest commit of their repository rather than
+        // whatever its default branch holds, so that the version recorded is the one retrieved
+        None if dataset == Dataset::CountryCodes => {
+            let commit = source::latest_commit(dataset::COUNTRY_REPOSITORY)?;
+            let source = dataset::country_codes_at(&commit);
+            version.get_or_insert(commit);
+            source
+        }
+        None if !dataset.downloadable() => {
+            return Err(format!(
+                "the {} are only published through {}, which offers no download, so they must be \
+                 given as a path to a table exported from it",
+                dataset.description().to_lowercase(),
+                dataset.upstream()
+            ))
+        }
+        None => dataset.upstream().to_string(),
+    };
+
+    // sil does not name the release of the tables it serves, so the date they were retrieved on
+    // stands in for it
+    if dataset == Dataset::LanguageTable && source::is_url(&source) {
+        version.get_or_insert_with(|| format!("retrieved {}", source::today()));
+    }
+
+    // every locale of the full coverage level is vendored, and the release they belong to is
+    // recorded by the tag the package of localized names is versioned by
+    let files = if dataset == Dataset::Cldr {
+        let locales = source::join(&source, dataset::CLDR_LOCALES);
+        println!("* retrieving the cldr locales from {}", locales);
+        let locales = dataset::cldr_locales(&locales, &source::fetch(&locales, options.offline)?)?;
+        if version.is_none() {
+            let package = source::join(&source, dataset::CLDR_PACKAGE);
+            version = dataset::cldr_release(&source::fetch(&package, options.offline)?);
+        }
+        dataset::cldr_files(&source, &locales)
+    } else {
+        vec![(source.clone(), dataset.file().to_string())]
+    };
+    let files = files
+        .into_iter()
+        .map(|(file_source, file)| {
+            println!("* retrieving {} from {}", file, file_source);
+            Ok((file, source::fetch(&file_source, options.offline)?))
+        })
+        .collect::<StdResult<Vec<(String, Vec<u8>)>, String>>()?;
+
+    // every file of the cldr records the release it belongs to, so it is taken from there if the
+    // package does not give the tag of one
+    if dataset == Dataset::Cldr && version.is_none() {
+        version = files
+            .iter()
+            .find(|(file, _)| file == "cldr/parentLocales.json")
+            .and_then(|(_, contents)| dataset::cldr_version(contents));
+    }
+    Ok(Update {
+        dataset,
+        source,
+        version,
+        files,
+    })
+}
+
+/// The contents of the datasets bundled within the asset directory, with any updates applied
+struct Assets<'a> {
+    directory: &'a Path,
+    updates: &'a [Update],
+}
+
+impl Assets<'_> {
+    /// Returns the path of a file within the asset directory
+    fn path(&self, file: &str) -> PathBuf {
+        self.directory.join(file)
+    }
+
+    /// Returns the contents of a file as currently bundled
+    fn bundled(&self, file: &str) -> StdResult<Vec<u8>, String> {
+        read(self.path(file)).map_err(|e| format!("unable to read {}: {}", file, e))
+    }
+
+    /// Returns the contents of a file once the updates are applied
+    fn updated(&self, file: &str) -> StdResult<Vec<u8>, String> {
+        self.updates
+            .iter()
+            .flat_map(|update| &update.files)
+            .find(|(updated, _)| updated == file)
+            .map_or_else(|| self.bundled(file), |(_, contents)| Ok(contents.clone()))
+    }
+}
+
+/// Parses a CLDR file, keyed by locale for the parent locales and by identifier for names
+fn cldr_file(
+    path: &Path,
+    file: &str,
+    contents: &[u8],
+) -> StdResult<BTreeMap<String, String>, String> {
+    match file
+        .trim_start_matches("cldr/")
+        .trim_end_matches(".json")
+        .split_once('/')
+    {
+        Some((locale, kind)) => dataset::cldr_names(path, contents, locale, kind),
+        None => dataset::cldr_parents(path, contents),
+    }
+}
+
+/// Returns the locales vendored within the asset directory, sorted by name
+fn vendored_cldr_locales(assets: &Path) -> StdResult<Vec<String>, String> {
+    let directory = assets.join(Dataset::Cldr.file());
+    let mut locales = read_dir(&directory)
+        .map_err(|e| format!("unable to read {}: {}", directory.display(), e))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect::<Vec<String>>();
+    locales.sort();
+    Ok(locales)
+}
+
+/// Returns what the names within a CLDR file are described by within the summary
+fn cldr_prefix(file: &str) -> String {
+    match file.trim_start_matches("cldr/").split_once('/') {
+        Some((locale, kind)) => format!("`{}` {} ", locale, kind.trim_end_matches(".json")),
+        None => "parent of ".to_string(),
+    }
+}
+
+/// Summarizes the changes made by an update as Markdown, after validating the files it holds
+fn summarize(assets: &Assets, update: &Update) -> StdResult<String, String> {
+    let mut summary = format!(
+        "## {} (`{}`)\n\nTaken from {}",
+        update.dataset.description(),
+        update.dataset.file(),
+        update.source
+    );
+    if let Some(version) = &update.version {
+        summary.push_str(&format!(" ({})", version));
+    }
+    summary.push_str(".\n\n");
+
+    // locales which were not vendored before the update have no names to compare against, while
+    // those it no longer holds have every name removed
+    let mut name_changes = Vec::new();
+    let mut files = update
+        .files
+        .iter()
+        .map(|(file, contents)| (file.clone(), Some(contents)))
+        .collect::<Vec<(String, Option<&Vec<u8>>)>>();
+    if update.dataset == Dataset::Cldr {
+        files.extend(
+            dataset::cldr_files("", &vendored_cldr_locales(assets.directory)?)
+                .into_iter()
+                .filter(|(_, file)| !update.files.iter().any(|(updated, _)| updated == file))
+                .map(|(_, file)| (file, None)),
+        );
+    }
+    for (file, contents) in &files {
+        let path = assets.path(file);
+        let old = if path.exists() || update.dataset != Dataset::Cldr {
+            assets.bundled(file)?
+        } else {
+            Vec::new()
+        };
+        let contents = if let Some(contents) = contents {
+            contents
+        } else {
+            name_changes.extend(diff::names(
+                &cldr_prefix(file),
+                &cldr_file(&path, file, &old)?,
+                &BTreeMap::new(),
+            ));
+            continue;
+        };
+        match update.dataset {
+            Dataset::CountryCodes => summary.push_str(&diff::country_codes(
+                &dataset::country_codes(&path, &old)?,
+                &dataset::country_codes(&path, contents)?,
+            )),
+            Dataset::LanguageTable => summary.push_str(&diff::language_table(
+                &dataset::language_table(&path, &old)?,
+                &dataset::language_table(&path, contents)?,
+            )),
+            Dataset::FrenchCountryNames => name_changes.extend(diff::names(
+                "",
+                &dataset::french_country_names(&path, &old)?,
+                &dataset::french_country_names(&path, contents)?,
+            )),
+            Dataset::FrenchLanguageNames => name_changes.extend(diff::names(
+                "",
+                &dataset::french_language_names(&path, &old)?,
+                &dataset::french_language_names(&path, contents)?,
+            )),
+            Dataset::Cldr => name_changes.extend(diff::names(
+                &cldr_prefix(file),
+                &if old.is_empty() {
+                    BTreeMap::new()
+                } else {
+                    cldr_file(&path, file, &old)?
+                },
+                &cldr_file(&path, file, contents)?,
+            )),
+        }
+    }
+    if ![Dataset::CountryCodes, Dataset::LanguageTable].contains(&update.dataset) {
+        summary.push_str(&diff::name_changes(name_changes));
+    }
+    Ok(summary)
+}
+
+/// Checks the datasets against each other once the updates are applied, returning the problems
+/// found alongside a summary of the notes worth a look
+fn cross_check(assets: &Assets) -> StdResult<(Vec<String>, String), String> {
+    let country_file = Dataset::CountryCodes.file();
+    let language_file = Dataset::LanguageTable.file();
+    let countries =
+        dataset::country_codes(&assets.path(country_file), &assets.updated(country_file)?)?;
+    let languages =
+        dataset::language_table(&assets.path(language_file), &assets.updated(language_file)?)?;
+    let old_countries =
+        dataset::country_codes(&assets.path(country_file), &assets.bundled(country_file)?)?;
+    let old_languages =
+        dataset::language_table(&assets.path(language_file), &assets.bundled(language_file)?)?;
+
+    let mut problems = Vec::new();
+    let french_file = Dataset::FrenchCountryNames.file();
+    let french_names =
+        dataset::french_country_names(&assets.path(french_file), &assets.updated(french_file)?)?;
+    for country in &countries {
+        if !french_names.contains_key(&country.alpha_2) {
+            problems.push(format!(
+                "{}: there is no french name for `{}`, which the crate cannot be built without",
+                french_file, country.alpha_2
+            ));
+        }
+    }
+
+    // supplementary datasets are maintained by hand, so the codes which were added by the update
+    // are only worth noting as lacking a row where the dataset can do without one
+    let country_codes = countries
+        .iter()
+        .map(|entry| entry.alpha_2.clone())
+        .collect::<HashSet<String>>();
+    let language_codes = languages
+        .iter()
+        .map(|entry| entry.iso639_3.clone())
+        .collect::<HashSet<String>>();
+    let existing = old_countries
+        .iter()
+        .map(|entry| entry.alpha_2.as_str())
+        .chain(old_languages.iter().map(|entry| entry.iso639_3.as_str()))
+        .collect::<HashSet<&str>>();
+    let mut notes = String::new();
+    for coverage in
+        dataset::supplementary_coverage(assets.directory, &country_codes, &language_codes)?
+    {
+        for key in coverage.unknown {
+            problems.push(format!(
+                "{}: the row keyed by `{}` does not correspond to a code, and must be removed",
+                coverage.file, key
+            ));
+        }
+        for (key, code) in coverage.unknown_references {
+            problems.push(format!(
+                "{}: the row keyed by `{}` refers to `{}`, which does not correspond to a code",
+                coverage.file, key, code
+            ));
+        }
+        if !coverage.required.is_empty() {
+            for code in &coverage.lacking {
+                problems.push(format!(
+                    "{}: there is no row for `{}`, which the crate cannot be built without as \
+                     every code must have a value for {}",
+                    coverage.file,
+                    code,
+                    coverage
+                        .required
+                        .iter()
+                        .map(|column| format!("`{}`", column))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ));
+            }
+            continue;
+        }
+
+        let lacking = coverage
+            .lacking
+            .iter()
+            .filter(|code| !existing.contains(code.as_str()))
+            .map(|code| format!("`{}`", code))
+            .collect::<Vec<String>>();
+        if !lacking.is_empty() {
+            notes.push_str(&format!(
+                "- `{}` has no row for the added codes {}\n",
+                coverage.file,
+                lacking.join(", ")
+            ));
+        }
+    }
+    if !notes.is_empty() {
+        notes = format!("## Supplementary datasets\n\n{}", notes);
+    }
+    Ok((problems, notes))
+}
+
+/// Rewrites `sources.tab`, recording the version each updated primary dataset was taken from and
+/// adding a row for any dataset which lacks one
+fn record_versions(assets: &Path, updates: &[Update]) -> StdResult<(), String> {
+    let path = assets.join("sources.tab");
+    let contents =
+        read_to_string(&path).map_err(|e| format!("unable to read sources.tab: {}", e))?;
+    let version = |dataset: Dataset| {
+        updates
+            .iter()
+            .find(|update| update.dataset == dataset)
+            .and_then(|update| update.version.as_deref())
+    };
+    let mut recorded = Vec::new();
+    let mut sources = SOURCES_HEADER.to_string();
+    for line in contents.lines().filter(|line| !line.starts_with('#')) {
+        let mut cells = line.split('\t').collect::<Vec<&str>>();
+        if let Some(dataset) = Dataset::ALL
+            .iter()
+            .copied()
+            .find(|dataset| dataset.file() == cells[0])
+        {
+            recorded.push(dataset);
+            if let (Some(version), true) = (version(dataset), cells.len() > 2) {
+                cells[2] = version;
+            }
+        }
+        sources.push_str(&cells.join("\t"));
+        sources.push('\n');
+    }
+    for dataset in Dataset::ALL
+        .iter()
+        .filter(|dataset| !recorded.contains(dataset))
+    {
+        sources.push_str(
+            &[
+                dataset.file(),
+                dataset.upstream(),
+                version(*dataset).unwrap_or_default(),
+                dataset.licence(),
+                dataset.attribution(),
+            ]
+            .join("\t"),
+        );
+        sources.push('\n');
+    }
+    write(&path, sources).map_err(|e| format!("unable to write sources.tab: {}", e))
+}
+
+/// Removes the CLDR locales which are no longer a part of the update, and rewrites the
+/// `cldr-<locale>` features of the crate to match those which are
+fn vendor_cldr_locales(assets: &Path, update: &Update) -> StdResult<(), String> {
+    let mut locales = update
+        .files
+        .iter()
+        .filter_map(|(file, _)| Some(file.strip_prefix("cldr/")?.split_once('/')?.0.to_string()))
+        .collect::<Vec<String>>();
+    locales.dedup();
+
+    for locale in vendored_cldr_locales(assets)? {
+        if !locales.contains(&locale) {
+            let path = assets.join(Dataset::Cldr.file()).join(&locale);
+            remove_dir_all(&path)
+                .map_err(|e| format!("unable to remove {}: {}", path.display(), e))?;
+        }
+    }
+
+    let parents = update
+        .files
+        .iter()
+        .find(|(file, _)| file == "cldr/parentLocales.json")
+        .map(|(file, contents)| dataset::cldr_parents(&assets.join(file), contents))
+        .transpose()?
+        .unwrap_or_default();
+    let manifest = assets.join("../Cargo.toml");
+    let contents = read_to_string(&manifest)
+        .map_err(|e| format!("unable to read {}: {}", manifest.display(), e))?;
+
+    // the features of the locales follow the `cldr` feature itself, and are replaced as a whole
+    let mut lines = contents.lines().collect::<Vec<&str>>();
+    let start = lines
+        .iter()
+        .position(|line| *line == "cldr = []")
+        .ok_or("the crate does not have a `cldr` feature")?
+        + 1;
+    let end = start
+        + lines[start..]
+            .iter()
+            .take_while(|line| line.starts_with("cldr-"))
+            .count();
+    let features = dataset::cldr_features(&locales, &parents)
+        .into_iter()
+        .map(|(feature, parent)| format!("{} = [\"{}\"]", feature, parent))
+        .collect::<Vec<String>>();
+    lines.splice(start..end, features.iter().map(String::as_str));
+    write(&manifest, lines.join("\n") + "\n")
+        .map_err(|e| format!("unable to write {}: {}", manifest.display(), e))
+}
+
+/// Updates the datasets bundled within the asset directory
+fn update(options: &Options) -> StdResult<(), String> {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../lib/assets");
+    let updates = options
+        .sources
+        .iter()
+        .map(|(dataset, source)| retrieve(options, *dataset, source.as_deref()))
+        .collect::<StdResult<Vec<Update>, String>>()?;
+    let bundle = Assets {
+        directory: &assets,
+        updates: &updates,
+    };
+
+    let mut problems = Vec::new();
+    let mut summaries = Vec::new();
+    for update in &updates {
+        match summarize(&bundle, update) {
+            Ok(summary) => summaries.push(summary),
+            Err(problem) => problems.push(problem),
+        }
+    }
+    if problems.is_empty() {
+        let (cross_problems, notes) = cross_check(&bundle)?;
+        problems.extend(cross_problems);
+        if !notes.is_empty() {
+            summaries.push(notes);
+        }
+    }
+
+    // the summary is printed regardless, as the changes it lists often explain the problems found
+    let changelog = format!("# Dataset changes\n\n{}", summaries.join("\n"));
+    println!("\n{}", changelog);
+    if !problems.is_empty() {
+        return Err(format!(
+            "the updated datasets are not valid, so nothing was written:\n{}",
+            problems.join("\n")
+        ));
+    }
+    if let Some(path) = &options.changelog {
+        write(path, &changelog)
+            .map_err(|e| format!("unable to write {}: {}", path.display(), e))?;
+    }
+    if options.check {
+        return Ok(());
+    }
+
+    for update in &updates {
+        for (file, contents) in &update.files {
+            let path = assets.join(file);
+            if let Some(parent) = path.parent() {
+                create_dir_all(parent)
+                    .map_err(|e| format!("unable to create {}: {}", parent.display(), e))?;
+            }
+            write(&path, contents).map_err(|e| format!("unable to write {}: {}", file, e))?;
+        }
+    }
+    if let Some(update) = updates
+        .iter()
+        .find(|update| update.dataset == Dataset::Cldr)
+    {
+        vendor_cldr_locales(&assets, update)?;
+    }
+    record_versions(&assets, &updates)?;
+    println!("* wrote the updated datasets");
+    Ok(())
+}
+
+/// Formats a string the way `iso-macro` formats a code into the identifier of its variant
+fn ascii_formatter(string: &mut str) {
+    if let Some(start) = string.get_mut(0..1) {
+        start.make_ascii_uppercase();
+    }
+    if let Some(remainder) = string.get_mut(1..) {
+        remainder.make_ascii_lowercase();
+    }
+}
+
+fn main() -> ExitCode {
+    let mut arguments = args().skip(1);
+    let result = match arguments.next().as_deref() {
+        Some("update") => Options::parse(arguments).and_then(|options| update(&options)),
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("! {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
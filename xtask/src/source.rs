@@ -0,0 +1,111 @@
+//
+// iso - implementations of data types related to common iso standards
+// Copyright (c) 2021 superwhiskers <whiskerdev@protonmail.com>
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//
+
+//! Retrieval of datasets from either a local path or a URL
+//!
+//! URLs are downloaded with `curl` and repositories are queried with `git`, exactly as the shell
+//! script this replaced did, so neither needs to be reimplemented here. Neither is run when working
+//! offline, in which case only local paths may be given.
+
+use std::{
+    fs::read,
+    process::Command,
+    result::Result as StdResult,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Returns whether or not a source is a URL rather than a path
+pub(crate) fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+/// Joins a path onto a source, which may be either a directory or a URL
+pub(crate) fn join(source: &str, path: &str) -> String {
+    format!("{}/{}", source.trim_end_matches('/'), path)
+}
+
+/// Returns the contents of a source
+pub(crate) fn fetch(source: &str, offline: bool) -> StdResult<Vec<u8>, String> {
+    if !is_url(source) {
+        return read(source).map_err(|e| format!("unable to read {}: {}", source, e));
+    }
+    if offline {
+        return Err(format!(
+            "unable to download {} while working offline, give a path instead",
+            source
+        ));
+    }
+
+    let output = Command::new("curl")
+        .args([
+            "--ssl-reqd",
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--location",
+        ])
+        .arg(source)
+        .output()
+        .map_err(|e| format!("unable to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "unable to download {}: {}",
+            source,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// Returns the commit the default branch of a git repository is at
+pub(crate) fn latest_commit(repository: &str) -> StdResult<String, String> {
+    let output = Command::new("git")
+        .args(["ls-remote", repository, "HEAD"])
+        .output()
+        .map_err(|e| format!("unable to run git: {}", e))?;
+    String::from_utf8_lossy(&output.stdout)
+        .split('\t')
+        .next()
+        .filter(|commit| output.status.success() && !commit.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            format!(
+                "unable to find the latest commit of {}: {}",
+                repository,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+        })
+}
+
+/// Returns the current date (in UTC) in the form `YYYY-MM-DD`
+pub(crate) fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86400)
+        .unwrap_or_default();
+
+    // note: this converts a count of days since the epoch into a date within the proleptic
+    // gregorian calendar, counting eras of 400 years from the first of march of the year 0
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}